@@ -0,0 +1,54 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::atomic::AtomicBool;
+
+use snarkvm_dpc::{devnet::*, prelude::*};
+
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+
+#[test]
+fn test_devnet_genesis_account() {
+    // Check that the genesis coinbase record belongs to the devnet genesis account.
+    let view_key = ViewKey::from_private_key(Devnet::genesis_private_key());
+    let coinbase_transaction = Devnet::genesis_block().to_coinbase_transaction().unwrap();
+    let records = coinbase_transaction.to_decrypted_records(&view_key.into()).collect::<Vec<_>>();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].owner(), Address::from_private_key(Devnet::genesis_private_key()));
+    assert_eq!(records[0].value(), Block::<Devnet>::block_reward(0));
+}
+
+#[test]
+fn dpc_devnet_integration_test() {
+    let rng = &mut ChaChaRng::seed_from_u64(1231275789u64);
+
+    let mut ledger = Ledger::<Devnet>::new().unwrap();
+    assert_eq!(ledger.latest_block_height(), 0);
+    assert_eq!(&ledger.latest_block().unwrap(), Devnet::genesis_block());
+
+    // Mine a few blocks to a new account.
+    let recipient = Account::new(rng);
+    for block_height in 1..=3 {
+        let coinbase_record = ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        assert_eq!(ledger.latest_block_height(), block_height);
+        assert_eq!(coinbase_record.owner(), recipient.address());
+        assert_eq!(coinbase_record.value(), Block::<Devnet>::block_reward(block_height));
+    }
+
+    // Check that the difficulty target remains trivial.
+    assert_eq!(ledger.latest_block_difficulty_target().unwrap(), u64::MAX);
+}
@@ -20,9 +20,8 @@ use crate::{
     BlockError,
     BlockHeader,
     BlockTemplate,
+    GenesisBuilder,
     LedgerProof,
-    LedgerTree,
-    LedgerTreeScheme,
     Network,
     Transaction,
     Transactions,
@@ -38,7 +37,6 @@ use std::{
     io::{Read, Result as IoResult, Write},
     str::FromStr,
     sync::atomic::AtomicBool,
-    time::Instant,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -69,38 +67,7 @@ impl<N: Network> Block<N> {
 
     /// Initializes a new genesis block with one coinbase transaction.
     pub fn new_genesis<R: Rng + CryptoRng>(recipient: Address<N>, rng: &mut R) -> Result<Self> {
-        // Compute the coinbase transaction.
-        let start = Instant::now();
-        let (transaction, coinbase_record) = Transaction::new_coinbase(recipient, Self::block_reward(0), true, rng)?;
-        let transactions = Transactions::from(&[transaction])?;
-        println!("{} seconds", (Instant::now() - start).as_secs());
-
-        // Construct the genesis block header metadata.
-        let block_height = 0u32;
-        let block_timestamp = 0i64;
-        let difficulty_target = u64::MAX;
-        let cumulative_weight = 0u128;
-
-        // Construct the block template.
-        let template = BlockTemplate::new(
-            LedgerProof::<N>::default().block_hash(),
-            block_height,
-            block_timestamp,
-            difficulty_target,
-            cumulative_weight,
-            LedgerTree::<N>::new()?.root(),
-            transactions,
-            coinbase_record,
-        );
-
-        // Construct the genesis block.
-        let block = Self::mine(&template, &AtomicBool::new(false), rng)?;
-
-        // Ensure the block is valid genesis block.
-        match block.is_genesis() {
-            true => Ok(block),
-            false => Err(anyhow!("Failed to initialize a genesis block")),
-        }
+        GenesisBuilder::new().add_allocation(recipient, Self::block_reward(0)).build(rng)
     }
 
     /// Initializes a new block from a given previous hash, header, and transactions list.
@@ -112,8 +79,7 @@ impl<N: Network> Block<N> {
         assert!(!(*transactions).is_empty(), "Cannot create block with no transactions");
 
         // Compute the block hash.
-        let block_hash =
-            N::block_hash_crh().hash(&to_bytes_le![previous_block_hash, header.to_header_root()?]?)?.into();
+        let block_hash = Self::compute_block_hash(previous_block_hash, &header)?;
 
         // Construct the block.
         let block = Self { block_hash, previous_block_hash, header, transactions };
@@ -125,6 +91,11 @@ impl<N: Network> Block<N> {
         }
     }
 
+    /// Returns the block hash for the given previous block hash and block header.
+    pub fn compute_block_hash(previous_block_hash: N::BlockHash, header: &BlockHeader<N>) -> Result<N::BlockHash> {
+        Ok(N::block_hash_crh().hash(&to_bytes_le![previous_block_hash, header.to_header_root()?]?)?.into())
+    }
+
     /// Returns `true` if the block is well-formed.
     pub fn is_valid(&self) -> bool {
         // Ensure the previous block hash is well-formed.
@@ -169,6 +140,15 @@ impl<N: Network> Block<N> {
             }
         };
 
+        // Ensure the coinbase transaction has one coinbase transition, unless it allocates the starting supply.
+        if self.height() != 0u32 && coinbase_transaction.transitions().len() != 1 {
+            eprintln!(
+                "Coinbase transaction must have 1 transition, found {}",
+                coinbase_transaction.transitions().len()
+            );
+            return false;
+        }
+
         // Ensure the coinbase reward is equal to or greater than the expected block reward.
         let coinbase_reward = AleoAmount::ZERO.sub(coinbase_transaction.value_balance()); // Make it a positive number.
         let block_reward = Self::block_reward(self.height());
@@ -0,0 +1,279 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::prelude::*;
+
+use anyhow::{anyhow, Result};
+use rand::{CryptoRng, Rng};
+use std::sync::atomic::AtomicBool;
+use time::OffsetDateTime;
+
+///
+/// A builder for genesis blocks that allocate the starting supply to a list of recipients.
+/// The genesis block contains one coinbase transaction, with one coinbase transition per allocation.
+///
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"))]
+pub struct GenesisBuilder<N: Network> {
+    /// The recipients of the starting supply, and their amounts.
+    allocations: Vec<(Address<N>, AleoAmount)>,
+    /// The timestamp of the genesis block.
+    timestamp: i64,
+    /// The difficulty target of the genesis block, from which the following difficulty targets are computed.
+    difficulty_target: u64,
+    /// The visibility of the coinbase records.
+    is_public: bool,
+}
+
+impl<N: Network> GenesisBuilder<N> {
+    /// Initializes a new instance of `GenesisBuilder`, with a zero timestamp and the minimum difficulty.
+    pub fn new() -> Self {
+        Self { allocations: Vec::new(), timestamp: 0i64, difficulty_target: u64::MAX, is_public: true }
+    }
+
+    /// Adds an allocation of the given amount to the given recipient.
+    pub fn add_allocation(mut self, recipient: Address<N>, amount: AleoAmount) -> Self {
+        self.allocations.push((recipient, amount));
+        self
+    }
+
+    /// Adds the given allocations.
+    pub fn add_allocations(mut self, allocations: Vec<(Address<N>, AleoAmount)>) -> Self {
+        self.allocations.extend(allocations);
+        self
+    }
+
+    /// Sets the timestamp of the genesis block.
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the difficulty target of the genesis block.
+    pub fn difficulty_target(mut self, difficulty_target: u64) -> Self {
+        self.difficulty_target = difficulty_target;
+        self
+    }
+
+    /// Sets the visibility of the coinbase records.
+    pub fn is_public(mut self, is_public: bool) -> Self {
+        self.is_public = is_public;
+        self
+    }
+
+    /// Returns the allocations.
+    pub fn allocations(&self) -> &[(Address<N>, AleoAmount)] {
+        &self.allocations
+    }
+
+    /// Returns the maximum number of allocations, which is the number of transitions in one transaction.
+    pub fn max_allocations() -> usize {
+        N::NUM_TRANSITIONS as usize
+    }
+
+    /// Returns an error if the allocations do not add up to the starting supply, or the metadata is invalid.
+    pub fn check(&self) -> Result<()> {
+        if self.allocations.is_empty() {
+            return Err(anyhow!("The genesis block requires at least one allocation"));
+        }
+        if self.allocations.len() > Self::max_allocations() {
+            return Err(anyhow!(
+                "The genesis block has {} allocations, which exceeds the maximum of {}",
+                self.allocations.len(),
+                Self::max_allocations()
+            ));
+        }
+        if let Some((recipient, amount)) = self.allocations.iter().find(|(_, amount)| !amount.is_positive()) {
+            return Err(anyhow!("The allocation to {} must be positive, found {}", recipient, amount));
+        }
+
+        // Ensure the allocations add up to the starting supply.
+        let starting_supply = Block::<N>::block_reward(0);
+        let total = self.allocations.iter().try_fold(0i64, |total, (_, amount)| total.checked_add(amount.0));
+        match total {
+            Some(total) if total == starting_supply.0 => {}
+            Some(total) => {
+                return Err(anyhow!(
+                    "The allocations must add up to the starting supply of {}, found {}",
+                    starting_supply,
+                    AleoAmount::from_i64(total)
+                ));
+            }
+            None => return Err(anyhow!("The allocations overflow the total supply")),
+        }
+
+        if self.timestamp < 0 {
+            return Err(anyhow!("The genesis block timestamp must be non-negative, found {}", self.timestamp));
+        }
+        if self.timestamp > OffsetDateTime::now_utc().unix_timestamp().saturating_add(N::ALEO_FUTURE_TIME_LIMIT_IN_SECS)
+        {
+            return Err(anyhow!("The genesis block timestamp {} exceeds the future time limit", self.timestamp));
+        }
+        if self.difficulty_target == 0 {
+            return Err(anyhow!("The genesis block difficulty target must be nonzero"));
+        }
+        Ok(())
+    }
+
+    /// Returns a new genesis block, mined with the given difficulty target.
+    pub fn build<R: Rng + CryptoRng>(&self, rng: &mut R) -> Result<Block<N>> {
+        self.check()?;
+
+        // Execute one coinbase transition per allocation.
+        let mut vm = VirtualMachine::<N>::new(LedgerTree::<N>::new()?.root())?;
+        let mut coinbase_records = Vec::with_capacity(self.allocations.len());
+        for (recipient, amount) in &self.allocations {
+            let request = Request::new_coinbase(*recipient, *amount, self.is_public, rng)?;
+            let (next_vm, response) = vm.execute(&request, rng)?;
+            coinbase_records.push(response.records()[0].clone());
+            vm = next_vm;
+        }
+        let transactions = Transactions::from(&[vm.finalize()?])?;
+
+        // Construct the block template.
+        let template = BlockTemplate::new(
+            LedgerProof::<N>::default().block_hash(),
+            0u32,
+            self.timestamp,
+            self.difficulty_target,
+            0u128,
+            LedgerTree::<N>::new()?.root(),
+            transactions,
+            coinbase_records[0].clone(),
+        );
+
+        // Construct the genesis block.
+        let block = Block::mine(&template, &AtomicBool::new(false), rng)?;
+
+        // Ensure the block is a valid genesis block.
+        match block.is_genesis() {
+            true => Ok(block),
+            false => Err(anyhow!("Failed to initialize a genesis block")),
+        }
+    }
+}
+
+impl<N: Network> Default for GenesisBuilder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_check_allocations() {
+        let rng = &mut thread_rng();
+        let starting_supply = Block::<Testnet2>::block_reward(0);
+        let first = Address::from_private_key(&PrivateKey::<Testnet2>::new(rng));
+        let second = Address::from_private_key(&PrivateKey::<Testnet2>::new(rng));
+
+        // The allocations must add up to the starting supply.
+        assert!(GenesisBuilder::<Testnet2>::new().check().is_err());
+        assert!(GenesisBuilder::new().add_allocation(first, starting_supply).check().is_ok());
+        assert!(GenesisBuilder::new().add_allocation(first, AleoAmount::ONE_CREDIT).check().is_err());
+        assert!(GenesisBuilder::new()
+            .add_allocation(first, starting_supply.sub(AleoAmount::ONE_CREDIT))
+            .add_allocation(second, AleoAmount::ONE_CREDIT)
+            .check()
+            .is_ok());
+
+        // The allocations must be positive.
+        assert!(GenesisBuilder::new()
+            .add_allocation(first, starting_supply.add(AleoAmount::ONE_CREDIT))
+            .add_allocation(second, AleoAmount::ZERO.sub(AleoAmount::ONE_CREDIT))
+            .check()
+            .is_err());
+
+        // The timestamp must be within the future time limit.
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp() + 3600;
+        assert!(GenesisBuilder::new().add_allocation(first, starting_supply).timestamp(timestamp).check().is_err());
+
+        // The allocations must fit in one transaction.
+        let allocation = AleoAmount::from_i64(starting_supply.0 / (Testnet2::NUM_TRANSITIONS as i64 + 1));
+        let allocations = vec![(first, allocation); Testnet2::NUM_TRANSITIONS as usize + 1];
+        assert!(GenesisBuilder::new().add_allocations(allocations).check().is_err());
+    }
+
+    #[test]
+    fn test_genesis_with_allocations() {
+        let rng = &mut thread_rng();
+        let starting_supply = Block::<Testnet2>::block_reward(0);
+        let accounts = (0..3).map(|_| PrivateKey::<Testnet2>::new(rng)).collect::<Vec<_>>();
+        let amounts = [
+            starting_supply.0 / 2,
+            starting_supply.0 / 4,
+            starting_supply.0 - starting_supply.0 / 2 - starting_supply.0 / 4,
+        ];
+
+        let mut builder = GenesisBuilder::new().timestamp(1_640_995_200);
+        for (private_key, amount) in accounts.iter().zip(amounts.iter()) {
+            builder = builder.add_allocation(Address::from_private_key(private_key), AleoAmount::from_i64(*amount));
+        }
+        let block = builder.build(rng).unwrap();
+        assert!(block.is_valid());
+        assert!(block.is_genesis());
+        assert_eq!(block.timestamp(), 1_640_995_200);
+
+        // Ensure each account receives its allocation.
+        let coinbase_transaction = block.to_coinbase_transaction().unwrap();
+        assert_eq!(coinbase_transaction.value_balance(), AleoAmount::ZERO.sub(starting_supply));
+        for (private_key, amount) in accounts.iter().zip(amounts.iter()) {
+            let view_key = ViewKey::from_private_key(private_key);
+            let records = coinbase_transaction.to_decrypted_records(&view_key.into()).collect::<Vec<_>>();
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].value(), AleoAmount::from_i64(*amount));
+        }
+    }
+
+    #[test]
+    fn test_multiple_coinbase_transitions_after_genesis() {
+        let rng = &mut thread_rng();
+        let genesis_block = Testnet2::genesis_block();
+        let block_reward = Block::<Testnet2>::block_reward(1);
+        let recipient = Address::from_private_key(&PrivateKey::<Testnet2>::new(rng));
+
+        // Execute a coinbase transaction with 2 coinbase transitions.
+        let mut vm = VirtualMachine::<Testnet2>::new(LedgerTree::<Testnet2>::new().unwrap().root()).unwrap();
+        let mut coinbase_records = Vec::new();
+        let half_block_reward = AleoAmount::from_i64(block_reward.0 / 2);
+        for amount in [half_block_reward, block_reward.sub(half_block_reward)] {
+            let request = Request::new_coinbase(recipient, amount, true, rng).unwrap();
+            let (next_vm, response) = vm.execute(&request, rng).unwrap();
+            coinbase_records.push(response.records()[0].clone());
+            vm = next_vm;
+        }
+        let transactions = Transactions::from(&[vm.finalize().unwrap()]).unwrap();
+
+        // Ensure a block after the genesis block may not contain it.
+        let template = BlockTemplate::new(
+            genesis_block.hash(),
+            1,
+            OffsetDateTime::now_utc().unix_timestamp(),
+            genesis_block.difficulty_target(),
+            genesis_block.cumulative_weight() + 1,
+            LedgerTree::<Testnet2>::new().unwrap().root(),
+            transactions,
+            coinbase_records[0].clone(),
+        );
+        assert!(Block::mine(&template, &AtomicBool::new(false), rng).is_err());
+    }
+}
@@ -31,6 +31,7 @@ use anyhow::{anyhow, Result};
 use rand::{CryptoRng, Rng};
 use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use std::{mem::size_of, sync::atomic::AtomicBool};
+use time::OffsetDateTime;
 
 /// Block header metadata.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -48,14 +49,11 @@ pub struct BlockHeaderMetadata {
 impl BlockHeaderMetadata {
     /// Initializes a new instance of a block header metadata.
     pub fn new<N: Network>(template: &BlockTemplate<N>) -> Self {
-        match template.block_height() == 0 {
-            true => Self::genesis(),
-            false => Self {
-                height: template.block_height(),
-                timestamp: template.block_timestamp(),
-                difficulty_target: template.difficulty_target(),
-                cumulative_weight: template.cumulative_weight(),
-            },
+        Self {
+            height: template.block_height(),
+            timestamp: template.block_timestamp(),
+            difficulty_target: template.difficulty_target(),
+            cumulative_weight: template.cumulative_weight(),
         }
     }
 
@@ -64,12 +62,48 @@ impl BlockHeaderMetadata {
         Self { height: 0u32, timestamp: 0i64, difficulty_target: u64::MAX, cumulative_weight: 0u128 }
     }
 
+    /// Returns the height of the block.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the block timestamp.
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Returns the difficulty target of the block.
+    pub fn difficulty_target(&self) -> u64 {
+        self.difficulty_target
+    }
+
+    /// Returns the cumulative weight up to the block (inclusive).
+    pub fn cumulative_weight(&self) -> u128 {
+        self.cumulative_weight
+    }
+
     /// Returns the size (in bytes) of a block header's metadata.
     pub fn size() -> usize {
         size_of::<u32>() + size_of::<i64>() + size_of::<u64>() + size_of::<u128>()
     }
 }
 
+impl FromBytes for BlockHeaderMetadata {
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let height = <[u8; 4]>::read_le(&mut reader)?;
+        let timestamp = <[u8; 8]>::read_le(&mut reader)?;
+        let difficulty_target = <[u8; 8]>::read_le(&mut reader)?;
+        let cumulative_weight = <[u8; 16]>::read_le(&mut reader)?;
+
+        Ok(Self {
+            height: u32::from_le_bytes(height),
+            timestamp: i64::from_le_bytes(timestamp),
+            difficulty_target: u64::from_le_bytes(difficulty_target),
+            cumulative_weight: u128::from_le_bytes(cumulative_weight),
+        })
+    }
+}
+
 impl ToBytes for BlockHeaderMetadata {
     fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
         self.height.to_le_bytes().write_le(&mut writer)?;
@@ -155,6 +189,27 @@ impl<N: Network> BlockHeader<N> {
         })
     }
 
+    ///
+    /// Reads a block header from the given reader, *without* checking its validity.
+    /// WARNING - This method must only be used to restore block headers that were previously verified.
+    ///
+    pub(crate) fn read_le_unchecked<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the header core variables.
+        let previous_ledger_root = FromBytes::read_le(&mut reader)?;
+        let transactions_root = FromBytes::read_le(&mut reader)?;
+
+        // Read the header metadata.
+        let metadata = FromBytes::read_le(&mut reader)?;
+
+        // Read the header nonce.
+        let nonce = FromBytes::read_le(&mut reader)?;
+        // Read the header proof.
+        let proof = FromBytes::read_le(&mut reader)?;
+
+        // Construct the block header.
+        Ok(Self { previous_ledger_root, transactions_root, metadata, nonce, proof })
+    }
+
     /// Returns `true` if the block header is well-formed.
     pub fn is_valid(&self) -> bool {
         // Ensure the ledger root is nonzero.
@@ -187,14 +242,23 @@ impl<N: Network> BlockHeader<N> {
         }
     }
 
+    ///
     /// Returns `true` if the block header is a genesis block header.
+    ///
+    /// A genesis block may set its own timestamp and difficulty target, as the anchor of the difficulty
+    /// schedule for the following blocks. The timestamp must not exceed the future time limit, as for any
+    /// other block, and the difficulty target must be nonzero, as the cumulative weight of the following
+    /// blocks is inversely proportional to it. A ledger only accepts the genesis block of its network.
+    ///
     pub fn is_genesis(&self) -> bool {
         // Ensure the height in the genesis block is 0.
         self.metadata.height == 0u32
-            // Ensure the timestamp in the genesis block is 0.
-            && self.metadata.timestamp == 0i64
-            // Ensure the difficulty target in the genesis block is u64::MAX.
-            && self.metadata.difficulty_target == u64::MAX
+            // Ensure the timestamp in the genesis block is non-negative, and within the future time limit.
+            && self.metadata.timestamp >= 0i64
+            && self.metadata.timestamp
+                <= OffsetDateTime::now_utc().unix_timestamp().saturating_add(N::ALEO_FUTURE_TIME_LIMIT_IN_SECS)
+            // Ensure the difficulty target in the genesis block is nonzero.
+            && self.metadata.difficulty_target > 0u64
             // Ensure the cumulative weight in the genesis block is 0u128.
             && self.metadata.cumulative_weight == 0u128
             // Ensure the PoSW proof is valid.
@@ -271,30 +335,14 @@ impl<N: Network> BlockHeader<N> {
 
 impl<N: Network> FromBytes for BlockHeader<N> {
     #[inline]
-    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
-        // Read the header core variables.
-        let previous_ledger_root = FromBytes::read_le(&mut reader)?;
-        let transactions_root = FromBytes::read_le(&mut reader)?;
+    fn read_le<R: Read>(reader: R) -> IoResult<Self> {
+        let block_header = Self::read_le_unchecked(reader)?;
 
-        // Read the header metadata.
-        let height = <[u8; 4]>::read_le(&mut reader)?;
-        let timestamp = <[u8; 8]>::read_le(&mut reader)?;
-        let difficulty_target = <[u8; 8]>::read_le(&mut reader)?;
-        let cumulative_weight = <[u8; 16]>::read_le(&mut reader)?;
-        let metadata = BlockHeaderMetadata {
-            height: u32::from_le_bytes(height),
-            timestamp: i64::from_le_bytes(timestamp),
-            difficulty_target: u64::from_le_bytes(difficulty_target),
-            cumulative_weight: u128::from_le_bytes(cumulative_weight),
-        };
-
-        // Read the header nonce.
-        let nonce = FromBytes::read_le(&mut reader)?;
-        // Read the header proof.
-        let proof = FromBytes::read_le(&mut reader)?;
-
-        // Construct the block header.
-        Ok(Self::from(previous_ledger_root, transactions_root, metadata, nonce, proof)?)
+        // Ensure the block header is well-formed.
+        match block_header.is_valid() {
+            true => Ok(block_header),
+            false => Err(BlockError::Message("Invalid block header".to_string()).into()),
+        }
     }
 }
 
@@ -17,9 +17,15 @@
 pub mod block;
 pub use block::*;
 
+pub mod genesis_builder;
+pub use genesis_builder::*;
+
 pub mod header;
 pub use header::*;
 
+pub mod summary;
+pub use summary::*;
+
 pub mod template;
 pub use template::*;
 
@@ -0,0 +1,125 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{AleoAmount, Block, Network};
+use snarkvm_utilities::ToBytes;
+
+use anyhow::Result;
+
+/// A summary of the statistics of a block.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"), PartialEq(bound = "N: Network"))]
+pub struct BlockSummary<N: Network> {
+    /// The hash of the block.
+    block_hash: N::BlockHash,
+    /// The height of the block.
+    height: u32,
+    /// The timestamp of the block.
+    timestamp: i64,
+    /// The difficulty target of the block.
+    difficulty_target: u64,
+    /// The number of transactions in the block.
+    num_transactions: usize,
+    /// The number of transitions in the block.
+    num_transitions: usize,
+    /// The total transaction fees in the block, excluding the block reward.
+    transaction_fees: AleoAmount,
+    /// The block reward.
+    block_reward: AleoAmount,
+    /// The serialized size of the block in bytes.
+    size_in_bytes: usize,
+}
+
+impl<N: Network> BlockSummary<N> {
+    /// Initializes a new block summary from the given block.
+    pub fn new(block: &Block<N>) -> Result<Self> {
+        Ok(Self {
+            block_hash: block.hash(),
+            height: block.height(),
+            timestamp: block.timestamp(),
+            difficulty_target: block.difficulty_target(),
+            num_transactions: block.transactions().len(),
+            num_transitions: block.transactions().iter().map(|transaction| transaction.transitions().len()).sum(),
+            transaction_fees: block.transactions().transaction_fees(),
+            block_reward: AleoAmount::ZERO.sub(block.to_coinbase_transaction()?.value_balance()),
+            size_in_bytes: block.to_bytes_le()?.len(),
+        })
+    }
+
+    /// Returns the hash of the block.
+    pub fn block_hash(&self) -> N::BlockHash {
+        self.block_hash
+    }
+
+    /// Returns the height of the block.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the timestamp of the block.
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Returns the difficulty target of the block.
+    pub fn difficulty_target(&self) -> u64 {
+        self.difficulty_target
+    }
+
+    /// Returns the number of transactions in the block.
+    pub fn num_transactions(&self) -> usize {
+        self.num_transactions
+    }
+
+    /// Returns the number of transitions in the block.
+    pub fn num_transitions(&self) -> usize {
+        self.num_transitions
+    }
+
+    /// Returns the total transaction fees in the block, excluding the block reward.
+    pub fn transaction_fees(&self) -> AleoAmount {
+        self.transaction_fees
+    }
+
+    /// Returns the block reward, as minted by the coinbase transaction.
+    pub fn block_reward(&self) -> AleoAmount {
+        self.block_reward
+    }
+
+    /// Returns the serialized size of the block in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.size_in_bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    #[test]
+    fn test_genesis_block_summary() {
+        let block = Testnet2::genesis_block();
+        let summary = BlockSummary::new(block).unwrap();
+
+        assert_eq!(block.hash(), summary.block_hash());
+        assert_eq!(0, summary.height());
+        assert_eq!(1, summary.num_transactions());
+        assert_eq!(AleoAmount::ZERO, summary.transaction_fees());
+        assert_eq!(Block::<Testnet2>::block_reward(0), summary.block_reward());
+        assert_eq!(block.to_bytes_le().unwrap().len(), summary.size_in_bytes());
+    }
+}
@@ -70,6 +70,29 @@ impl<N: Network> Transactions<N> {
         }
     }
 
+    /// Initializes from a given transactions list, *without* checking its validity.
+    /// WARNING - This method must only be used to restore transactions that were previously verified.
+    pub(crate) fn from_unchecked(transactions: Vec<Transaction<N>>) -> Result<Self, BlockError> {
+        // Compute the transactions tree.
+        let tree = MerkleTree::<N::TransactionsRootParameters>::new(
+            Arc::new(N::transactions_root_parameters().clone()),
+            &transactions.iter().map(Transaction::transaction_id).collect::<Vec<_>>(),
+        )?;
+
+        Ok(Self { transactions, tree: Arc::new(tree) })
+    }
+
+    /// Reads a transactions list from the given reader, *without* checking its validity.
+    /// WARNING - This method must only be used to restore transactions that were previously verified.
+    pub(crate) fn read_le_unchecked<R: Read>(mut reader: R) -> IoResult<Self> {
+        let num_transactions: u16 = FromBytes::read_le(&mut reader)?;
+        let mut transactions = Vec::with_capacity(num_transactions as usize);
+        for _ in 0..num_transactions {
+            transactions.push(Transaction::read_le_unchecked(&mut reader)?);
+        }
+        Ok(Self::from_unchecked(transactions)?)
+    }
+
     /// Returns `true` if the transactions are well-formed.
     pub fn is_valid(&self) -> bool {
         // Ensure the transactions list is not empty.
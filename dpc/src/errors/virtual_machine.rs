@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::FunctionType;
 use snarkvm_algorithms::{
     CRHError,
     CommitmentError,
@@ -64,21 +65,39 @@ pub enum VMError {
     #[error("{}", _0)]
     FromHexError(#[from] hex::FromHexError),
 
+    #[error("Function ID mismatch: evaluating {}, but the request calls {}", _0, _1)]
+    FunctionIDMismatch(String, String),
+
+    #[error("Function type mismatch: the function is declared as {:?}, but evaluated as {:?}", _0, _1)]
+    FunctionTypeMismatch(FunctionType, FunctionType),
+
     #[error("Given private key does not correspond to the record owner")]
     IncorrectPrivateKey,
 
+    #[error("Function type {:?} expects {} input records, found {}", _0, _1, _2)]
+    InvalidInputCount(FunctionType, u8, usize),
+
+    #[error("Function type {:?} expects at most {} output records, found {}", _0, _1, _2)]
+    InvalidOutputCount(FunctionType, u8, usize),
+
     #[error("{}", _0)]
     MerkleError(#[from] MerkleError),
 
     #[error("Missing caller {}", _0)]
     MissingCaller(String),
 
+    #[error("Function {} does not exist in program {}", _0, _1)]
+    MissingFunction(String, String),
+
     #[error("{}", _0)]
     ParameterError(#[from] ParameterError),
 
     #[error("{}", _0)]
     PRFError(#[from] PRFError),
 
+    #[error("Program ID mismatch: evaluating {}, but the request records belong to {}", _0, _1)]
+    ProgramIDMismatch(String, String),
+
     #[error("{}", _0)]
     SignatureError(#[from] SignatureError),
 
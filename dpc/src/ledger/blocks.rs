@@ -16,12 +16,22 @@
 
 use crate::prelude::*;
 use snarkvm_algorithms::merkle_tree::*;
+use snarkvm_utilities::{to_bytes_le, FromBytes, ToBytes};
 
 use anyhow::{anyhow, Result};
-use itertools::Itertools;
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 use time::OffsetDateTime;
 
+/// The storage metadata key for the latest block height.
+const LATEST_BLOCK_HEIGHT_KEY: &[u8] = b"latest_block_height";
+/// The storage metadata key for the latest block hash.
+const LATEST_BLOCK_HASH_KEY: &[u8] = b"latest_block_hash";
+/// The storage metadata key for the latest pruned block height.
+const PRUNED_BLOCK_HEIGHT_KEY: &[u8] = b"pruned_block_height";
+
 #[derive(Clone, Debug)]
 pub struct Blocks<N: Network> {
     /// The current block height.
@@ -36,6 +46,26 @@ pub struct Blocks<N: Network> {
     headers: HashMap<u32, BlockHeader<N>>,
     /// The chain of block transactions.
     transactions: HashMap<u32, Transactions<N>>,
+    /// The index of previous ledger roots to block heights.
+    ledger_roots: HashMap<N::LedgerRoot, u32>,
+    /// The index of transaction IDs to (block height, transaction index).
+    transaction_ids: HashMap<N::TransactionID, (u32, u16)>,
+    /// The index of transition IDs to (block height, transaction index, transition index).
+    transition_ids: HashMap<N::TransitionID, (u32, u16, u8)>,
+    /// The index of serial numbers to (block height, transaction index, transition index, serial number index).
+    serial_numbers: HashMap<N::SerialNumber, (u32, u16, u8, u8)>,
+    /// The index of commitments to (block height, transaction index, transition index, commitment index).
+    commitments: HashMap<N::Commitment, (u32, u16, u8, u8)>,
+    /// The registry of deployed programs.
+    programs: ProgramRegistry<N>,
+    /// The height of the trusted checkpoint block, before which the chain was not verified and may not be reverted.
+    checkpoint_height: u32,
+    /// The number of most recent blocks whose transactions are retained, if the chain is pruned.
+    pruning_depth: Option<u32>,
+    /// The height of the latest block whose transactions were pruned, if any.
+    pruned_height: Option<u32>,
+    /// The storage backend for the chain, if it is persisted.
+    storage: Option<Arc<dyn Storage>>,
 }
 
 impl<N: Network> Blocks<N> {
@@ -51,16 +81,402 @@ impl<N: Network> Blocks<N> {
             previous_hashes: Default::default(),
             headers: Default::default(),
             transactions: Default::default(),
+            ledger_roots: Default::default(),
+            transaction_ids: Default::default(),
+            transition_ids: Default::default(),
+            serial_numbers: Default::default(),
+            commitments: Default::default(),
+            programs: ProgramRegistry::new()?,
+            checkpoint_height: 0,
+            pruning_depth: None,
+            pruned_height: None,
+            storage: None,
         };
 
         blocks.ledger_tree.add(&genesis_block.hash())?;
         blocks.previous_hashes.insert(height, genesis_block.previous_block_hash());
         blocks.headers.insert(height, genesis_block.header().clone());
         blocks.transactions.insert(height, genesis_block.transactions().clone());
+        blocks.add_to_indexes(genesis_block.header(), genesis_block.transactions())?;
 
         Ok(blocks)
     }
 
+    ///
+    /// Opens an instance of `Blocks` from the given storage, resuming from the latest committed block.
+    /// If the storage is empty, it is initialized with the genesis block.
+    ///
+    /// Note: Blocks in storage were verified when they were committed, and are *not* re-verified.
+    ///
+    pub fn open(storage: Arc<dyn Storage>) -> Result<Self> {
+        // Retrieve the latest block height, or initialize the storage with the genesis block.
+        let latest_block_height = match storage.get(DataMap::Metadata, LATEST_BLOCK_HEIGHT_KEY)? {
+            Some(height) => u32::read_le(&height[..])?,
+            None => {
+                let mut blocks = Self::new()?;
+                storage.write_batch(Self::to_storage_batch(N::genesis_block(), 0)?)?;
+                blocks.storage = Some(storage);
+                return Ok(blocks);
+            }
+        };
+        let latest_block_hash = match storage.get(DataMap::Metadata, LATEST_BLOCK_HASH_KEY)? {
+            Some(block_hash) => N::BlockHash::read_le(&block_hash[..])?,
+            None => return Err(anyhow!("Missing latest block hash in storage")),
+        };
+
+        // Retrieve the latest pruned block height, if the chain is pruned.
+        let pruned_height = match storage.get(DataMap::Metadata, PRUNED_BLOCK_HEIGHT_KEY)? {
+            Some(height) => Some(u32::read_le(&height[..])?),
+            None => None,
+        };
+
+        // Restore the ledger tree.
+        let block_hashes = storage
+            .entries(DataMap::LedgerTree)?
+            .iter()
+            .map(|(_, block_hash)| N::BlockHash::read_le(&block_hash[..]))
+            .collect::<Result<Vec<_>, _>>()?;
+        if block_hashes.len() != latest_block_height as usize + 1 {
+            return Err(anyhow!(
+                "Found {} block hashes in storage, expected {}",
+                block_hashes.len(),
+                latest_block_height + 1
+            ));
+        }
+        if block_hashes.last() != Some(&latest_block_hash) {
+            return Err(anyhow!("The latest block hash in storage does not match the ledger tree"));
+        }
+        let mut ledger_tree = LedgerTree::<N>::new()?;
+        ledger_tree.add_all(&block_hashes)?;
+
+        // Restore the chain of blocks.
+        let mut previous_hashes = HashMap::with_capacity(block_hashes.len());
+        let mut headers = HashMap::with_capacity(block_hashes.len());
+        let mut transactions = HashMap::with_capacity(block_hashes.len());
+        for height in 0..=latest_block_height {
+            let key = Self::to_height_key(height);
+            let (previous_hash, header) =
+                match (storage.get(DataMap::PreviousBlockHashes, &key)?, storage.get(DataMap::BlockHeaders, &key)?) {
+                    (Some(previous_hash), Some(header)) => {
+                        (N::BlockHash::read_le(&previous_hash[..])?, BlockHeader::<N>::read_le_unchecked(&header[..])?)
+                    }
+                    _ => return Err(anyhow!("Missing block {} in storage", height)),
+                };
+            previous_hashes.insert(height, previous_hash);
+            headers.insert(height, header);
+
+            // Restore the block transactions, unless they were pruned.
+            if pruned_height.map_or(true, |pruned_height| height > pruned_height) {
+                match storage.get(DataMap::BlockTransactions, &key)? {
+                    Some(block_transactions) => {
+                        transactions.insert(height, Transactions::<N>::read_le_unchecked(&block_transactions[..])?)
+                    }
+                    None => return Err(anyhow!("Missing block {} in storage", height)),
+                };
+            }
+        }
+
+        // Ensure the storage belongs to the same network, by checking the genesis block.
+        if headers.get(&0) != Some(N::genesis_block().header()) || block_hashes[0] != N::genesis_block().hash() {
+            return Err(anyhow!(
+                "The genesis block in storage does not match the genesis block of {}",
+                N::NETWORK_NAME
+            ));
+        }
+
+        let mut blocks = Self {
+            current_height: latest_block_height,
+            current_hash: latest_block_hash,
+            ledger_tree,
+            previous_hashes,
+            headers,
+            transactions,
+            ledger_roots: Default::default(),
+            transaction_ids: Default::default(),
+            transition_ids: Default::default(),
+            serial_numbers: Default::default(),
+            commitments: Default::default(),
+            programs: ProgramRegistry::new()?,
+            checkpoint_height: 0,
+            pruning_depth: None,
+            pruned_height,
+            storage: Some(storage.clone()),
+        };
+
+        // Restore the indexes.
+        for height in 0..=latest_block_height {
+            let header = blocks.get_block_header(height)?.clone();
+            match blocks.is_pruned(height) {
+                true => {
+                    if blocks.ledger_roots.insert(header.previous_ledger_root(), height).is_some() {
+                        return Err(anyhow!(
+                            "Duplicate ledger root {} in block {}",
+                            header.previous_ledger_root(),
+                            height
+                        ));
+                    }
+                }
+                false => {
+                    let transactions = blocks.get_block_transactions(height)?.clone();
+                    blocks.add_to_indexes(&header, &transactions)?;
+                }
+            }
+        }
+
+        // Restore the serial numbers and commitments of the pruned blocks.
+        if let Some(pruned_height) = pruned_height {
+            for (serial_number, location) in storage.entries(DataMap::SerialNumbers)? {
+                let location = Self::from_location_bytes(&location)?;
+                if location.0 <= pruned_height {
+                    blocks.serial_numbers.insert(N::SerialNumber::read_le(&serial_number[..])?, location);
+                }
+            }
+            for (commitment, location) in storage.entries(DataMap::Commitments)? {
+                let location = Self::from_location_bytes(&location)?;
+                if location.0 <= pruned_height {
+                    blocks.commitments.insert(N::Commitment::read_le(&commitment[..])?, location);
+                }
+            }
+            for (_, deployment) in storage.entries(DataMap::Programs)? {
+                let (height, deployment) = Self::from_deployment_bytes(&deployment)?;
+                if height <= pruned_height {
+                    blocks.programs.add(height, deployment)?;
+                }
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    ///
+    /// Initializes an instance of `Blocks` from the given snapshot, if the snapshot ends in the given trusted block hash.
+    /// The block headers are checked to form a chain to the trusted block hash, and the ledger tree is checked
+    /// to match the ledger root of the snapshot. The proofs in the blocks up to the trusted block are *not* verified,
+    /// and blocks added after the trusted block are fully verified.
+    ///
+    pub fn from_snapshot(snapshot: &LedgerSnapshot<N>, trusted_block_hash: N::BlockHash) -> Result<Self> {
+        // Ensure the snapshot ends in the trusted block.
+        let height = snapshot.block_height();
+        if snapshot.block_hash()? != trusted_block_hash {
+            return Err(anyhow!("The ledger snapshot does not end in the trusted block {}", trusted_block_hash));
+        }
+        if snapshot.headers.len() != snapshot.block_hashes.len() {
+            return Err(anyhow!(
+                "The ledger snapshot contains {} block headers for {} blocks",
+                snapshot.headers.len(),
+                snapshot.block_hashes.len()
+            ));
+        }
+
+        // Ensure the snapshot starts from the genesis block of the network.
+        let genesis_block = N::genesis_block();
+        if snapshot.block_hashes[0] != genesis_block.hash() || snapshot.headers[0] != *genesis_block.header() {
+            return Err(anyhow!(
+                "The genesis block in the ledger snapshot does not match the genesis block of {}",
+                N::NETWORK_NAME
+            ));
+        }
+
+        // Ensure the block headers form a chain to the trusted block.
+        let mut previous_hashes = HashMap::with_capacity(snapshot.block_hashes.len());
+        let mut headers = HashMap::with_capacity(snapshot.block_hashes.len());
+        let mut previous_block_hash = genesis_block.previous_block_hash();
+        for (block_height, (block_hash, header)) in snapshot.block_hashes.iter().zip(&snapshot.headers).enumerate() {
+            if header.height() as usize != block_height
+                || Block::compute_block_hash(previous_block_hash, header)? != *block_hash
+            {
+                return Err(anyhow!("The ledger snapshot contains an invalid block header at height {}", block_height));
+            }
+            previous_hashes.insert(header.height(), previous_block_hash);
+            headers.insert(header.height(), header.clone());
+            previous_block_hash = *block_hash;
+        }
+
+        // Ensure the transactions match the trusted block.
+        if snapshot.transactions.transactions_root() != snapshot.headers[height as usize].transactions_root() {
+            return Err(anyhow!(
+                "The ledger snapshot transactions do not match the trusted block {}",
+                trusted_block_hash
+            ));
+        }
+
+        // Ensure the serial numbers and commitments match the blocks before the trusted block.
+        let transitions = Self::verify_snapshot_transitions(snapshot, &snapshot.headers[..height as usize])?;
+
+        // Ensure the ledger tree matches the ledger root.
+        let mut ledger_tree = LedgerTree::<N>::new()?;
+        ledger_tree.add_all(&snapshot.block_hashes)?;
+        if ledger_tree.root() != snapshot.ledger_root {
+            return Err(anyhow!("The ledger snapshot does not match the ledger root {}", snapshot.ledger_root));
+        }
+
+        let mut blocks = Self {
+            current_height: height,
+            current_hash: trusted_block_hash,
+            ledger_tree,
+            previous_hashes,
+            headers,
+            transactions: Default::default(),
+            ledger_roots: Default::default(),
+            transaction_ids: Default::default(),
+            transition_ids: Default::default(),
+            serial_numbers: Default::default(),
+            commitments: Default::default(),
+            programs: ProgramRegistry::new()?,
+            checkpoint_height: height,
+            pruning_depth: None,
+            pruned_height: height.checked_sub(1),
+            storage: None,
+        };
+
+        // Restore the indexes of the blocks before the trusted block.
+        for header in &snapshot.headers[..height as usize] {
+            if blocks.ledger_roots.insert(header.previous_ledger_root(), header.height()).is_some() {
+                return Err(anyhow!(
+                    "Duplicate ledger root {} in block {}",
+                    header.previous_ledger_root(),
+                    header.height()
+                ));
+            }
+        }
+        for ((block_height, transaction_index, transition_index), (serial_numbers, commitments)) in transitions {
+            for (index, serial_number) in serial_numbers.into_iter().enumerate() {
+                let location = (block_height, transaction_index, transition_index, index as u8);
+                if blocks.serial_numbers.insert(serial_number, location).is_some() {
+                    return Err(anyhow!("Duplicate serial number {} in the ledger snapshot", serial_number));
+                }
+            }
+            for (index, commitment) in commitments.into_iter().enumerate() {
+                let location = (block_height, transaction_index, transition_index, index as u8);
+                if blocks.commitments.insert(commitment, location).is_some() {
+                    return Err(anyhow!("Duplicate commitment {} in the ledger snapshot", commitment));
+                }
+            }
+        }
+        for (deployment_height, deployment) in snapshot.deployments.iter().filter(|(h, _)| *h < height) {
+            blocks.programs.add(*deployment_height, deployment.clone())?;
+        }
+
+        // Restore the trusted block, with its indexes.
+        blocks.transactions.insert(height, snapshot.transactions.clone());
+        blocks.add_to_indexes(&snapshot.headers[height as usize], &snapshot.transactions)?;
+
+        Ok(blocks)
+    }
+
+    /// Returns a snapshot of the chain state at the latest block.
+    pub fn to_snapshot(&self) -> Result<LedgerSnapshot<N>> {
+        let block_hashes =
+            (0..=self.current_height).map(|height| self.get_block_hash(height)).collect::<Result<_>>()?;
+        let headers = (0..=self.current_height)
+            .map(|height| Ok(self.get_block_header(height)?.clone()))
+            .collect::<Result<_>>()?;
+
+        // Order the serial numbers and commitments by location, including their index in the transition.
+        let mut serial_numbers: Vec<_> = self.serial_numbers.iter().map(|(k, v)| (*k, *v)).collect();
+        serial_numbers.sort_by_key(|(_, location)| *location);
+        let serial_numbers = serial_numbers.into_iter().map(|(k, (h, t, i, _))| (k, (h, t, i))).collect();
+        let mut commitments: Vec<_> = self.commitments.iter().map(|(k, v)| (*k, *v)).collect();
+        commitments.sort_by_key(|(_, location)| *location);
+        let commitments = commitments.into_iter().map(|(k, (h, t, i, _))| (k, (h, t, i))).collect();
+        let mut deployments: Vec<_> =
+            self.programs.deployments().map(|(height, deployment)| (height, deployment.clone())).collect();
+        deployments.sort_by_key(|(height, deployment)| (*height, deployment.program_id().to_string()));
+
+        Ok(LedgerSnapshot {
+            block_hashes,
+            headers,
+            transactions: self.latest_block_transactions()?.clone(),
+            ledger_root: self.latest_ledger_root(),
+            serial_numbers,
+            commitments,
+            deployments,
+        })
+    }
+
+    ///
+    /// Returns the serial numbers and commitments of the snapshot in the given blocks, grouped by their
+    /// (block height, transaction index, transition index), if they match the transactions roots of the blocks.
+    ///
+    /// The transition IDs and transaction IDs are recomputed from the serial numbers and commitments,
+    /// in the order they appear in the snapshot, and the transactions root of each block is recomputed from them.
+    ///
+    #[allow(clippy::type_complexity)]
+    fn verify_snapshot_transitions(
+        snapshot: &LedgerSnapshot<N>,
+        headers: &[BlockHeader<N>],
+    ) -> Result<BTreeMap<(u32, u16, u8), (Vec<N::SerialNumber>, Vec<N::Commitment>)>> {
+        let num_blocks = headers.len() as u32;
+
+        // Group the serial numbers and commitments by transition.
+        let mut transitions = BTreeMap::<_, (Vec<_>, Vec<_>)>::new();
+        for (serial_number, location) in snapshot.serial_numbers.iter().filter(|(_, (h, _, _))| *h < num_blocks) {
+            transitions.entry(*location).or_default().0.push(*serial_number);
+        }
+        for (commitment, location) in snapshot.commitments.iter().filter(|(_, (h, _, _))| *h < num_blocks) {
+            transitions.entry(*location).or_default().1.push(*commitment);
+        }
+
+        // Compute the transition IDs of each transaction.
+        let mut transactions = BTreeMap::<_, Vec<_>>::new();
+        for ((block_height, transaction_index, transition_index), (serial_numbers, commitments)) in &transitions {
+            let transition_ids = transactions.entry((*block_height, *transaction_index)).or_default();
+            if serial_numbers.len() != N::NUM_INPUT_RECORDS
+                || commitments.len() != N::NUM_OUTPUT_RECORDS
+                || *transition_index as usize != transition_ids.len()
+            {
+                return Err(anyhow!("The ledger snapshot contains an invalid transition in block {}", block_height));
+            }
+            transition_ids.push(Transition::<N>::compute_transition_id(serial_numbers, commitments)?);
+        }
+
+        // Compute the transaction IDs of each block.
+        let mut block_transaction_ids = vec![Vec::new(); headers.len()];
+        for ((block_height, transaction_index), transition_ids) in &transactions {
+            let transaction_ids = &mut block_transaction_ids[*block_height as usize];
+            if *transaction_index as usize != transaction_ids.len() {
+                return Err(anyhow!("The ledger snapshot contains an invalid transaction in block {}", block_height));
+            }
+            transaction_ids.push(Transaction::<N>::compute_transaction_id_from_transition_ids(transition_ids)?);
+        }
+
+        // Ensure the transactions root of each block matches its block header.
+        for (header, transaction_ids) in headers.iter().zip(&block_transaction_ids) {
+            let transactions_tree = MerkleTree::<N::TransactionsRootParameters>::new(
+                Arc::new(N::transactions_root_parameters().clone()),
+                transaction_ids,
+            )?;
+            if N::TransactionsRoot::from(*transactions_tree.root()) != header.transactions_root() {
+                return Err(anyhow!(
+                    "The ledger snapshot serial numbers and commitments do not match block {}",
+                    header.height()
+                ));
+            }
+        }
+
+        Ok(transitions)
+    }
+
+    /// Returns the height of the trusted checkpoint block, which is the genesis block unless initialized from a snapshot.
+    pub fn checkpoint_height(&self) -> u32 {
+        self.checkpoint_height
+    }
+
+    /// Returns the number of most recent blocks whose transactions are retained, if the chain is pruned.
+    pub fn pruning_depth(&self) -> Option<u32> {
+        self.pruning_depth
+    }
+
+    /// Returns the height of the latest block whose transactions were pruned, if any.
+    pub fn pruned_height(&self) -> Option<u32> {
+        self.pruned_height
+    }
+
+    /// Returns `true` if the transactions of the given block height were pruned.
+    pub fn is_pruned(&self, height: u32) -> bool {
+        self.pruned_height.map_or(false, |pruned_height| height <= pruned_height)
+    }
+
     /// Returns the latest block height.
     pub fn latest_block_height(&self) -> u32 {
         self.current_height
@@ -121,6 +537,7 @@ impl<N: Network> Blocks<N> {
     pub fn get_block_transactions(&self, height: u32) -> Result<&Transactions<N>> {
         match self.transactions.get(&height) {
             Some(transactions) => Ok(transactions),
+            None if self.is_pruned(height) => Err(anyhow!("Block transactions for height {} were pruned", height)),
             None => Err(anyhow!("Missing block transactions for height {}", height)),
         }
     }
@@ -152,6 +569,39 @@ impl<N: Network> Blocks<N> {
         }
     }
 
+    /// Returns the block height given the block hash.
+    pub fn get_block_height(&self, block_hash: &N::BlockHash) -> Result<u32> {
+        match self.ledger_tree.get_block_hash_index(block_hash) {
+            Some(height) => Ok(*height),
+            None => Err(anyhow!("Block {} does not exist in the canon chain", block_hash)),
+        }
+    }
+
+    /// Returns the block headers from the given start block height to the given end block height, inclusive.
+    pub fn get_block_headers(&self, start_height: u32, end_height: u32) -> Result<Vec<BlockHeader<N>>> {
+        if start_height > end_height {
+            return Err(anyhow!("Invalid block height range {}..={}", start_height, end_height));
+        }
+
+        (start_height..=end_height).map(|height| Ok(self.get_block_header(height)?.clone())).collect()
+    }
+
+    /// Returns the transaction given the transaction ID.
+    pub fn get_transaction(&self, transaction_id: &N::TransactionID) -> Result<Transaction<N>> {
+        let (height, transaction_index) = match self.transaction_ids.get(transaction_id) {
+            Some(location) => *location,
+            None if self.pruned_height.is_some() => {
+                return Err(anyhow!("Transaction {} does not exist in the canon chain, or was pruned", transaction_id));
+            }
+            None => return Err(anyhow!("Transaction {} does not exist in the canon chain", transaction_id)),
+        };
+
+        match self.get_block_transactions(height)?.get(transaction_index as usize) {
+            Some(transaction) => Ok(transaction.clone()),
+            None => Err(anyhow!("Missing transaction {} in block {}", transaction_id, height)),
+        }
+    }
+
     /// Returns `true` if the given block height exists.
     pub fn contains_height(&self, height: u32) -> bool {
         self.previous_hashes.contains_key(&height)
@@ -161,38 +611,161 @@ impl<N: Network> Blocks<N> {
 
     /// Returns `true` if the given ledger root exists.
     pub fn contains_ledger_root(&self, ledger_root: &N::LedgerRoot) -> bool {
-        *ledger_root == self.latest_ledger_root()
-            || self.headers.values().map(BlockHeader::previous_ledger_root).any(|root| root == *ledger_root)
+        *ledger_root == self.latest_ledger_root() || self.ledger_roots.contains_key(ledger_root)
+    }
+
+    ///
+    /// Returns the number of blocks added since the given ledger root was the latest ledger root,
+    /// or `None` if the given ledger root does not exist.
+    ///
+    pub fn get_ledger_root_depth(&self, ledger_root: &N::LedgerRoot) -> Option<u32> {
+        match *ledger_root == self.latest_ledger_root() {
+            true => Some(0),
+            false => self.ledger_roots.get(ledger_root).map(|height| self.current_height + 1 - height),
+        }
     }
 
     /// Returns `true` if the given block hash exists.
     pub fn contains_block_hash(&self, block_hash: &N::BlockHash) -> bool {
-        self.current_hash == *block_hash || self.previous_hashes.values().any(|hash| *hash == *block_hash)
+        self.ledger_tree.contains_block_hash(block_hash)
     }
 
     /// Returns `true` if the given transaction exists.
     pub fn contains_transaction(&self, transaction: &Transaction<N>) -> bool {
-        self.transactions.values().flat_map(|transactions| &**transactions).any(|tx| *tx == *transaction)
+        self.transaction_ids.contains_key(&transaction.transaction_id())
+    }
+
+    /// Returns `true` if the given transaction ID exists.
+    pub fn contains_transaction_id(&self, transaction_id: &N::TransactionID) -> bool {
+        self.transaction_ids.contains_key(transaction_id)
+    }
+
+    /// Returns `true` if the given transition ID exists.
+    pub fn contains_transition_id(&self, transition_id: &N::TransitionID) -> bool {
+        self.transition_ids.contains_key(transition_id)
     }
 
     /// Returns `true` if the given serial number exists.
     pub fn contains_serial_number(&self, serial_number: &N::SerialNumber) -> bool {
-        self.transactions
-            .values()
-            .flat_map(|transactions| (**transactions).iter().map(Transaction::serial_numbers))
-            .any(|mut serial_numbers| serial_numbers.contains(serial_number))
+        self.serial_numbers.contains_key(serial_number)
     }
 
     /// Returns `true` if the given commitment exists.
     pub fn contains_commitment(&self, commitment: &N::Commitment) -> bool {
-        self.transactions
-            .values()
-            .flat_map(|transactions| (**transactions).iter().map(Transaction::commitments))
-            .any(|mut commitments| commitments.contains(commitment))
+        self.commitments.contains_key(commitment)
+    }
+
+    /// Returns `true` if the given program ID is deployed.
+    pub fn contains_program(&self, program_id: &N::ProgramID) -> bool {
+        self.programs.contains_program(program_id)
+    }
+
+    /// Returns the deployment of the given program ID.
+    pub fn get_program_deployment(&self, program_id: &N::ProgramID) -> Result<&Deployment<N>> {
+        self.programs.get_deployment(program_id)
+    }
+
+    ///
+    /// Returns `Ok(())` if every transition in the given transaction executes a deployed program,
+    /// and the programs it deploys are not deployed yet.
+    ///
+    pub fn verify_programs(&self, transaction: &Transaction<N>) -> Result<()> {
+        self.programs.verify_transaction(transaction)
+    }
+
+    /// Returns the (block height, transaction index) of the given transaction ID, if it exists.
+    pub fn get_transaction_location(&self, transaction_id: &N::TransactionID) -> Option<(u32, u16)> {
+        self.transaction_ids.get(transaction_id).copied()
+    }
+
+    /// Returns the (block height, transaction index, transition index) of the given transition ID, if it exists.
+    pub fn get_transition_location(&self, transition_id: &N::TransitionID) -> Option<(u32, u16, u8)> {
+        self.transition_ids.get(transition_id).copied()
+    }
+
+    /// Returns the (block height, transaction index, transition index) of the given serial number, if it exists.
+    pub fn get_serial_number_location(&self, serial_number: &N::SerialNumber) -> Option<(u32, u16, u8)> {
+        self.serial_numbers
+            .get(serial_number)
+            .map(|(height, transaction_index, transition_index, _)| (*height, *transaction_index, *transition_index))
+    }
+
+    /// Returns the (block height, transaction index, transition index) of the given commitment, if it exists.
+    pub fn get_commitment_location(&self, commitment: &N::Commitment) -> Option<(u32, u16, u8)> {
+        self.commitments
+            .get(commitment)
+            .map(|(height, transaction_index, transition_index, _)| (*height, *transaction_index, *transition_index))
     }
 
     /// Adds the given block as the next block in the chain.
     pub fn add_next(&mut self, block: &Block<N>) -> Result<()> {
+        self.commit(|blocks, batch| {
+            blocks.apply_next(block, batch)?;
+            blocks.apply_prune(batch)
+        })
+    }
+
+    ///
+    /// Reverts the chain to the given block height, returning the removed blocks in ascending order of height.
+    /// The chain may only be reverted up to `N::ALEO_MAXIMUM_FORK_DEPTH` blocks.
+    ///
+    pub fn revert_to(&mut self, height: u32) -> Result<Vec<Block<N>>> {
+        self.commit(|blocks, batch| blocks.apply_revert(height, batch))
+    }
+
+    ///
+    /// Reverts the chain to the given block height, and adds the given blocks as the next blocks in the chain,
+    /// returning the removed blocks in ascending order of height. This operation executes atomically,
+    /// meaning if any of the given blocks is invalid, the chain is left unchanged.
+    ///
+    pub fn reorganize(&mut self, height: u32, blocks: &[Block<N>]) -> Result<Vec<Block<N>>> {
+        self.commit(|chain, batch| {
+            let reverted_blocks = chain.apply_revert(height, batch)?;
+            for block in blocks {
+                chain.apply_next(block, batch)?;
+            }
+            chain.apply_prune(batch)?;
+            Ok(reverted_blocks)
+        })
+    }
+
+    ///
+    /// Sets the number of most recent blocks whose transactions are retained, and prunes the transactions
+    /// of all older blocks. The serial numbers, commitments, and ledger roots of pruned blocks are retained.
+    /// If `None` is provided, blocks added from now on are no longer pruned.
+    ///
+    pub fn set_pruning_depth(&mut self, pruning_depth: Option<u32>) -> Result<()> {
+        if pruning_depth == Some(0) {
+            return Err(anyhow!("The pruning depth must retain at least the latest block"));
+        }
+
+        self.commit(|blocks, batch| {
+            blocks.pruning_depth = pruning_depth;
+            blocks.apply_prune(batch)
+        })
+    }
+
+    ///
+    /// Applies the given operation to a copy of the chain, and commits the resulting storage batch,
+    /// if the storage exists, before replacing the chain with the copy. This code section executes atomically.
+    ///
+    fn commit<T>(&mut self, operation: impl FnOnce(&mut Self, &mut StorageBatch) -> Result<T>) -> Result<T> {
+        let mut blocks = self.clone();
+        let mut batch = StorageBatch::new();
+
+        let output = operation(&mut blocks, &mut batch)?;
+
+        // Commit the batch to storage, if it exists, before updating the in-memory state.
+        if let Some(storage) = &self.storage {
+            storage.write_batch(batch)?;
+        }
+
+        *self = blocks;
+        Ok(output)
+    }
+
+    /// Adds the given block as the next block in the chain, and appends the storage operations to the given batch.
+    fn apply_next(&mut self, block: &Block<N>, batch: &mut StorageBatch) -> Result<()> {
         // Ensure the block itself is valid.
         if !block.is_valid() {
             return Err(anyhow!("The given block is invalid"));
@@ -220,82 +793,205 @@ impl<N: Network> Blocks<N> {
             return Err(anyhow!("The given block hash already exists in the ledger"));
         }
 
+        // Ensure the block header follows the current block header.
+        Self::verify_next_header(self.get_block_header(self.current_height)?, block.header())?;
+
+        for transaction in block.transactions().iter() {
+            // Ensure the transaction in the block do not already exist.
+            if self.contains_transaction(transaction) {
+                return Err(anyhow!("The given block has a duplicate transaction in the ledger"));
+            }
+            // Ensure the transaction in the block references a valid past or current ledger root.
+            if !self.contains_ledger_root(&transaction.ledger_root()) {
+                return Err(anyhow!(
+                    "The given transaction references a non-existent ledger root {}",
+                    &transaction.ledger_root()
+                ));
+            }
+            // Ensure the transaction executes deployed programs, and does not redeploy a program.
+            self.programs.verify_transaction(transaction)?;
+        }
+
+        // Ensure the ledger does not already contain a given serial numbers.
+        for serial_number in block.serial_numbers() {
+            if self.contains_serial_number(serial_number) {
+                return Err(anyhow!("Serial number already exists in the ledger"));
+            }
+        }
+
+        // Ensure the ledger does not already contain a given commitments.
+        for commitment in block.commitments() {
+            if self.contains_commitment(commitment) {
+                return Err(anyhow!("Commitment already exists in the ledger"));
+            }
+        }
+
+        // Add the block to the ledger.
+        self.current_height = height;
+        self.current_hash = block_hash;
+        let ledger_tree_index = self.ledger_tree.add(&block.hash())?;
+        self.previous_hashes.insert(height, block.previous_block_hash());
+        self.headers.insert(height, block.header().clone());
+        self.transactions.insert(height, block.transactions().clone());
+        self.add_to_indexes(block.header(), block.transactions())?;
+
+        batch.extend(Self::to_storage_batch(block, ledger_tree_index)?);
+
+        Ok(())
+    }
+
+    ///
+    /// Ensures the given block header may follow the given previous block header,
+    /// by checking its timestamp, difficulty target, and cumulative weight.
+    ///
+    pub(crate) fn verify_next_header(previous_header: &BlockHeader<N>, header: &BlockHeader<N>) -> Result<()> {
         // Ensure the next block timestamp is within the declared time limit.
         let now = OffsetDateTime::now_utc().unix_timestamp();
-        if block.timestamp() > (now + N::ALEO_FUTURE_TIME_LIMIT_IN_SECS) {
+        if header.timestamp() > (now + N::ALEO_FUTURE_TIME_LIMIT_IN_SECS) {
             return Err(anyhow!("The given block timestamp exceeds the time limit"));
         }
 
-        // Ensure the next block timestamp is after the current block timestamp.
-        let current_block = self.latest_block()?;
-        if block.timestamp() <= current_block.timestamp() {
+        // Ensure the next block timestamp is after the previous block timestamp.
+        if header.timestamp() <= previous_header.timestamp() {
             return Err(anyhow!("The given block timestamp is before the current timestamp"));
         }
 
         // Ensure the expected difficulty target is met.
         let expected_difficulty_target =
-            Blocks::<N>::compute_difficulty_target(N::genesis_block().header(), block.timestamp(), block.height());
-        if block.difficulty_target() != expected_difficulty_target {
+            Blocks::<N>::compute_difficulty_target(N::genesis_block().header(), header.timestamp(), header.height());
+        if header.difficulty_target() != expected_difficulty_target {
             return Err(anyhow!(
                 "The given block difficulty target is incorrect. Found {}, but expected {}",
-                block.difficulty_target(),
+                header.difficulty_target(),
                 expected_difficulty_target
             ));
         }
 
         // Ensure the expected cumulative weight is computed correctly.
         let expected_cumulative_weight =
-            current_block.cumulative_weight().saturating_add((u64::MAX / expected_difficulty_target) as u128);
-        if block.cumulative_weight() != expected_cumulative_weight {
+            previous_header.cumulative_weight().saturating_add((u64::MAX / expected_difficulty_target) as u128);
+        if header.cumulative_weight() != expected_cumulative_weight {
             return Err(anyhow!(
                 "The given cumulative weight is incorrect. Found {}, but expected {}",
-                block.cumulative_weight(),
+                header.cumulative_weight(),
                 expected_cumulative_weight
             ));
         }
 
-        for transaction in block.transactions().iter() {
-            // Ensure the transaction in the block do not already exist.
-            if self.contains_transaction(transaction) {
-                return Err(anyhow!("The given block has a duplicate transaction in the ledger"));
-            }
-            // Ensure the transaction in the block references a valid past or current ledger root.
-            if !self.contains_ledger_root(&transaction.ledger_root()) {
-                return Err(anyhow!(
-                    "The given transaction references a non-existent ledger root {}",
-                    &transaction.ledger_root()
-                ));
-            }
+        Ok(())
+    }
+
+    /// Reverts the chain to the given block height, and appends the storage operations to the given batch.
+    fn apply_revert(&mut self, height: u32, batch: &mut StorageBatch) -> Result<Vec<Block<N>>> {
+        // Ensure the given block height is not ahead of the chain.
+        if height > self.current_height {
+            return Err(anyhow!("Cannot revert to block {}, as it is ahead of block {}", height, self.current_height));
         }
 
-        // Ensure the ledger does not already contain a given serial numbers.
-        for serial_number in block.serial_numbers() {
-            if self.contains_serial_number(serial_number) {
-                return Err(anyhow!("Serial number already exists in the ledger"));
-            }
+        // Ensure the revert does not go past the trusted checkpoint block.
+        if height < self.checkpoint_height {
+            return Err(anyhow!(
+                "Cannot revert to block {}, as it precedes the checkpoint block {}",
+                height,
+                self.checkpoint_height
+            ));
         }
 
-        // Ensure the ledger does not already contain a given commitments.
-        for commitment in block.commitments() {
-            if self.contains_commitment(commitment) {
-                return Err(anyhow!("Commitment already exists in the ledger"));
+        // Ensure the revert does not exceed the maximum fork depth.
+        if self.current_height - height > N::ALEO_MAXIMUM_FORK_DEPTH {
+            return Err(anyhow!(
+                "Cannot revert {} blocks, as it exceeds the maximum fork depth of {}",
+                self.current_height - height,
+                N::ALEO_MAXIMUM_FORK_DEPTH
+            ));
+        }
+
+        // Ensure the block to revert to was not pruned, as the latest block must retain its transactions.
+        if self.is_pruned(height) {
+            return Err(anyhow!("Cannot revert to block {}, as its transactions were pruned", height));
+        }
+
+        // Retrieve the blocks to revert, and the block hashes that remain.
+        let reverted_blocks =
+            (height + 1..=self.current_height).map(|h| self.get_block(h)).collect::<Result<Vec<_>>>()?;
+        let block_hashes = (0..=height).map(|h| self.get_block_hash(h)).collect::<Result<Vec<_>>>()?;
+        let block_hash = block_hashes[height as usize];
+
+        // Remove the reverted blocks from the chain.
+        for block in &reverted_blocks {
+            let height_key = Self::to_height_key(block.height());
+
+            self.previous_hashes.remove(&block.height());
+            self.headers.remove(&block.height());
+            self.transactions.remove(&block.height());
+            self.remove_from_indexes(block.header(), block.transactions());
+
+            batch.remove(DataMap::PreviousBlockHashes, height_key);
+            batch.remove(DataMap::BlockHeaders, height_key);
+            batch.remove(DataMap::BlockTransactions, height_key);
+            for serial_number in block.serial_numbers() {
+                batch.remove(DataMap::SerialNumbers, serial_number.to_bytes_le()?);
+            }
+            for commitment in block.commitments() {
+                batch.remove(DataMap::Commitments, commitment.to_bytes_le()?);
+            }
+            for transaction in block.transactions().iter() {
+                for deployment in transaction.deployments() {
+                    batch.remove(DataMap::Programs, deployment.program_id().to_bytes_le()?);
+                }
+            }
+            if let Some(index) = self.ledger_tree.get_block_hash_index(&block.hash()) {
+                batch.remove(DataMap::LedgerTree, index.to_be_bytes());
             }
         }
 
-        // Add the block to the ledger. This code section executes atomically.
-        {
-            let mut blocks = self.clone();
+        // Rebuild the ledger tree from the remaining block hashes.
+        if !reverted_blocks.is_empty() {
+            let mut ledger_tree = LedgerTree::<N>::new()?;
+            ledger_tree.add_all(&block_hashes)?;
+            self.ledger_tree = ledger_tree;
+        }
+
+        self.current_height = height;
+        self.current_hash = block_hash;
+
+        batch.insert(DataMap::Metadata, LATEST_BLOCK_HEIGHT_KEY, height.to_bytes_le()?);
+        batch.insert(DataMap::Metadata, LATEST_BLOCK_HASH_KEY, block_hash.to_bytes_le()?);
+
+        Ok(reverted_blocks)
+    }
 
-            blocks.current_height = height;
-            blocks.current_hash = block_hash;
-            blocks.ledger_tree.add(&block.hash())?;
-            blocks.previous_hashes.insert(height, block.previous_block_hash());
-            blocks.headers.insert(height, block.header().clone());
-            blocks.transactions.insert(height, block.transactions().clone());
+    ///
+    /// Prunes the transactions of the blocks older than the pruning depth, if the chain is pruned,
+    /// and appends the storage operations to the given batch.
+    ///
+    fn apply_prune(&mut self, batch: &mut StorageBatch) -> Result<()> {
+        let pruned_height = match self.pruning_depth {
+            Some(pruning_depth) if self.current_height >= pruning_depth => self.current_height - pruning_depth,
+            _ => return Ok(()),
+        };
+        let start_height = match self.pruned_height {
+            Some(previous_pruned_height) if previous_pruned_height >= pruned_height => return Ok(()),
+            Some(previous_pruned_height) => previous_pruned_height + 1,
+            None => 0,
+        };
 
-            *self = blocks;
+        // Remove the block transactions, and the transaction and transition IDs they index.
+        for height in start_height..=pruned_height {
+            if let Some(transactions) = self.transactions.remove(&height) {
+                for transaction in transactions.iter() {
+                    self.transaction_ids.remove(&transaction.transaction_id());
+                    for transition in transaction.transitions() {
+                        self.transition_ids.remove(&transition.transition_id());
+                    }
+                }
+            }
+            batch.remove(DataMap::BlockTransactions, Self::to_height_key(height));
         }
 
+        self.pruned_height = Some(pruned_height);
+        batch.insert(DataMap::Metadata, PRUNED_BLOCK_HEIGHT_KEY, pruned_height.to_bytes_le()?);
+
         Ok(())
     }
 
@@ -316,63 +1012,34 @@ impl<N: Network> Blocks<N> {
     /// Returns a ledger proof for the given commitment.
     ///
     pub fn to_ledger_proof(&self, commitment: N::Commitment) -> Result<LedgerProof<N>> {
-        // TODO (howardwu): Optimize this operation.
-        let transaction = self
-            .transactions
-            .values()
-            .flat_map(|transactions| &**transactions)
-            .filter(|transaction| transaction.commitments().contains(&commitment))
-            .collect::<Vec<_>>();
-        assert_eq!(1, transaction.len()); // TODO (howardwu): Clean this up with a proper error handler.
-        let transaction = transaction[0];
-        let local_proof = {
-            // Initialize a transitions tree.
-            let mut transitions_tree = Transitions::<N>::new()?;
-            // Add all given transition IDs to the tree.
-            transitions_tree.add_all(transaction.transitions())?;
-            // Return the local proof for the transitions tree.
-            transitions_tree.to_local_proof(commitment)?
+        // Retrieve the location of the commitment.
+        let (block_height, transaction_index, _) = match self.get_commitment_location(&commitment) {
+            Some(location) => location,
+            None => return Err(anyhow!("Commitment {} does not exist in the ledger", commitment)),
         };
-        let transaction_id = local_proof.transaction_id();
-
-        // TODO (howardwu): Optimize this operation.
-        let block_height = self
-            .transactions
-            .iter()
-            .filter_map(|(block_height, transactions)| match transactions.transaction_ids().contains(&transaction_id) {
-                true => Some(block_height),
-                false => None,
-            })
-            .collect::<Vec<_>>();
-        assert_eq!(1, block_height.len()); // TODO (howardwu): Clean this up with a proper error handler.
-        let block_height = *block_height[0];
         let transactions = self.get_block_transactions(block_height)?;
         let block_header = self.get_block_header(block_height)?;
 
-        // Compute the transactions inclusion proof.
-        let transactions_inclusion_proof = {
-            // TODO (howardwu): Optimize this operation.
-            let index = transactions
-                .transaction_ids()
-                .enumerate()
-                .filter_map(|(index, id)| match id == transaction_id {
-                    true => Some(index),
-                    false => None,
-                })
-                .collect::<Vec<_>>();
-            assert_eq!(1, index.len()); // TODO (howardwu): Clean this up with a proper error handler.
-            transactions.to_transactions_inclusion_proof(index[0], transaction_id)?
+        let transaction = match transactions.get(transaction_index as usize) {
+            Some(transaction) => transaction,
+            None => return Err(anyhow!("Missing transaction {} in block {}", transaction_index, block_height)),
         };
+        let local_proof = transaction.to_local_proof(commitment)?;
+        let transaction_id = local_proof.transaction_id();
+
+        // Compute the transactions inclusion proof.
+        let transactions_inclusion_proof =
+            transactions.to_transactions_inclusion_proof(transaction_index as usize, transaction_id)?;
 
         // Compute the block header inclusion proof.
         let transactions_root = transactions.transactions_root();
         let block_header_inclusion_proof = block_header.to_header_inclusion_proof(1, transactions_root)?;
         let block_header_root = block_header.to_header_root()?;
-        let previous_block_hash = self.get_previous_block_hash(self.current_height)?;
-        let current_block_hash = self.current_hash;
+        let block_hash = self.get_block_hash(block_height)?;
+        let previous_block_hash = self.get_previous_block_hash(block_height)?;
 
         let record_proof = RecordProof::new(
-            current_block_hash,
+            block_hash,
             previous_block_hash,
             block_header_root,
             block_header_inclusion_proof,
@@ -382,11 +1049,131 @@ impl<N: Network> Blocks<N> {
         )?;
 
         let ledger_root = self.latest_ledger_root();
-        let ledger_root_inclusion_proof = self.to_ledger_root_inclusion_proof(&current_block_hash)?;
+        let ledger_root_inclusion_proof = self.to_ledger_root_inclusion_proof(&block_hash)?;
 
         LedgerProof::new(ledger_root, ledger_root_inclusion_proof, record_proof)
     }
 
+    /// Adds the given block header and transactions to the indexes.
+    fn add_to_indexes(&mut self, header: &BlockHeader<N>, transactions: &Transactions<N>) -> Result<()> {
+        let height = header.height();
+        if self.ledger_roots.insert(header.previous_ledger_root(), height).is_some() {
+            return Err(anyhow!("Duplicate ledger root {} in block {}", header.previous_ledger_root(), height));
+        }
+
+        for (transaction_index, transaction) in transactions.iter().enumerate() {
+            let transaction_index = transaction_index as u16;
+            if self.transaction_ids.insert(transaction.transaction_id(), (height, transaction_index)).is_some() {
+                return Err(anyhow!("Duplicate transaction {} in block {}", transaction.transaction_id(), height));
+            }
+
+            for (transition_index, transition) in transaction.transitions().iter().enumerate() {
+                let location = (height, transaction_index, transition_index as u8);
+                if self.transition_ids.insert(transition.transition_id(), location).is_some() {
+                    return Err(anyhow!("Duplicate transition {} in block {}", transition.transition_id(), height));
+                }
+                for (index, serial_number) in transition.serial_numbers().enumerate() {
+                    let location = (location.0, location.1, location.2, index as u8);
+                    if self.serial_numbers.insert(*serial_number, location).is_some() {
+                        return Err(anyhow!("Duplicate serial number {} in block {}", serial_number, height));
+                    }
+                }
+                for (index, commitment) in transition.commitments().enumerate() {
+                    let location = (location.0, location.1, location.2, index as u8);
+                    if self.commitments.insert(*commitment, location).is_some() {
+                        return Err(anyhow!("Duplicate commitment {} in block {}", commitment, height));
+                    }
+                }
+            }
+
+            for deployment in transaction.deployments() {
+                self.programs.add(height, deployment.clone())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the given block header and transactions from the indexes.
+    fn remove_from_indexes(&mut self, header: &BlockHeader<N>, transactions: &Transactions<N>) {
+        self.ledger_roots.remove(&header.previous_ledger_root());
+
+        for transaction in transactions.iter() {
+            self.transaction_ids.remove(&transaction.transaction_id());
+            for transition in transaction.transitions() {
+                self.transition_ids.remove(&transition.transition_id());
+                for serial_number in transition.serial_numbers() {
+                    self.serial_numbers.remove(serial_number);
+                }
+                for commitment in transition.commitments() {
+                    self.commitments.remove(commitment);
+                }
+            }
+            for deployment in transaction.deployments() {
+                self.programs.remove(&deployment.program_id());
+            }
+        }
+    }
+
+    /// Returns the storage key for the given block height.
+    /// Note: Heights are encoded in big-endian so that storage keys are ordered by height.
+    fn to_height_key(height: u32) -> [u8; 4] {
+        height.to_be_bytes()
+    }
+
+    /// Returns the storage value for the given (block height, transaction index, transition index, record index).
+    fn to_location_bytes(location: (u32, u16, u8, u8)) -> Result<Vec<u8>> {
+        Ok(to_bytes_le![location.0, location.1, location.2, location.3]?)
+    }
+
+    /// Returns the (block height, transaction index, transition index, record index) from the given storage value.
+    fn from_location_bytes(mut location: &[u8]) -> Result<(u32, u16, u8, u8)> {
+        Ok((
+            u32::read_le(&mut location)?,
+            u16::read_le(&mut location)?,
+            u8::read_le(&mut location)?,
+            u8::read_le(&mut location)?,
+        ))
+    }
+
+    /// Returns the (block height, deployment) from the given storage value.
+    fn from_deployment_bytes(mut deployment: &[u8]) -> Result<(u32, Deployment<N>)> {
+        Ok((u32::read_le(&mut deployment)?, Deployment::read_le(&mut deployment)?))
+    }
+
+    /// Returns the storage batch that commits the given block, at the given index in the ledger tree.
+    fn to_storage_batch(block: &Block<N>, ledger_tree_index: u32) -> Result<StorageBatch> {
+        let height_key = Self::to_height_key(block.height());
+
+        let mut batch = StorageBatch::new();
+        batch.insert(DataMap::PreviousBlockHashes, height_key, block.previous_block_hash().to_bytes_le()?);
+        batch.insert(DataMap::BlockHeaders, height_key, block.header().to_bytes_le()?);
+        batch.insert(DataMap::BlockTransactions, height_key, block.transactions().to_bytes_le()?);
+        for (transaction_index, transaction) in block.transactions().iter().enumerate() {
+            for (transition_index, transition) in transaction.transitions().iter().enumerate() {
+                let location = (block.height(), transaction_index as u16, transition_index as u8);
+                for (index, serial_number) in transition.serial_numbers().enumerate() {
+                    let location = Self::to_location_bytes((location.0, location.1, location.2, index as u8))?;
+                    batch.insert(DataMap::SerialNumbers, serial_number.to_bytes_le()?, location);
+                }
+                for (index, commitment) in transition.commitments().enumerate() {
+                    let location = Self::to_location_bytes((location.0, location.1, location.2, index as u8))?;
+                    batch.insert(DataMap::Commitments, commitment.to_bytes_le()?, location);
+                }
+            }
+            for deployment in transaction.deployments() {
+                batch.insert(DataMap::Programs, deployment.program_id().to_bytes_le()?, to_bytes_le![
+                    block.height(),
+                    deployment
+                ]?);
+            }
+        }
+        batch.insert(DataMap::LedgerTree, ledger_tree_index.to_be_bytes(), block.hash().to_bytes_le()?);
+        batch.insert(DataMap::Metadata, LATEST_BLOCK_HEIGHT_KEY, block.height().to_bytes_le()?);
+        batch.insert(DataMap::Metadata, LATEST_BLOCK_HASH_KEY, block.hash().to_bytes_le()?);
+        Ok(batch)
+    }
+
     /// Returns the expected difficulty target given the previous block and expected next block details.
     pub fn compute_difficulty_target(
         anchor_block_header: &BlockHeader<N>,
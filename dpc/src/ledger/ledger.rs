@@ -18,15 +18,25 @@ use crate::prelude::*;
 
 use anyhow::{anyhow, Result};
 use rand::{CryptoRng, Rng};
-use std::{collections::HashMap, sync::atomic::AtomicBool};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{atomic::AtomicBool, Arc},
+};
 use time::OffsetDateTime;
 
+/// The maximum total serialized size of the unconfirmed transactions included in a mined block.
+const MAXIMUM_BLOCK_TRANSACTIONS_SIZE_IN_BYTES: usize = 2 * 1024 * 1024;
+/// The maximum number of orphan blocks retained by the ledger. As a fork must consist entirely of orphan blocks,
+/// this also bounds how far ahead of the canon chain an orphan block may be.
+const MAXIMUM_NUMBER_OF_ORPHAN_BLOCKS: usize = 256;
+
 #[derive(Clone, Debug)]
 pub struct Ledger<N: Network> {
     /// The canonical chain of blocks.
     canon_blocks: Blocks<N>,
-    /// The set of unknown orphan blocks.
-    orphan_blocks: HashMap<u32, Block<N>>,
+    /// The tree of orphan blocks that are candidates for forks, keyed by block hash.
+    orphan_blocks: HashMap<N::BlockHash, Block<N>>,
     /// The pool of unconfirmed transactions.
     memory_pool: MemoryPool<N>,
 }
@@ -37,6 +47,53 @@ impl<N: Network> Ledger<N> {
         Ok(Self { canon_blocks: Blocks::new()?, orphan_blocks: Default::default(), memory_pool: MemoryPool::new() })
     }
 
+    /// Opens the ledger in the given directory, resuming from the latest committed block.
+    /// If the directory does not contain a ledger, it is initialized with the genesis block.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::open_with_storage(Arc::new(FileStorage::open(path)?))
+    }
+
+    /// Opens the ledger from the given storage backend, resuming from the latest committed block.
+    /// If the storage is empty, it is initialized with the genesis block.
+    pub fn open_with_storage(storage: Arc<dyn Storage>) -> Result<Self> {
+        Ok(Self {
+            canon_blocks: Blocks::open(storage)?,
+            orphan_blocks: Default::default(),
+            memory_pool: MemoryPool::new(),
+        })
+    }
+
+    ///
+    /// Initializes the ledger from the given snapshot, if the snapshot ends in the given trusted block hash.
+    /// The blocks up to the trusted block are *not* re-verified, while blocks added after it are fully verified.
+    ///
+    pub fn from_snapshot(snapshot: &LedgerSnapshot<N>, trusted_block_hash: N::BlockHash) -> Result<Self> {
+        Ok(Self {
+            canon_blocks: Blocks::from_snapshot(snapshot, trusted_block_hash)?,
+            orphan_blocks: Default::default(),
+            memory_pool: MemoryPool::new(),
+        })
+    }
+
+    /// Returns a snapshot of the canon chain state at the latest block.
+    pub fn to_snapshot(&self) -> Result<LedgerSnapshot<N>> {
+        self.canon_blocks.to_snapshot()
+    }
+
+    ///
+    /// Sets the number of most recent blocks whose transactions are retained, and prunes the transactions
+    /// of all older blocks. Pruned blocks retain the indexes needed to check for double spends.
+    /// If `None` is provided, blocks added from now on are no longer pruned.
+    ///
+    pub fn set_pruning_depth(&mut self, pruning_depth: Option<u32>) -> Result<()> {
+        self.canon_blocks.set_pruning_depth(pruning_depth)
+    }
+
+    /// Returns the height of the latest block whose transactions were pruned, if any.
+    pub fn pruned_height(&self) -> Option<u32> {
+        self.canon_blocks.pruned_height()
+    }
+
     /// Returns the latest block height.
     pub fn latest_block_height(&self) -> u32 {
         self.canon_blocks.latest_block_height()
@@ -77,11 +134,96 @@ impl<N: Network> Ledger<N> {
         self.canon_blocks.latest_block()
     }
 
+    /// Returns the block hash given the block height.
+    pub fn get_block_hash(&self, height: u32) -> Result<N::BlockHash> {
+        self.canon_blocks.get_block_hash(height)
+    }
+
+    /// Returns the block given the block height.
+    pub fn get_block(&self, height: u32) -> Result<Block<N>> {
+        self.canon_blocks.get_block(height)
+    }
+
+    /// Returns the block height given the block hash.
+    pub fn get_block_height(&self, block_hash: &N::BlockHash) -> Result<u32> {
+        self.canon_blocks.get_block_height(block_hash)
+    }
+
+    /// Returns the block header given the block height.
+    pub fn get_block_header(&self, height: u32) -> Result<&BlockHeader<N>> {
+        self.canon_blocks.get_block_header(height)
+    }
+
+    /// Returns the block headers from the given start block height to the given end block height, inclusive.
+    pub fn get_block_headers(&self, start_height: u32, end_height: u32) -> Result<Vec<BlockHeader<N>>> {
+        self.canon_blocks.get_block_headers(start_height, end_height)
+    }
+
+    /// Returns the block given the block hash.
+    pub fn get_block_by_hash(&self, block_hash: &N::BlockHash) -> Result<Block<N>> {
+        self.canon_blocks.get_block(self.canon_blocks.get_block_height(block_hash)?)
+    }
+
+    /// Returns the block summary given the block height.
+    pub fn get_block_summary(&self, height: u32) -> Result<BlockSummary<N>> {
+        BlockSummary::new(&self.canon_blocks.get_block(height)?)
+    }
+
+    /// Returns the transaction given the transaction ID.
+    pub fn get_transaction(&self, transaction_id: &N::TransactionID) -> Result<Transaction<N>> {
+        self.canon_blocks.get_transaction(transaction_id)
+    }
+
+    /// Returns the block containing the given transition ID.
+    pub fn get_block_containing_transition(&self, transition_id: &N::TransitionID) -> Result<Block<N>> {
+        match self.canon_blocks.get_transition_location(transition_id) {
+            Some((height, _, _)) => self.canon_blocks.get_block(height),
+            None => Err(anyhow!("Transition {} does not exist in the canon chain", transition_id)),
+        }
+    }
+
+    ///
+    /// Returns the custom events emitted from the given start block height to the given end block height, inclusive,
+    /// as tuples of the block height, the ID of the emitting transition, and the event data.
+    ///
+    pub fn get_custom_events(
+        &self,
+        start_height: u32,
+        end_height: u32,
+    ) -> Result<Vec<(u32, N::TransitionID, Vec<u8>)>> {
+        if start_height > end_height {
+            return Err(anyhow!("Invalid block height range {}..={}", start_height, end_height));
+        }
+
+        let mut events = Vec::new();
+        for height in start_height..=end_height {
+            let transactions = self.canon_blocks.get_block_transactions(height)?;
+            for transition in transactions.iter().flat_map(Transaction::transitions) {
+                for event in transition.events() {
+                    if let Event::Custom(data) = event {
+                        events.push((height, transition.transition_id(), data.clone()));
+                    }
+                }
+            }
+        }
+        Ok(events)
+    }
+
     /// Returns `true` if the given ledger root exists on the canon chain.
     pub fn contains_ledger_root(&self, ledger_root: &N::LedgerRoot) -> bool {
         self.canon_blocks.contains_ledger_root(ledger_root)
     }
 
+    /// Returns `true` if the given program ID is deployed on the canon chain.
+    pub fn contains_program(&self, program_id: &N::ProgramID) -> bool {
+        self.canon_blocks.contains_program(program_id)
+    }
+
+    /// Returns the deployment of the given program ID on the canon chain.
+    pub fn get_program_deployment(&self, program_id: &N::ProgramID) -> Result<&Deployment<N>> {
+        self.canon_blocks.get_program_deployment(program_id)
+    }
+
     /// Returns `true` if the given block hash exists on the canon chain.
     pub fn contains_block_hash(&self, block_hash: &N::BlockHash) -> bool {
         self.canon_blocks.contains_block_hash(block_hash)
@@ -92,26 +234,94 @@ impl<N: Network> Ledger<N> {
         self.canon_blocks.contains_transaction(transaction)
     }
 
+    /// Returns `true` if the given block hash exists in the orphan blocks.
+    pub fn contains_orphan_block(&self, block_hash: &N::BlockHash) -> bool {
+        self.orphan_blocks.contains_key(block_hash)
+    }
+
+    /// Returns the memory pool of unconfirmed transactions.
+    pub fn memory_pool(&self) -> &MemoryPool<N> {
+        &self.memory_pool
+    }
+
+    /// Returns the number of orphan blocks.
+    pub fn num_orphan_blocks(&self) -> usize {
+        self.orphan_blocks.len()
+    }
+
     /// Adds the given canon block, if it is well-formed and does not already exist.
     /// Note: This method requires blocks to be added in order of canon block height.
     pub fn add_next_block(&mut self, block: &Block<N>) -> Result<()> {
         // Attempt to insert the block into canon.
         self.canon_blocks.add_next(block)?;
 
+        // Remove the block from the orphan blocks, if it exists.
+        self.orphan_blocks.remove(&block.hash());
+
+        // Remove the transactions that are no longer valid from the memory pool.
+        self.memory_pool.revalidate(&self.canon_blocks);
+
         Ok(())
     }
 
-    /// Adds the given orphan block, if it is well-formed and does not already exist.
+    ///
+    /// Adds the given orphan block, if it is well-formed and does not already exist, and switches the canon chain
+    /// to the heaviest fork that connects to the canon chain within the maximum fork depth.
+    /// If the heaviest fork fails to be applied, its branch is discarded, and an error is returned.
+    ///
     pub fn add_orphan_block(&mut self, block: &Block<N>) -> Result<()> {
         // Ensure the block does not exist in canon.
         if self.canon_blocks.contains_block_hash(&block.hash()) {
             return Err(anyhow!("Orphan block already exists in canon chain"));
         }
 
+        // Ensure the block does not exist in the orphan blocks.
+        if self.orphan_blocks.contains_key(&block.hash()) {
+            return Err(anyhow!("Orphan block already exists in the orphan blocks"));
+        }
+
+        // Ensure the block is within the maximum fork depth.
+        let latest_block_height = self.latest_block_height();
+        if block.height() + N::ALEO_MAXIMUM_FORK_DEPTH <= latest_block_height {
+            return Err(anyhow!("Orphan block {} exceeds the maximum fork depth", block.height()));
+        }
+
+        // Ensure the block is within the maximum distance ahead of the canon chain.
+        if block.height() > latest_block_height.saturating_add(MAXIMUM_NUMBER_OF_ORPHAN_BLOCKS as u32) {
+            return Err(anyhow!("Orphan block {} is too far ahead of the canon chain", block.height()));
+        }
+
+        // Ensure the maximum number of orphan blocks is not exceeded.
+        if self.orphan_blocks.len() >= MAXIMUM_NUMBER_OF_ORPHAN_BLOCKS {
+            return Err(anyhow!("The ledger exceeds the maximum number of orphan blocks"));
+        }
+
+        // Ensure the block is well-formed.
+        if !block.is_valid() {
+            return Err(anyhow!("Orphan block {} is invalid", block.height()));
+        }
+
         // Insert the block into the orphan blocks.
-        self.orphan_blocks.insert(block.height(), block.clone());
+        self.orphan_blocks.insert(block.hash(), block.clone());
 
-        Ok(())
+        // Attempt to switch to the heaviest fork.
+        self.update_canon_chain()
+    }
+
+    ///
+    /// Reverts the canon chain to the given block height, returning the removed blocks in ascending order of height.
+    /// The canon chain may only be reverted up to `N::ALEO_MAXIMUM_FORK_DEPTH` blocks.
+    ///
+    pub fn revert_to(&mut self, height: u32) -> Result<Vec<Block<N>>> {
+        let reverted_blocks = self.canon_blocks.revert_to(height)?;
+
+        // Remove the transactions that reference reverted ledger roots from the memory pool.
+        self.memory_pool.revalidate(&self.canon_blocks);
+
+        // Return the reverted transactions to the memory pool, if they are still valid.
+        self.add_reverted_transactions(&reverted_blocks);
+
+        Ok(reverted_blocks)
     }
 
     /// Adds the given unconfirmed transaction to the memory pool.
@@ -135,20 +345,22 @@ impl<N: Network> Ledger<N> {
             }
         }
 
+        // Ensure the transaction executes deployed programs, and does not redeploy a program.
+        self.canon_blocks.verify_programs(transaction)?;
+
         // Attempt to add the transaction into the memory pool.
         self.memory_pool.add_transaction(transaction)?;
 
         Ok(())
     }
 
-    /// Mines a new block and adds it to the canon blocks.
-    pub fn mine_next_block<R: Rng + CryptoRng>(
-        &mut self,
+    /// Returns a block template for the next block, with a coinbase transaction to the given recipient.
+    pub fn get_block_template<R: Rng + CryptoRng>(
+        &self,
         recipient: Address<N>,
         is_public: bool,
-        terminator: &AtomicBool,
         rng: &mut R,
-    ) -> Result<Record<N>> {
+    ) -> Result<BlockTemplate<N>> {
         // Prepare the new block.
         let previous_block_hash = self.latest_block_hash();
         let block_height = self.latest_block_height() + 1;
@@ -168,13 +380,16 @@ impl<N: Network> Ledger<N> {
         let amount = Block::<N>::block_reward(block_height);
         let (coinbase_transaction, coinbase_record) =
             Transaction::<N>::new_coinbase(recipient, amount, is_public, rng)?;
-        let transactions = Transactions::from(&[vec![coinbase_transaction], self.memory_pool.transactions()].concat())?;
+        let unconfirmed_transactions = self
+            .memory_pool
+            .select_for_block(MAXIMUM_BLOCK_TRANSACTIONS_SIZE_IN_BYTES, Self::maximum_unconfirmed_transactions());
+        let transactions = Transactions::from(&[vec![coinbase_transaction], unconfirmed_transactions].concat())?;
 
         // Retrieve the current ledger root.
         let previous_ledger_root = self.canon_blocks.latest_ledger_root();
 
         // Construct the block template.
-        let template = BlockTemplate::new(
+        Ok(BlockTemplate::new(
             previous_block_hash,
             block_height,
             block_timestamp,
@@ -182,8 +397,20 @@ impl<N: Network> Ledger<N> {
             cumulative_weight,
             previous_ledger_root,
             transactions,
-            coinbase_record.clone(),
-        );
+            coinbase_record,
+        ))
+    }
+
+    /// Mines a new block and adds it to the canon blocks.
+    pub fn mine_next_block<R: Rng + CryptoRng>(
+        &mut self,
+        recipient: Address<N>,
+        is_public: bool,
+        terminator: &AtomicBool,
+        rng: &mut R,
+    ) -> Result<Record<N>> {
+        let template = self.get_block_template(recipient, is_public, rng)?;
+        let coinbase_record = template.coinbase_record().clone();
 
         // Mine the next block.
         let block = Block::mine(&template, terminator, rng)?;
@@ -191,12 +418,114 @@ impl<N: Network> Ledger<N> {
         // Attempt to add the block to the canon chain.
         self.add_next_block(&block)?;
 
-        // On success, clear the memory pool of its transactions.
-        self.memory_pool.clear_all_transactions();
-
         Ok(coinbase_record)
     }
 
+    ///
+    /// Switches the canon chain to the heaviest fork of orphan blocks, if its cumulative weight
+    /// exceeds that of the canon chain, and it connects to the canon chain within the maximum fork depth.
+    /// If the fork fails to be applied, the fork and its descendants are discarded from the orphan blocks.
+    ///
+    fn update_canon_chain(&mut self) -> Result<()> {
+        // Find the heaviest fork that connects to the canon chain.
+        let heaviest_fork = self
+            .orphan_blocks
+            .keys()
+            .filter_map(|block_hash| self.to_fork(block_hash))
+            .max_by_key(|(_, fork)| fork.last().map(Block::cumulative_weight));
+
+        // Ensure the fork is heavier than the canon chain.
+        let (fork_height, fork) = match heaviest_fork {
+            Some((fork_height, fork)) => (fork_height, fork),
+            None => return Ok(()),
+        };
+        let tip = match fork.last() {
+            Some(tip) if tip.cumulative_weight() > self.latest_cumulative_weight()? => tip,
+            _ => return Ok(()),
+        };
+
+        let reverted_blocks = match self.canon_blocks.reorganize(fork_height, &fork) {
+            Ok(reverted_blocks) => reverted_blocks,
+            Err(error) => {
+                self.remove_orphan_branch(&fork[0].hash());
+                return Err(anyhow!("Failed to switch to the fork at block {}: {}", tip.height(), error));
+            }
+        };
+
+        // Move the fork blocks out of the orphan blocks.
+        for block in &fork {
+            self.orphan_blocks.remove(&block.hash());
+        }
+
+        // Remove the transactions that are no longer valid from the memory pool.
+        self.memory_pool.revalidate(&self.canon_blocks);
+
+        // Move the reverted blocks into the orphan blocks, so the canon chain may switch back to them.
+        for block in &reverted_blocks {
+            self.orphan_blocks.insert(block.hash(), block.clone());
+        }
+        self.add_reverted_transactions(&reverted_blocks);
+
+        // Remove the orphan blocks that now exceed the maximum fork depth.
+        let latest_block_height = self.latest_block_height();
+        self.orphan_blocks.retain(|_, block| block.height() + N::ALEO_MAXIMUM_FORK_DEPTH > latest_block_height);
+        Ok(())
+    }
+
+    /// Removes the given orphan block, and all orphan blocks that descend from it.
+    fn remove_orphan_branch(&mut self, block_hash: &N::BlockHash) {
+        let mut removed_block_hashes = vec![*block_hash];
+        while let Some(block_hash) = removed_block_hashes.pop() {
+            self.orphan_blocks.remove(&block_hash);
+            removed_block_hashes.extend(
+                self.orphan_blocks.values().filter(|block| block.previous_block_hash() == block_hash).map(Block::hash),
+            );
+        }
+    }
+
+    ///
+    /// Returns the fork height and the blocks of the fork that ends in the given orphan block, in ascending order of height,
+    /// if the fork connects to the canon chain within the maximum fork depth.
+    ///
+    fn to_fork(&self, block_hash: &N::BlockHash) -> Option<(u32, Vec<Block<N>>)> {
+        // Walk back through the orphan blocks, until reaching the canon chain.
+        let mut fork = Vec::new();
+        let mut block_hash = *block_hash;
+        while let Some(block) = self.orphan_blocks.get(&block_hash) {
+            fork.push(block.clone());
+            block_hash = block.previous_block_hash();
+        }
+        fork.reverse();
+
+        // Ensure the fork connects to the canon chain, at the expected block height.
+        let fork_height = fork.first()?.height().checked_sub(1)?;
+        if self.canon_blocks.get_block_hash(fork_height).ok()? != block_hash {
+            return None;
+        }
+
+        // Ensure the fork is within the maximum fork depth.
+        match self.latest_block_height().saturating_sub(fork_height) <= N::ALEO_MAXIMUM_FORK_DEPTH {
+            true => Some((fork_height, fork)),
+            false => None,
+        }
+    }
+
+    /// Adds the non-coinbase transactions from the given reverted blocks to the memory pool, if they are still valid.
+    fn add_reverted_transactions(&mut self, reverted_blocks: &[Block<N>]) {
+        for transaction in reverted_blocks.iter().flat_map(|block| block.transactions().iter()) {
+            if !transaction.value_balance().is_negative() && !self.memory_pool.contains_transaction(transaction) {
+                // Reverted transactions may conflict with the new canon chain, in which case they are dropped.
+                let _ = self.add_unconfirmed_transaction(transaction);
+            }
+        }
+    }
+
+    /// Returns the maximum number of unconfirmed transactions in a mined block, which is the capacity
+    /// of the transactions tree in the block header, less the coinbase transaction.
+    fn maximum_unconfirmed_transactions() -> usize {
+        (1usize << N::HEADER_TRANSACTIONS_TREE_DEPTH) - 1
+    }
+
     /// Returns the ledger tree.
     pub fn to_ledger_tree(&self) -> &LedgerTree<N> {
         self.canon_blocks.to_ledger_tree()
@@ -214,8 +543,9 @@ impl<N: Network> Ledger<N> {
 mod tests {
     use super::*;
     use crate::{testnet1::Testnet1, testnet2::Testnet2};
+    use snarkvm_utilities::{FromBytes, ToBytes};
 
-    use rand::thread_rng;
+    use rand::{thread_rng, Rng};
 
     #[test]
     fn test_new() {
@@ -246,4 +576,358 @@ mod tests {
             assert_eq!(1, ledger.latest_block_height());
         }
     }
+
+    #[test]
+    fn test_open() {
+        let rng = &mut thread_rng();
+        let directory = std::env::temp_dir().join(format!("snarkvm-ledger-{}", rng.gen::<u64>()));
+
+        let recipient = Account::<Testnet2>::new(rng);
+        let (block_height, block_hash, ledger_root) = {
+            let mut ledger = Ledger::<Testnet2>::open(&directory).unwrap();
+            assert_eq!(0, ledger.latest_block_height());
+
+            ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+            ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+            assert_eq!(2, ledger.latest_block_height());
+
+            (ledger.latest_block_height(), ledger.latest_block_hash(), ledger.latest_ledger_root())
+        };
+
+        // Ensure the ledger resumes from the latest committed block.
+        let mut ledger = Ledger::<Testnet2>::open(&directory).unwrap();
+        assert_eq!(block_height, ledger.latest_block_height());
+        assert_eq!(block_hash, ledger.latest_block_hash());
+        assert_eq!(ledger_root, ledger.latest_ledger_root());
+
+        // Ensure the resumed ledger can extend the chain.
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        assert_eq!(3, ledger.latest_block_height());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_revert_to() {
+        let rng = &mut thread_rng();
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let recipient = Account::<Testnet2>::new(rng);
+        let genesis_hash = ledger.latest_block_hash();
+        let genesis_ledger_root = ledger.latest_ledger_root();
+
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        assert_eq!(2, ledger.latest_block_height());
+
+        let reverted_blocks = ledger.revert_to(0).unwrap();
+        assert_eq!(vec![1, 2], reverted_blocks.iter().map(Block::height).collect::<Vec<_>>());
+        assert_eq!(0, ledger.latest_block_height());
+        assert_eq!(genesis_hash, ledger.latest_block_hash());
+        assert_eq!(genesis_ledger_root, ledger.latest_ledger_root());
+        for block in &reverted_blocks {
+            assert!(!ledger.contains_block_hash(&block.hash()));
+            assert!(block
+                .serial_numbers()
+                .all(|serial_number| !ledger.canon_blocks.contains_serial_number(serial_number)));
+        }
+
+        // Ensure the reverted blocks can be re-added to the canon chain.
+        for block in &reverted_blocks {
+            ledger.add_next_block(block).unwrap();
+        }
+        assert_eq!(2, ledger.latest_block_height());
+
+        // Ensure the ledger cannot revert to a future block height.
+        assert!(ledger.revert_to(3).is_err());
+    }
+
+    #[test]
+    fn test_fork_choice() {
+        let rng = &mut thread_rng();
+        let recipient = Account::<Testnet2>::new(rng);
+
+        // Mine a canon chain of 1 block, and a competing fork of 2 blocks.
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+
+        let mut fork = Ledger::<Testnet2>::new().unwrap();
+        fork.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        fork.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        let fork_blocks = vec![fork.canon_blocks.get_block(1).unwrap(), fork.latest_block().unwrap()];
+
+        // Ensure a disconnected orphan block does not change the canon chain.
+        let canon_hash = ledger.latest_block_hash();
+        ledger.add_orphan_block(&fork_blocks[1]).unwrap();
+        assert_eq!(canon_hash, ledger.latest_block_hash());
+        assert!(ledger.contains_orphan_block(&fork_blocks[1].hash()));
+
+        // Ensure the ledger switches to the heavier fork once it connects.
+        ledger.add_orphan_block(&fork_blocks[0]).unwrap();
+        assert_eq!(2, ledger.latest_block_height());
+        assert_eq!(fork.latest_block_hash(), ledger.latest_block_hash());
+        assert_eq!(fork.latest_ledger_root(), ledger.latest_ledger_root());
+
+        // Ensure the previous canon block is retained as an orphan block.
+        assert!(ledger.contains_orphan_block(&canon_hash));
+        assert!(!ledger.contains_orphan_block(&fork_blocks[0].hash()));
+        assert!(!ledger.contains_orphan_block(&fork_blocks[1].hash()));
+    }
+
+    /// Mines a block with the given previous block hash, height, and cumulative weight, outside of the ledger.
+    fn mine_unchecked_block<R: Rng + CryptoRng>(
+        ledger: &Ledger<Testnet2>,
+        previous_block_hash: <Testnet2 as Network>::BlockHash,
+        block_height: u32,
+        cumulative_weight: u128,
+        recipient: Address<Testnet2>,
+        rng: &mut R,
+    ) -> Block<Testnet2> {
+        let (coinbase_transaction, coinbase_record) =
+            Transaction::new_coinbase(recipient, Block::<Testnet2>::block_reward(block_height), true, rng).unwrap();
+        let template = BlockTemplate::new(
+            previous_block_hash,
+            block_height,
+            OffsetDateTime::now_utc().unix_timestamp(),
+            ledger.latest_block_difficulty_target().unwrap(),
+            cumulative_weight,
+            ledger.latest_ledger_root(),
+            Transactions::from(&[coinbase_transaction]).unwrap(),
+            coinbase_record,
+        );
+        Block::mine(&template, &AtomicBool::new(false), rng).unwrap()
+    }
+
+    #[test]
+    fn test_invalid_fork_is_discarded() {
+        let rng = &mut thread_rng();
+        let recipient = Account::<Testnet2>::new(rng);
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        let canon_hash = ledger.latest_block_hash();
+
+        // Mine a fork of 2 blocks that claims a higher cumulative weight than its difficulty targets add up to.
+        let genesis_hash = ledger.get_block_hash(0).unwrap();
+        let first = mine_unchecked_block(&ledger, genesis_hash, 1, u128::MAX / 2, recipient.address(), rng);
+        let second = mine_unchecked_block(&ledger, first.hash(), 2, u128::MAX / 2 + 1, recipient.address(), rng);
+
+        // Ensure the ledger fails to switch to the fork once it connects, and discards the whole fork.
+        ledger.add_orphan_block(&second).unwrap();
+        assert!(ledger.add_orphan_block(&first).is_err());
+        assert_eq!(canon_hash, ledger.latest_block_hash());
+        assert!(!ledger.contains_orphan_block(&first.hash()));
+        assert!(!ledger.contains_orphan_block(&second.hash()));
+        assert_eq!(0, ledger.num_orphan_blocks());
+    }
+
+    #[test]
+    fn test_orphan_block_distance() {
+        let rng = &mut thread_rng();
+        let recipient = Account::<Testnet2>::new(rng);
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+
+        // Ensure an orphan block too far ahead of the canon chain is rejected.
+        let block_height = MAXIMUM_NUMBER_OF_ORPHAN_BLOCKS as u32 + 1;
+        let block =
+            mine_unchecked_block(&ledger, ledger.latest_block_hash(), block_height, 0, recipient.address(), rng);
+        assert!(ledger.add_orphan_block(&block).is_err());
+        assert_eq!(0, ledger.num_orphan_blocks());
+    }
+
+    #[test]
+    fn test_explorer_queries() {
+        let rng = &mut thread_rng();
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let recipient = Account::<Testnet2>::new(rng);
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+
+        // Ensure blocks are retrievable by height and by hash.
+        let block = ledger.get_block(1).unwrap();
+        assert_eq!(block, ledger.get_block_by_hash(&block.hash()).unwrap());
+        assert_eq!(1, ledger.get_block_height(&block.hash()).unwrap());
+        assert_eq!(block.hash(), ledger.get_block_hash(1).unwrap());
+        assert!(ledger.get_block(3).is_err());
+
+        // Ensure block header ranges are inclusive, and bounded by the canon chain.
+        let headers = ledger.get_block_headers(0, 2).unwrap();
+        assert_eq!(vec![0, 1, 2], headers.iter().map(BlockHeader::height).collect::<Vec<_>>());
+        assert_eq!(block.header(), ledger.get_block_header(1).unwrap());
+        assert!(ledger.get_block_headers(2, 1).is_err());
+        assert!(ledger.get_block_headers(1, 3).is_err());
+
+        // Ensure transactions and transitions resolve to their block.
+        let transaction = block.to_coinbase_transaction().unwrap();
+        assert_eq!(transaction, ledger.get_transaction(&transaction.transaction_id()).unwrap());
+        let transition_id = transaction.transitions()[0].transition_id();
+        assert_eq!(block, ledger.get_block_containing_transition(&transition_id).unwrap());
+
+        // Ensure the block summary reflects the block.
+        let summary = ledger.get_block_summary(1).unwrap();
+        assert_eq!(block.hash(), summary.block_hash());
+        assert_eq!(1, summary.num_transactions());
+        assert_eq!(Block::<Testnet2>::block_reward(1), summary.block_reward());
+
+        // Ensure the coinbase transactions emit no custom events.
+        assert!(ledger.get_custom_events(0, 2).unwrap().is_empty());
+
+        // Ensure reverted blocks are no longer retrievable.
+        ledger.revert_to(0).unwrap();
+        assert!(ledger.get_block_by_hash(&block.hash()).is_err());
+        assert!(ledger.get_transaction(&transaction.transaction_id()).is_err());
+        assert!(ledger.get_block_containing_transition(&transition_id).is_err());
+    }
+
+    #[test]
+    fn test_from_snapshot() {
+        let rng = &mut thread_rng();
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let recipient = Account::<Testnet2>::new(rng);
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+
+        // Ensure the snapshot survives serialization.
+        let snapshot = ledger.to_snapshot().unwrap();
+        let snapshot = LedgerSnapshot::<Testnet2>::read_le(&snapshot.to_bytes_le().unwrap()[..]).unwrap();
+        assert_eq!(ledger.to_snapshot().unwrap(), snapshot);
+        assert_eq!(2, snapshot.block_height());
+
+        // Ensure the snapshot is only imported from the trusted block hash.
+        assert!(Ledger::<Testnet2>::from_snapshot(&snapshot, ledger.get_block_hash(1).unwrap()).is_err());
+
+        // Ensure the snapshot serial numbers and commitments must match the blocks before the trusted block.
+        let mut tampered_snapshot = snapshot.clone();
+        let index = tampered_snapshot.serial_numbers.iter().position(|(_, (height, _, _))| *height == 1).unwrap();
+        let (first, second) =
+            (tampered_snapshot.serial_numbers[index].0, tampered_snapshot.serial_numbers[index + 1].0);
+        tampered_snapshot.serial_numbers[index].0 = second;
+        tampered_snapshot.serial_numbers[index + 1].0 = first;
+        assert!(Ledger::<Testnet2>::from_snapshot(&tampered_snapshot, ledger.latest_block_hash()).is_err());
+
+        let mut tampered_snapshot = snapshot.clone();
+        tampered_snapshot.commitments.retain(|(_, (height, _, _))| *height != 1);
+        assert!(Ledger::<Testnet2>::from_snapshot(&tampered_snapshot, ledger.latest_block_hash()).is_err());
+
+        let mut synced_ledger = Ledger::<Testnet2>::from_snapshot(&snapshot, ledger.latest_block_hash()).unwrap();
+        assert_eq!(ledger.latest_block_height(), synced_ledger.latest_block_height());
+        assert_eq!(ledger.latest_block_hash(), synced_ledger.latest_block_hash());
+        assert_eq!(ledger.latest_ledger_root(), synced_ledger.latest_ledger_root());
+        for serial_number in ledger.get_block(1).unwrap().serial_numbers() {
+            assert!(synced_ledger.canon_blocks.contains_serial_number(serial_number));
+        }
+
+        // Ensure the synced ledger accepts the next block, and may not revert past the checkpoint block.
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        synced_ledger.add_next_block(&ledger.latest_block().unwrap()).unwrap();
+        assert_eq!(ledger.latest_ledger_root(), synced_ledger.latest_ledger_root());
+        assert!(synced_ledger.revert_to(2).is_ok());
+        assert!(synced_ledger.revert_to(1).is_err());
+    }
+
+    #[test]
+    fn test_pruning() {
+        let rng = &mut thread_rng();
+        let directory = std::env::temp_dir().join(format!("snarkvm-ledger-{}", rng.gen::<u64>()));
+
+        let recipient = Account::<Testnet2>::new(rng);
+        let serial_numbers = {
+            let mut ledger = Ledger::<Testnet2>::open(&directory).unwrap();
+            assert!(ledger.set_pruning_depth(Some(0)).is_err());
+            ledger.set_pruning_depth(Some(2)).unwrap();
+            assert_eq!(None, ledger.pruned_height());
+
+            ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+            ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+            assert_eq!(Some(0), ledger.pruned_height());
+            let serial_numbers = ledger.get_block(1).unwrap().serial_numbers().cloned().collect::<Vec<_>>();
+
+            ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+            assert_eq!(Some(1), ledger.pruned_height());
+
+            // Ensure queries for pruned transactions fail, while the retained blocks remain available.
+            let error = ledger.get_block(1).unwrap_err();
+            assert!(error.to_string().contains("pruned"));
+            assert!(ledger.get_block(2).is_ok());
+            assert!(ledger.latest_block().is_ok());
+            assert!(ledger.get_block_header(1).is_ok());
+            assert!(ledger.revert_to(1).is_err());
+            for serial_number in &serial_numbers {
+                assert!(ledger.canon_blocks.contains_serial_number(serial_number));
+            }
+
+            serial_numbers
+        };
+
+        // Ensure the pruned ledger resumes with the indexes needed for double-spend checks.
+        let mut ledger = Ledger::<Testnet2>::open(&directory).unwrap();
+        assert_eq!(Some(1), ledger.pruned_height());
+        assert!(ledger.get_block(1).unwrap_err().to_string().contains("pruned"));
+        for serial_number in &serial_numbers {
+            assert!(ledger.canon_blocks.contains_serial_number(serial_number));
+        }
+
+        // Ensure the resumed ledger extends the chain, without pruning until a pruning depth is set.
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        assert_eq!(Some(1), ledger.pruned_height());
+        ledger.set_pruning_depth(Some(1)).unwrap();
+        assert_eq!(Some(3), ledger.pruned_height());
+        for serial_number in &serial_numbers {
+            assert!(ledger.canon_blocks.contains_serial_number(serial_number));
+        }
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_to_ledger_proof() {
+        let rng = &mut thread_rng();
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let recipient = Account::<Testnet2>::new(rng);
+
+        let record = ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+
+        // Ensure the ledger proof is generated for an existing commitment.
+        let ledger_proof = ledger.to_ledger_proof(record.commitment()).unwrap();
+        assert_eq!(ledger.latest_ledger_root(), ledger_proof.ledger_root());
+        assert_eq!(record.commitment(), ledger_proof.commitment());
+
+        // Ensure the ledger proof fails for a non-existent commitment.
+        let record = ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        ledger.revert_to(2).unwrap();
+        assert!(ledger.to_ledger_proof(record.commitment()).is_err());
+    }
+
+    #[test]
+    fn test_redeploy_program() {
+        let rng = &mut thread_rng();
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let account = Account::<Testnet2>::new(rng);
+        assert!(ledger.contains_program(Testnet2::noop_program_id()));
+
+        // Craft a transaction that deploys the noop program, which is deployed with the network.
+        let record = ledger.mine_next_block(account.address(), true, &AtomicBool::new(false), rng).unwrap();
+        let ledger_proof = ledger.to_ledger_proof(record.commitment()).unwrap();
+        let request = Request::new_deploy(
+            account.private_key(),
+            vec![record],
+            vec![ledger_proof, LedgerProof::default()],
+            Deployment::new_noop().unwrap(),
+            AleoAmount::ZERO,
+            rng,
+        )
+        .unwrap();
+        let transaction = Transaction::new(ledger.canon_blocks.to_ledger_tree().clone(), &request, rng).unwrap();
+        assert!(transaction.is_deployment());
+        assert!(transaction.transitions()[0].to_deployment().unwrap().is_some());
+
+        // Ensure the redeployment is rejected.
+        assert!(ledger.canon_blocks.verify_programs(&transaction).is_err());
+        assert!(ledger.add_unconfirmed_transaction(&transaction).is_err());
+    }
 }
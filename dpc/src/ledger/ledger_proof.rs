@@ -19,7 +19,7 @@ use snarkvm_algorithms::merkle_tree::MerklePath;
 use snarkvm_utilities::{FromBytes, ToBytes};
 
 use anyhow::{anyhow, Result};
-use std::io::{Read, Result as IoResult, Write};
+use std::io::{Error, ErrorKind, Read, Result as IoResult, Write};
 
 /// A ledger proof of inclusion.
 #[derive(Derivative)]
@@ -132,10 +132,15 @@ impl<N: Network> FromBytes for LedgerProof<N> {
     fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
         let ledger_root = FromBytes::read_le(&mut reader)?;
         let ledger_root_inclusion_proof = FromBytes::read_le(&mut reader)?;
-        let record_proof = FromBytes::read_le(&mut reader)?;
+        let record_proof: RecordProof<N> = FromBytes::read_le(&mut reader)?;
 
-        Ok(Self::new(ledger_root, ledger_root_inclusion_proof, record_proof)
-            .expect("Failed to deserialize a ledger inclusion proof"))
+        // A dummy ledger proof, which is not for a record in the ledger, is read back as it was written.
+        if record_proof.block_hash() == Default::default() {
+            return Ok(Self { ledger_root, ledger_root_inclusion_proof, record_proof });
+        }
+
+        Self::new(ledger_root, ledger_root_inclusion_proof, record_proof)
+            .map_err(|error| Error::new(ErrorKind::Other, format!("{}", error)))
     }
 }
 
@@ -0,0 +1,330 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::prelude::*;
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+
+///
+/// A header-only view of the canon chain, which verifies each block header it is given,
+/// and maintains the ledger tree to verify ledger proofs without any block transactions.
+///
+#[derive(Clone, Debug)]
+pub struct LightClient<N: Network> {
+    /// The current block height.
+    current_height: u32,
+    /// The current block hash.
+    current_hash: N::BlockHash,
+    /// The current ledger tree.
+    ledger_tree: LedgerTree<N>,
+    /// The chain of previous block hashes.
+    previous_hashes: HashMap<u32, N::BlockHash>,
+    /// The chain of block headers.
+    headers: HashMap<u32, BlockHeader<N>>,
+    /// The index of previous ledger roots to block heights.
+    ledger_roots: HashMap<N::LedgerRoot, u32>,
+}
+
+impl<N: Network> LightClient<N> {
+    ///
+    /// Initializes a new instance of `LightClient` with the genesis block header.
+    ///
+    pub fn new() -> Result<Self> {
+        let genesis_block = N::genesis_block();
+        let height = genesis_block.height();
+
+        let mut light_client = Self {
+            current_height: height,
+            current_hash: genesis_block.hash(),
+            ledger_tree: LedgerTree::<N>::new()?,
+            previous_hashes: Default::default(),
+            headers: Default::default(),
+            ledger_roots: Default::default(),
+        };
+
+        light_client.ledger_tree.add(&genesis_block.hash())?;
+        light_client.previous_hashes.insert(height, genesis_block.previous_block_hash());
+        light_client.headers.insert(height, genesis_block.header().clone());
+        light_client.ledger_roots.insert(genesis_block.header().previous_ledger_root(), height);
+
+        Ok(light_client)
+    }
+
+    /// Returns the latest block height.
+    pub fn latest_block_height(&self) -> u32 {
+        self.current_height
+    }
+
+    /// Returns the latest block hash.
+    pub fn latest_block_hash(&self) -> N::BlockHash {
+        self.current_hash
+    }
+
+    /// Returns the latest ledger root.
+    pub fn latest_ledger_root(&self) -> N::LedgerRoot {
+        self.ledger_tree.root()
+    }
+
+    /// Returns the latest block header.
+    pub fn latest_block_header(&self) -> Result<&BlockHeader<N>> {
+        self.get_block_header(self.current_height)
+    }
+
+    /// Returns the block header given the block height.
+    pub fn get_block_header(&self, height: u32) -> Result<&BlockHeader<N>> {
+        match self.headers.get(&height) {
+            Some(header) => Ok(header),
+            None => Err(anyhow!("Block header {} missing from light client", height)),
+        }
+    }
+
+    /// Returns the previous block hash given the block height.
+    pub fn get_previous_block_hash(&self, height: u32) -> Result<N::BlockHash> {
+        match self.previous_hashes.get(&height) {
+            Some(previous_hash) => Ok(*previous_hash),
+            None => Err(anyhow!("Previous block hash {} missing from light client", height)),
+        }
+    }
+
+    /// Returns the block hash given the block height.
+    pub fn get_block_hash(&self, height: u32) -> Result<N::BlockHash> {
+        if height > self.current_height {
+            return Err(anyhow!("Given block height {} is greater than current height", height));
+        }
+
+        match height == self.current_height {
+            true => Ok(self.current_hash),
+            false => self.get_previous_block_hash(height + 1),
+        }
+    }
+
+    /// Returns the block height given the block hash.
+    pub fn get_block_height(&self, block_hash: &N::BlockHash) -> Result<u32> {
+        match self.ledger_tree.get_block_hash_index(block_hash) {
+            Some(height) => Ok(*height),
+            None => Err(anyhow!("Block {} does not exist in the light client", block_hash)),
+        }
+    }
+
+    /// Returns `true` if the given block hash exists.
+    pub fn contains_block_hash(&self, block_hash: &N::BlockHash) -> bool {
+        self.ledger_tree.contains_block_hash(block_hash)
+    }
+
+    /// Returns `true` if the given ledger root exists.
+    pub fn contains_ledger_root(&self, ledger_root: &N::LedgerRoot) -> bool {
+        *ledger_root == self.latest_ledger_root() || self.ledger_roots.contains_key(ledger_root)
+    }
+
+    ///
+    /// Adds the given block header as the next block header in the chain.
+    ///
+    pub fn add_next_header(&mut self, header: &BlockHeader<N>) -> Result<()> {
+        // Ensure the block header itself is valid, including its PoSW proof.
+        if !header.is_valid() {
+            return Err(anyhow!("The given block header is invalid"));
+        }
+
+        // Ensure the next block height is correct.
+        let height = header.height();
+        if self.current_height + 1 != height {
+            return Err(anyhow!("The given block header has an incorrect block height"));
+        }
+
+        // Ensure the block header commits to the current ledger root.
+        if header.previous_ledger_root() != self.latest_ledger_root() {
+            return Err(anyhow!("The given block header has an incorrect ledger root"));
+        }
+
+        // Ensure the block hash does not already exist.
+        let block_hash = Block::compute_block_hash(self.current_hash, header)?;
+        if self.contains_block_hash(&block_hash) {
+            return Err(anyhow!("The given block hash already exists in the light client"));
+        }
+
+        // Ensure the block header follows the current block header.
+        Blocks::verify_next_header(self.latest_block_header()?, header)?;
+
+        // Add the block header to the light client.
+        let mut light_client = self.clone();
+        light_client.ledger_tree.add(&block_hash)?;
+        light_client.previous_hashes.insert(height, self.current_hash);
+        light_client.headers.insert(height, header.clone());
+        light_client.ledger_roots.insert(header.previous_ledger_root(), height);
+        light_client.current_height = height;
+        light_client.current_hash = block_hash;
+
+        *self = light_client;
+        Ok(())
+    }
+
+    ///
+    /// Reverts the light client to the given block height, and returns the reverted block headers.
+    ///
+    pub fn revert_to(&mut self, height: u32) -> Result<Vec<BlockHeader<N>>> {
+        // Ensure the given block height is not ahead of the chain.
+        if height > self.current_height {
+            return Err(anyhow!("Cannot revert to block {}, as it is ahead of block {}", height, self.current_height));
+        }
+
+        // Ensure the revert does not exceed the maximum fork depth.
+        if self.current_height - height > N::ALEO_MAXIMUM_FORK_DEPTH {
+            return Err(anyhow!(
+                "Cannot revert {} blocks, as it exceeds the maximum fork depth of {}",
+                self.current_height - height,
+                N::ALEO_MAXIMUM_FORK_DEPTH
+            ));
+        }
+
+        // Retrieve the block headers to revert, and the block hashes that remain.
+        let reverted_headers = (height + 1..=self.current_height)
+            .map(|h| self.get_block_header(h).cloned())
+            .collect::<Result<Vec<_>>>()?;
+        let block_hashes = (0..=height).map(|h| self.get_block_hash(h)).collect::<Result<Vec<_>>>()?;
+
+        let mut light_client = self.clone();
+        for header in &reverted_headers {
+            light_client.previous_hashes.remove(&header.height());
+            light_client.headers.remove(&header.height());
+            light_client.ledger_roots.remove(&header.previous_ledger_root());
+        }
+
+        // Rebuild the ledger tree from the remaining block hashes.
+        light_client.ledger_tree = LedgerTree::<N>::new()?;
+        light_client.ledger_tree.add_all(&block_hashes)?;
+        light_client.current_height = height;
+        light_client.current_hash = block_hashes[height as usize];
+
+        *self = light_client;
+        Ok(reverted_headers)
+    }
+
+    ///
+    /// Verifies the given ledger proof against the block headers in the light client,
+    /// ensuring its commitment belongs to a block in the canon chain.
+    ///
+    pub fn verify_ledger_proof(&self, ledger_proof: &LedgerProof<N>) -> Result<()> {
+        // Ensure the ledger root is a current or previous ledger root.
+        let ledger_root = ledger_proof.ledger_root();
+        if !self.contains_ledger_root(&ledger_root) {
+            return Err(anyhow!("Ledger root {} does not exist in the light client", ledger_root));
+        }
+
+        // Ensure the ledger root inclusion proof is valid.
+        let block_hash = ledger_proof.block_hash();
+        if !ledger_proof.ledger_root_inclusion_proof().verify(&ledger_root, &block_hash)? {
+            return Err(anyhow!("Block hash {} does not belong to ledger root {}", block_hash, ledger_root));
+        }
+
+        // Ensure the block hash and its block header belong to the canon chain.
+        let height = self.get_block_height(&block_hash)?;
+        let header = self.get_block_header(height)?;
+        if ledger_proof.previous_block_hash() != self.get_previous_block_hash(height)? {
+            return Err(anyhow!("The ledger proof has an incorrect previous block hash for block {}", height));
+        }
+        if ledger_proof.block_header_root() != header.to_header_root()? {
+            return Err(anyhow!("The ledger proof has an incorrect block header root for block {}", height));
+        }
+        if ledger_proof.transactions_root() != header.transactions_root() {
+            return Err(anyhow!("The ledger proof has an incorrect transactions root for block {}", height));
+        }
+
+        // Ensure the block header inclusion proof is valid.
+        let transactions_root = ledger_proof.transactions_root();
+        if !ledger_proof.block_header_inclusion_proof().verify(&ledger_proof.block_header_root(), &transactions_root)? {
+            return Err(anyhow!("Transactions root {} does not belong to block {}", transactions_root, height));
+        }
+
+        // Ensure the transactions inclusion proof is valid.
+        let transaction_id = ledger_proof.transaction_id();
+        if !ledger_proof.transactions_inclusion_proof().verify(&transactions_root, &transaction_id)? {
+            return Err(anyhow!(
+                "Transaction {} does not belong to transactions root {}",
+                transaction_id,
+                transactions_root
+            ));
+        }
+
+        // Ensure the transaction inclusion proof is valid.
+        let transition_id = ledger_proof.transition_id();
+        if !ledger_proof.transaction_inclusion_proof().verify(&transaction_id, &transition_id)? {
+            return Err(anyhow!("Transition {} does not belong to transaction {}", transition_id, transaction_id));
+        }
+
+        // Ensure the transition inclusion proof is valid.
+        let commitment = ledger_proof.commitment();
+        if !ledger_proof.transition_inclusion_proof().verify(&transition_id, &commitment)? {
+            return Err(anyhow!("Commitment {} does not belong to transition {}", commitment, transition_id));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    use rand::thread_rng;
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn test_light_client() {
+        let rng = &mut thread_rng();
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let recipient = Account::<Testnet2>::new(rng);
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+
+        // Ensure the light client follows the headers of the ledger.
+        let mut light_client = LightClient::<Testnet2>::new().unwrap();
+        for header in ledger.get_block_headers(1, 2).unwrap() {
+            light_client.add_next_header(&header).unwrap();
+        }
+        assert_eq!(ledger.latest_block_height(), light_client.latest_block_height());
+        assert_eq!(ledger.latest_block_hash(), light_client.latest_block_hash());
+        assert_eq!(ledger.latest_ledger_root(), light_client.latest_ledger_root());
+
+        // Ensure the light client rejects headers out of order.
+        assert!(light_client.add_next_header(ledger.get_block_header(1).unwrap()).is_err());
+        assert!(light_client.add_next_header(ledger.get_block_header(2).unwrap()).is_err());
+
+        // Ensure the light client verifies a ledger proof for the latest coinbase record.
+        let commitment = *ledger.latest_block().unwrap().commitments().next().unwrap();
+        let ledger_proof = ledger.to_ledger_proof(commitment).unwrap();
+        light_client.verify_ledger_proof(&ledger_proof).unwrap();
+
+        // Ensure the light client verifies a ledger proof for a coinbase record in an older block.
+        let commitment = *ledger.get_block(1).unwrap().commitments().next().unwrap();
+        let older_ledger_proof = ledger.to_ledger_proof(commitment).unwrap();
+        assert_eq!(ledger.get_block_hash(1).unwrap(), older_ledger_proof.block_hash());
+        assert_eq!(ledger.latest_ledger_root(), older_ledger_proof.ledger_root());
+        light_client.verify_ledger_proof(&older_ledger_proof).unwrap();
+
+        // Ensure a light client behind the ledger proof does not verify it.
+        let reverted_headers = light_client.revert_to(1).unwrap();
+        assert_eq!(1, reverted_headers.len());
+        assert_eq!(ledger.get_block_hash(1).unwrap(), light_client.latest_block_hash());
+        assert!(light_client.verify_ledger_proof(&ledger_proof).is_err());
+
+        // Ensure the light client accepts the reverted header again.
+        light_client.add_next_header(&reverted_headers[0]).unwrap();
+        light_client.verify_ledger_proof(&ledger_proof).unwrap();
+    }
+}
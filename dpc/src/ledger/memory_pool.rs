@@ -15,14 +15,46 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::prelude::*;
+use snarkvm_utilities::ToBytes;
 
 use anyhow::{anyhow, Result};
-use std::collections::{HashMap, HashSet};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+use time::OffsetDateTime;
+
+/// An unconfirmed transaction, along with the metadata used to prioritize it in the memory pool.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"))]
+struct MemoryPoolEntry<N: Network> {
+    /// The unconfirmed transaction.
+    transaction: Transaction<N>,
+    /// The fee paid by the transaction.
+    fee: u64,
+    /// The serialized size of the transaction in bytes.
+    size_in_bytes: usize,
+    /// The UNIX timestamp at which the transaction entered the memory pool.
+    timestamp: i64,
+}
+
+impl<N: Network> MemoryPoolEntry<N> {
+    ///
+    /// Returns the ordering of `self` relative to `other` in descending order of priority.
+    /// Transactions are prioritized by fee per byte, and then by age, with older transactions first.
+    ///
+    fn cmp_priority(&self, other: &Self) -> Ordering {
+        // Compare the fee rates without division, as `fee_a / size_a` against `fee_b / size_b`.
+        let fee_rate = self.fee as u128 * other.size_in_bytes as u128;
+        let other_fee_rate = other.fee as u128 * self.size_in_bytes as u128;
+        other_fee_rate.cmp(&fee_rate).then(self.timestamp.cmp(&other.timestamp))
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct MemoryPool<N: Network> {
     /// The pool of unconfirmed transactions.
-    transactions: HashMap<N::TransactionID, Transaction<N>>,
+    transactions: HashMap<N::TransactionID, MemoryPoolEntry<N>>,
     /// The list of unconfirmed serial numbers.
     serial_numbers: HashSet<N::SerialNumber>,
     /// The list of unconfirmed commitments.
@@ -30,16 +62,44 @@ pub struct MemoryPool<N: Network> {
     /// The set of open requests.
     #[allow(dead_code)]
     requests: HashSet<Request<N>>,
+    /// The total serialized size of the unconfirmed transactions in bytes.
+    size_in_bytes: usize,
+    /// The maximum total serialized size of the unconfirmed transactions in bytes.
+    capacity_in_bytes: usize,
+    /// The number of seconds an unconfirmed transaction may remain in the memory pool.
+    transaction_lifetime_in_secs: i64,
+    /// The maximum number of blocks an unconfirmed transaction's ledger root may trail the canon chain by.
+    ledger_root_depth: u32,
 }
 
 impl<N: Network> MemoryPool<N> {
+    /// The default maximum total serialized size of the unconfirmed transactions in bytes.
+    pub const DEFAULT_CAPACITY_IN_BYTES: usize = 64 * 1024 * 1024;
+    /// The default maximum number of blocks an unconfirmed transaction's ledger root may trail the canon chain by.
+    pub const DEFAULT_LEDGER_ROOT_DEPTH: u32 = 1024;
+    /// The default number of seconds an unconfirmed transaction may remain in the memory pool.
+    pub const DEFAULT_TRANSACTION_LIFETIME_IN_SECS: i64 = 6 * 60 * 60;
+
     /// Initializes a new instance of a memory pool.
     pub fn new() -> Self {
+        Self::with_capacity(
+            Self::DEFAULT_CAPACITY_IN_BYTES,
+            Self::DEFAULT_TRANSACTION_LIFETIME_IN_SECS,
+            Self::DEFAULT_LEDGER_ROOT_DEPTH,
+        )
+    }
+
+    /// Initializes a new instance of a memory pool, with the given limits.
+    pub fn with_capacity(capacity_in_bytes: usize, transaction_lifetime_in_secs: i64, ledger_root_depth: u32) -> Self {
         Self {
             transactions: Default::default(),
             serial_numbers: Default::default(),
             commitments: Default::default(),
             requests: Default::default(),
+            size_in_bytes: 0,
+            capacity_in_bytes,
+            transaction_lifetime_in_secs,
+            ledger_root_depth,
         }
     }
 
@@ -48,12 +108,51 @@ impl<N: Network> MemoryPool<N> {
         self.transactions.contains_key(&transaction.transaction_id())
     }
 
-    /// Returns the transactions in the memory pool.
+    /// Returns the number of transactions in the memory pool.
+    pub fn num_transactions(&self) -> usize {
+        self.transactions.len()
+    }
+
+    /// Returns the total serialized size of the transactions in the memory pool in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.size_in_bytes
+    }
+
+    /// Returns the maximum total serialized size of the transactions in the memory pool in bytes.
+    pub fn capacity_in_bytes(&self) -> usize {
+        self.capacity_in_bytes
+    }
+
+    /// Returns the transactions in the memory pool, in descending order of priority.
     pub fn transactions(&self) -> Vec<Transaction<N>> {
-        self.transactions.values().cloned().collect()
+        self.to_prioritized_entries().into_iter().map(|entry| entry.transaction.clone()).collect()
+    }
+
+    ///
+    /// Returns at most `max_transactions` of the highest priority transactions in the memory pool,
+    /// whose total serialized size does not exceed `max_bytes`.
+    ///
+    pub fn select_for_block(&self, max_bytes: usize, max_transactions: usize) -> Vec<Transaction<N>> {
+        let mut remaining_bytes = max_bytes;
+        let mut transactions = Vec::new();
+        for entry in self.to_prioritized_entries() {
+            if transactions.len() >= max_transactions {
+                break;
+            }
+            // Skip transactions that do not fit, as a smaller transaction may still fit.
+            if entry.size_in_bytes <= remaining_bytes {
+                remaining_bytes -= entry.size_in_bytes;
+                transactions.push(entry.transaction.clone());
+            }
+        }
+        transactions
     }
 
+    ///
     /// Adds the given unconfirmed transaction to the memory pool.
+    /// If the memory pool is full, lower priority transactions are evicted to make room,
+    /// and if there are not enough of them, the given transaction is rejected.
+    ///
     pub fn add_transaction(&mut self, transaction: &Transaction<N>) -> Result<()> {
         // Ensure the unconfirmed transaction itself is valid.
         if !transaction.is_valid() {
@@ -85,17 +184,52 @@ impl<N: Network> MemoryPool<N> {
             }
         }
 
+        // Ensure the memory pool does not already contain a deployment of a given program.
+        for deployment in transaction.deployments() {
+            if self
+                .transactions
+                .values()
+                .flat_map(|entry| entry.transaction.deployments())
+                .any(|candidate| candidate.program_id() == deployment.program_id())
+            {
+                return Err(anyhow!("Program deployment already exists in memory pool"));
+            }
+        }
+
+        // Ensure the transaction fits in the memory pool.
+        let entry = MemoryPoolEntry {
+            transaction: transaction.clone(),
+            fee: transaction.value_balance().0 as u64,
+            size_in_bytes: transaction.to_bytes_le()?.len(),
+            timestamp: OffsetDateTime::now_utc().unix_timestamp(),
+        };
+        if entry.size_in_bytes > self.capacity_in_bytes {
+            return Err(anyhow!("Transaction exceeds the memory pool capacity of {} bytes", self.capacity_in_bytes));
+        }
+
         // Add the transaction to the memory pool. This code section executes atomically.
         {
             let mut memory_pool = self.clone();
 
-            memory_pool.transactions.insert(transaction_id, transaction.clone());
+            // Evict the lowest priority transactions, until there is room for the given transaction.
+            let mut evicted = self.to_prioritized_entries().into_iter().rev();
+            while memory_pool.size_in_bytes + entry.size_in_bytes > memory_pool.capacity_in_bytes {
+                match evicted.next() {
+                    Some(lowest) if lowest.cmp_priority(&entry) == Ordering::Greater => {
+                        memory_pool.remove_transaction(&lowest.transaction)
+                    }
+                    _ => return Err(anyhow!("Memory pool is full of higher priority transactions")),
+                }
+            }
+
             for serial_number in transaction.serial_numbers() {
                 memory_pool.serial_numbers.insert(*serial_number);
             }
             for commitment in transaction.commitments() {
                 memory_pool.commitments.insert(*commitment);
             }
+            memory_pool.size_in_bytes += entry.size_in_bytes;
+            memory_pool.transactions.insert(transaction_id, entry);
 
             *self = memory_pool;
         }
@@ -105,19 +239,7 @@ impl<N: Network> MemoryPool<N> {
 
     /// Clear a transaction (and associated state) from the memory pool.
     pub fn remove_transaction(&mut self, transaction: &Transaction<N>) {
-        // This code section executes atomically.
-
-        let mut memory_pool = self.clone();
-
-        memory_pool.transactions.remove(&transaction.transaction_id());
-        for serial_number in transaction.serial_numbers() {
-            memory_pool.serial_numbers.remove(serial_number);
-        }
-        for commitment in transaction.commitments() {
-            memory_pool.commitments.remove(commitment);
-        }
-
-        *self = memory_pool;
+        self.remove_transactions(std::slice::from_ref(transaction));
     }
 
     /// Clear a list of transactions (and associated state) from the memory pool.
@@ -127,23 +249,75 @@ impl<N: Network> MemoryPool<N> {
         let mut memory_pool = self.clone();
 
         for transaction in transactions {
-            memory_pool.transactions.remove(&transaction.transaction_id());
-            for serial_number in transaction.serial_numbers() {
-                memory_pool.serial_numbers.remove(serial_number);
-            }
-            for commitment in transaction.commitments() {
-                memory_pool.commitments.remove(commitment);
+            if let Some(entry) = memory_pool.transactions.remove(&transaction.transaction_id()) {
+                memory_pool.size_in_bytes -= entry.size_in_bytes;
+                for serial_number in transaction.serial_numbers() {
+                    memory_pool.serial_numbers.remove(serial_number);
+                }
+                for commitment in transaction.commitments() {
+                    memory_pool.commitments.remove(commitment);
+                }
             }
         }
 
         *self = memory_pool;
     }
 
+    /// Clears the transactions that entered the memory pool more than the transaction lifetime before the given timestamp.
+    pub fn remove_expired_transactions(&mut self, timestamp: i64) {
+        let expired: Vec<_> = self
+            .transactions
+            .values()
+            .filter(|entry| entry.timestamp.saturating_add(self.transaction_lifetime_in_secs) < timestamp)
+            .map(|entry| entry.transaction.clone())
+            .collect();
+        self.remove_transactions(&expired);
+    }
+
+    ///
+    /// Clears the transactions that are no longer valid on the given canon chain. This includes
+    /// transactions that were included in a block, spend a serial number or reuse a commitment from the
+    /// canon chain, reference a ledger root that is unknown or exceeds the maximum ledger root depth,
+    /// or execute a program that is no longer deployed, or deploy a program that is already deployed.
+    /// Expired transactions are also cleared.
+    ///
+    pub fn revalidate(&mut self, canon: &Blocks<N>) {
+        self.remove_expired_transactions(OffsetDateTime::now_utc().unix_timestamp());
+
+        let invalid: Vec<_> = self
+            .transactions
+            .values()
+            .map(|entry| &entry.transaction)
+            .filter(|transaction| {
+                let is_stale_ledger_root = match canon.get_ledger_root_depth(&transaction.ledger_root()) {
+                    Some(depth) => depth > self.ledger_root_depth,
+                    None => true,
+                };
+
+                is_stale_ledger_root
+                    || canon.contains_transaction(transaction)
+                    || transaction.serial_numbers().any(|serial_number| canon.contains_serial_number(serial_number))
+                    || transaction.commitments().any(|commitment| canon.contains_commitment(commitment))
+                    || canon.verify_programs(transaction).is_err()
+            })
+            .cloned()
+            .collect();
+        self.remove_transactions(&invalid);
+    }
+
     /// Clears all transactions (and associated state) from the memory pool.
     pub fn clear_all_transactions(&mut self) {
         self.transactions = Default::default();
         self.serial_numbers = Default::default();
         self.commitments = Default::default();
+        self.size_in_bytes = 0;
+    }
+
+    /// Returns the memory pool entries, in descending order of priority.
+    fn to_prioritized_entries(&self) -> Vec<&MemoryPoolEntry<N>> {
+        let mut entries: Vec<_> = self.transactions.values().collect();
+        entries.sort_by(|a, b| a.cmp_priority(b));
+        entries
     }
 }
 
@@ -152,3 +326,129 @@ impl<N: Network> Default for MemoryPool<N> {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    use rand::thread_rng;
+    use std::sync::atomic::AtomicBool;
+
+    /// Mines a coinbase record for the given account, and returns a transaction that spends it with the given fee.
+    fn sample_transaction(
+        ledger: &mut Ledger<Testnet2>,
+        account: &Account<Testnet2>,
+        fee: i64,
+    ) -> Transaction<Testnet2> {
+        let rng = &mut thread_rng();
+
+        let record = ledger.mine_next_block(account.address(), true, &AtomicBool::new(false), rng).unwrap();
+        let ledger_proofs = vec![ledger.to_ledger_proof(record.commitment()).unwrap(), Default::default()];
+
+        let request = Request::new_transfer(
+            account.private_key(),
+            vec![record],
+            ledger_proofs,
+            account.address(),
+            AleoAmount::from_i64(1),
+            AleoAmount::from_i64(fee),
+            true,
+            rng,
+        )
+        .unwrap();
+        Transaction::new(ledger.to_ledger_tree().clone(), &request, rng).unwrap()
+    }
+
+    #[test]
+    fn test_select_for_block() {
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let account = Account::<Testnet2>::new(&mut thread_rng());
+
+        let low_fee_transaction = sample_transaction(&mut ledger, &account, 1);
+        let high_fee_transaction = sample_transaction(&mut ledger, &account, 100);
+
+        let mut memory_pool = MemoryPool::<Testnet2>::new();
+        memory_pool.add_transaction(&low_fee_transaction).unwrap();
+        memory_pool.add_transaction(&high_fee_transaction).unwrap();
+        assert_eq!(2, memory_pool.num_transactions());
+
+        // Ensure the transactions are returned in descending order of fee per byte.
+        assert_eq!(vec![high_fee_transaction.clone(), low_fee_transaction.clone()], memory_pool.transactions());
+
+        // Ensure the selected transactions fit within the given size budget.
+        let size_in_bytes = memory_pool.size_in_bytes();
+        assert!(memory_pool.select_for_block(0, 2).is_empty());
+        assert_eq!(vec![high_fee_transaction.clone()], memory_pool.select_for_block(size_in_bytes - 1, 2));
+        assert_eq!(
+            vec![high_fee_transaction.clone(), low_fee_transaction.clone()],
+            memory_pool.select_for_block(size_in_bytes, 2)
+        );
+
+        // Ensure the selected transactions do not exceed the given count, when the memory pool holds more.
+        assert!(memory_pool.select_for_block(size_in_bytes, 0).is_empty());
+        assert_eq!(vec![high_fee_transaction], memory_pool.select_for_block(size_in_bytes, 1));
+    }
+
+    #[test]
+    fn test_eviction_and_expiration() {
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let account = Account::<Testnet2>::new(&mut thread_rng());
+
+        let low_fee_transaction = sample_transaction(&mut ledger, &account, 1);
+        let high_fee_transaction = sample_transaction(&mut ledger, &account, 100);
+        let capacity_in_bytes = high_fee_transaction.to_bytes_le().unwrap().len();
+
+        let mut memory_pool = MemoryPool::<Testnet2>::with_capacity(capacity_in_bytes, 60, 1);
+        memory_pool.add_transaction(&low_fee_transaction).unwrap();
+
+        // Ensure a higher priority transaction evicts a lower priority transaction.
+        memory_pool.add_transaction(&high_fee_transaction).unwrap();
+        assert_eq!(vec![high_fee_transaction.clone()], memory_pool.transactions());
+
+        // Ensure a lower priority transaction is rejected from a full memory pool.
+        assert!(memory_pool.add_transaction(&low_fee_transaction).is_err());
+        assert_eq!(capacity_in_bytes, memory_pool.size_in_bytes());
+
+        // Ensure transactions are retained within their lifetime, and removed after it.
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+        memory_pool.remove_expired_transactions(timestamp);
+        assert_eq!(1, memory_pool.num_transactions());
+        memory_pool.remove_expired_transactions(timestamp + 120);
+        assert_eq!(0, memory_pool.num_transactions());
+        assert_eq!(0, memory_pool.size_in_bytes());
+    }
+
+    #[test]
+    fn test_revalidate() {
+        let rng = &mut thread_rng();
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let account = Account::<Testnet2>::new(rng);
+
+        // Ensure a transaction is removed from the memory pool once it is included in a block.
+        let transaction = sample_transaction(&mut ledger, &account, 1);
+        ledger.add_unconfirmed_transaction(&transaction).unwrap();
+        assert_eq!(1, ledger.memory_pool().num_transactions());
+
+        ledger.mine_next_block(account.address(), true, &AtomicBool::new(false), rng).unwrap();
+        assert!(ledger.contains_transaction(&transaction));
+        assert_eq!(0, ledger.memory_pool().num_transactions());
+
+        // Ensure a transaction is removed from the memory pool once its ledger root is too old.
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let transaction = sample_transaction(&mut ledger, &account, 1);
+        let mut memory_pool = MemoryPool::<Testnet2>::with_capacity(usize::MAX, i64::MAX, 1);
+        memory_pool.add_transaction(&transaction).unwrap();
+
+        let mut canon = Blocks::<Testnet2>::new().unwrap();
+        canon.add_next(&ledger.latest_block().unwrap()).unwrap();
+        for expected_num_transactions in [1, 1, 0] {
+            memory_pool.revalidate(&canon);
+            assert_eq!(expected_num_transactions, memory_pool.num_transactions());
+
+            ledger.mine_next_block(account.address(), true, &AtomicBool::new(false), rng).unwrap();
+            canon.add_next(&ledger.latest_block().unwrap()).unwrap();
+        }
+    }
+}
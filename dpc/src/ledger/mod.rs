@@ -26,5 +26,17 @@ pub use ledger_proof::*;
 pub mod ledger_tree;
 pub use ledger_tree::*;
 
+pub mod light_client;
+pub use light_client::*;
+
 pub mod memory_pool;
 pub use memory_pool::*;
+
+pub mod program_registry;
+pub use program_registry::*;
+
+pub mod snapshot;
+pub use snapshot::*;
+
+pub mod storage;
+pub use storage::{DataMap, FileStorage, MemoryStorage, Storage, StorageBatch};
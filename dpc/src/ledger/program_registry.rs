@@ -0,0 +1,191 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+use crate::prelude::*;
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+
+///
+/// The registry of programs deployed on the ledger, which binds each program ID to its published
+/// function verifying keys. The noop program is deployed implicitly, as part of the network.
+///
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"))]
+pub struct ProgramRegistry<N: Network> {
+    /// The deployment of the noop program.
+    noop: Deployment<N>,
+    /// The map of program IDs to (block height, deployment).
+    deployments: HashMap<N::ProgramID, (u32, Deployment<N>)>,
+}
+
+impl<N: Network> ProgramRegistry<N> {
+    /// Initializes a new instance of the program registry, with the noop program.
+    pub fn new() -> Result<Self> {
+        Ok(Self { noop: Deployment::new_noop()?, deployments: Default::default() })
+    }
+
+    /// Returns the number of deployed programs, excluding the noop program.
+    pub fn len(&self) -> usize {
+        self.deployments.len()
+    }
+
+    /// Returns `true` if no programs were deployed, besides the noop program.
+    pub fn is_empty(&self) -> bool {
+        self.deployments.is_empty()
+    }
+
+    /// Returns `true` if the given program ID is deployed.
+    pub fn contains_program(&self, program_id: &N::ProgramID) -> bool {
+        self.get_deployment(program_id).is_ok()
+    }
+
+    /// Returns the deployment of the given program ID.
+    pub fn get_deployment(&self, program_id: &N::ProgramID) -> Result<&Deployment<N>> {
+        match self.deployments.get(program_id) {
+            Some((_, deployment)) => Ok(deployment),
+            None if *program_id == self.noop.program_id() => Ok(&self.noop),
+            None => Err(anyhow!("Program {} is not deployed", program_id)),
+        }
+    }
+
+    /// Returns the block height of the deployment of the given program ID, or `None` for the noop program.
+    pub fn get_deployment_height(&self, program_id: &N::ProgramID) -> Option<u32> {
+        self.deployments.get(program_id).map(|(height, _)| *height)
+    }
+
+    /// Returns the deployed programs as (block height, deployment), excluding the noop program.
+    pub fn deployments(&self) -> impl Iterator<Item = (u32, &Deployment<N>)> + '_ {
+        self.deployments.values().map(|(height, deployment)| (*height, deployment))
+    }
+
+    ///
+    /// Returns `Ok(())` if the given execution references a deployed program,
+    /// and its verifying key and program path match a function of the deployed program.
+    ///
+    pub fn verify_execution(&self, execution: &Execution<N>) -> Result<()> {
+        let deployment = self.get_deployment(&execution.program_id)?;
+        match deployment.is_valid_function(&execution.verifying_key, &execution.program_path) {
+            true => Ok(()),
+            false => Err(anyhow!("Execution does not match a deployed function of program {}", execution.program_id)),
+        }
+    }
+
+    ///
+    /// Returns `Ok(())` if every transition in the given transaction executes a deployed program,
+    /// each deployment is bound to its transition, and the programs it deploys are not deployed yet.
+    ///
+    pub fn verify_transaction(&self, transaction: &Transaction<N>) -> Result<()> {
+        let mut deployments = Vec::new();
+        for transition in transaction.transitions() {
+            self.verify_execution(transition.execution())?;
+            deployments.extend(transition.to_deployment()?);
+        }
+
+        let mut program_ids = Vec::new();
+        for deployment in deployments {
+            let program_id = deployment.program_id();
+            if self.contains_program(&program_id) || program_ids.contains(&program_id) {
+                return Err(anyhow!("Program {} is already deployed", program_id));
+            }
+            program_ids.push(program_id);
+        }
+
+        Ok(())
+    }
+
+    /// Adds the given deployment at the given block height.
+    pub fn add(&mut self, height: u32, deployment: Deployment<N>) -> Result<()> {
+        let program_id = deployment.program_id();
+        if self.contains_program(&program_id) {
+            return Err(anyhow!("Duplicate deployment of program {} in block {}", program_id, height));
+        }
+        self.deployments.insert(program_id, (height, deployment));
+        Ok(())
+    }
+
+    /// Removes the deployment of the given program ID, if it exists.
+    pub fn remove(&mut self, program_id: &N::ProgramID) -> Option<Deployment<N>> {
+        self.deployments.remove(program_id).map(|(_, deployment)| deployment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_noop_program_is_deployed() {
+        let registry = ProgramRegistry::<Testnet2>::new().unwrap();
+        assert!(registry.is_empty());
+        assert!(registry.contains_program(Testnet2::noop_program_id()));
+        assert!(!registry.contains_program(&Default::default()));
+
+        // Ensure the executions in the genesis block are valid.
+        let transaction = Testnet2::genesis_block().to_coinbase_transaction().unwrap();
+        registry.verify_transaction(&transaction).unwrap();
+    }
+
+    #[test]
+    fn test_execution_with_undeployed_program() {
+        let registry = ProgramRegistry::<Testnet2>::new().unwrap();
+
+        let transaction = Testnet2::genesis_block().to_coinbase_transaction().unwrap();
+        let mut execution = transaction.transitions()[0].execution().clone();
+        registry.verify_execution(&execution).unwrap();
+
+        execution.program_id = Default::default();
+        assert!(registry.verify_execution(&execution).is_err());
+    }
+
+    #[test]
+    fn test_unbound_deployment() {
+        let registry = ProgramRegistry::<Testnet2>::new().unwrap();
+
+        // Attach a deployment to the coinbase transition of the genesis block.
+        let transaction = Testnet2::genesis_block().to_coinbase_transaction().unwrap();
+        let transition = &transaction.transitions()[0];
+        let mut events: Vec<_> = transition.events().cloned().collect();
+        let caller = Account::<Testnet2>::new(&mut thread_rng()).address();
+        events.push(Event::Operation(Operation::Deploy(caller, Deployment::new_noop().unwrap())));
+        let transition = Transition::from(
+            transition.transition_id(),
+            transition.serial_numbers().cloned().collect(),
+            transition.ciphertexts().cloned().collect(),
+            *transition.value_balance(),
+            events,
+            transition.execution().clone(),
+        )
+        .unwrap();
+        let transaction =
+            Transaction::from_unchecked(transaction.inner_circuit_id(), transaction.ledger_root(), vec![transition])
+                .unwrap();
+
+        // Ensure the deployment is not bound, as the coinbase record does not commit to the program ID.
+        assert!(transaction.transitions()[0].to_deployment().is_err());
+        assert!(!transaction.is_deployment());
+        assert!(registry.verify_transaction(&transaction).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_deployment() {
+        let mut registry = ProgramRegistry::<Testnet2>::new().unwrap();
+        assert!(registry.add(1, Deployment::new_noop().unwrap()).is_err());
+        assert!(registry.is_empty());
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::prelude::*;
+use snarkvm_utilities::{FromBytes, ToBytes};
+
+use anyhow::{anyhow, Result};
+use std::io::{Read, Result as IoResult, Write};
+
+///
+/// A snapshot of the canon chain state at a committed block, which allows a ledger to be initialized
+/// from a trusted checkpoint, without re-verifying the blocks before it.
+///
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"), PartialEq(bound = "N: Network"))]
+pub struct LedgerSnapshot<N: Network> {
+    /// The block hashes of the canon chain, in ascending order of block height.
+    pub(super) block_hashes: Vec<N::BlockHash>,
+    /// The block headers of the canon chain, in ascending order of block height.
+    pub(super) headers: Vec<BlockHeader<N>>,
+    /// The transactions of the committed block.
+    pub(super) transactions: Transactions<N>,
+    /// The ledger root after the committed block.
+    pub(super) ledger_root: N::LedgerRoot,
+    /// The serial numbers of the canon chain, with their (block height, transaction index, transition index),
+    /// in the order of their transitions.
+    pub(super) serial_numbers: Vec<(N::SerialNumber, (u32, u16, u8))>,
+    /// The commitments of the canon chain, with their (block height, transaction index, transition index),
+    /// in the order of their transitions.
+    pub(super) commitments: Vec<(N::Commitment, (u32, u16, u8))>,
+    /// The program deployments of the canon chain, with their block height.
+    pub(super) deployments: Vec<(u32, Deployment<N>)>,
+}
+
+impl<N: Network> LedgerSnapshot<N> {
+    /// Returns the block height of the committed block.
+    pub fn block_height(&self) -> u32 {
+        self.block_hashes.len().saturating_sub(1) as u32
+    }
+
+    /// Returns the block hash of the committed block.
+    pub fn block_hash(&self) -> Result<N::BlockHash> {
+        match self.block_hashes.last() {
+            Some(block_hash) => Ok(*block_hash),
+            None => Err(anyhow!("The ledger snapshot is empty")),
+        }
+    }
+
+    /// Returns the ledger root after the committed block.
+    pub fn ledger_root(&self) -> N::LedgerRoot {
+        self.ledger_root
+    }
+
+    /// Returns the number of serial numbers in the snapshot.
+    pub fn num_serial_numbers(&self) -> usize {
+        self.serial_numbers.len()
+    }
+
+    /// Returns the number of commitments in the snapshot.
+    pub fn num_commitments(&self) -> usize {
+        self.commitments.len()
+    }
+
+    /// Returns the number of program deployments in the snapshot.
+    pub fn num_deployments(&self) -> usize {
+        self.deployments.len()
+    }
+}
+
+impl<N: Network> FromBytes for LedgerSnapshot<N> {
+    #[inline]
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // The lengths are not trusted, so the vectors are not preallocated from them.
+        let num_blocks: u32 = FromBytes::read_le(&mut reader)?;
+        let mut block_hashes = Vec::new();
+        for _ in 0..num_blocks {
+            block_hashes.push(FromBytes::read_le(&mut reader)?);
+        }
+
+        // The block headers are verified against the trusted block hash, when the snapshot is imported.
+        let mut headers = Vec::new();
+        for _ in 0..num_blocks {
+            headers.push(BlockHeader::read_le_unchecked(&mut reader)?);
+        }
+
+        let transactions = Transactions::read_le_unchecked(&mut reader)?;
+        let ledger_root = FromBytes::read_le(&mut reader)?;
+
+        let num_serial_numbers: u64 = FromBytes::read_le(&mut reader)?;
+        let mut serial_numbers = Vec::new();
+        for _ in 0..num_serial_numbers {
+            let serial_number = FromBytes::read_le(&mut reader)?;
+            let location =
+                (FromBytes::read_le(&mut reader)?, FromBytes::read_le(&mut reader)?, FromBytes::read_le(&mut reader)?);
+            serial_numbers.push((serial_number, location));
+        }
+
+        let num_commitments: u64 = FromBytes::read_le(&mut reader)?;
+        let mut commitments = Vec::new();
+        for _ in 0..num_commitments {
+            let commitment = FromBytes::read_le(&mut reader)?;
+            let location =
+                (FromBytes::read_le(&mut reader)?, FromBytes::read_le(&mut reader)?, FromBytes::read_le(&mut reader)?);
+            commitments.push((commitment, location));
+        }
+
+        let num_deployments: u32 = FromBytes::read_le(&mut reader)?;
+        let mut deployments = Vec::new();
+        for _ in 0..num_deployments {
+            let height = FromBytes::read_le(&mut reader)?;
+            let deployment = FromBytes::read_le(&mut reader)?;
+            deployments.push((height, deployment));
+        }
+
+        Ok(Self { block_hashes, headers, transactions, ledger_root, serial_numbers, commitments, deployments })
+    }
+}
+
+impl<N: Network> ToBytes for LedgerSnapshot<N> {
+    #[inline]
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        (self.block_hashes.len() as u32).write_le(&mut writer)?;
+        for block_hash in &self.block_hashes {
+            block_hash.write_le(&mut writer)?;
+        }
+        for header in &self.headers {
+            header.write_le(&mut writer)?;
+        }
+
+        self.transactions.write_le(&mut writer)?;
+        self.ledger_root.write_le(&mut writer)?;
+
+        (self.serial_numbers.len() as u64).write_le(&mut writer)?;
+        for (serial_number, (height, transaction_index, transition_index)) in &self.serial_numbers {
+            serial_number.write_le(&mut writer)?;
+            height.write_le(&mut writer)?;
+            transaction_index.write_le(&mut writer)?;
+            transition_index.write_le(&mut writer)?;
+        }
+
+        (self.commitments.len() as u64).write_le(&mut writer)?;
+        for (commitment, (height, transaction_index, transition_index)) in &self.commitments {
+            commitment.write_le(&mut writer)?;
+            height.write_le(&mut writer)?;
+            transaction_index.write_le(&mut writer)?;
+            transition_index.write_le(&mut writer)?;
+        }
+
+        (self.deployments.len() as u32).write_le(&mut writer)?;
+        for (height, deployment) in &self.deployments {
+            height.write_le(&mut writer)?;
+            deployment.write_le(&mut writer)?;
+        }
+        Ok(())
+    }
+}
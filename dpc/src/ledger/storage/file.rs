@@ -0,0 +1,394 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use anyhow::{anyhow, Result};
+use blake2::{Blake2s256, Digest};
+use std::{
+    convert::TryFrom,
+    fs::{self, File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// The name of the append-only log file within the storage directory.
+const LOG_FILE_NAME: &str = "ledger.log";
+/// The name of the temporary log file used during compaction.
+const COMPACTION_FILE_NAME: &str = "ledger.log.compact";
+/// The size of a log record header, consisting of the payload length (8 bytes) and checksum (32 bytes).
+const RECORD_HEADER_SIZE: usize = 8 + 32;
+/// The default minimum size of the log file, in bytes, before it is compacted automatically.
+pub const DEFAULT_COMPACTION_THRESHOLD_IN_BYTES: u64 = 64 * 1024 * 1024;
+
+///
+/// A file-backed storage backend for the ledger.
+///
+/// Each batch is appended to a log file as a single checksummed record and synced to disk before
+/// it is applied in memory. On open, the log is replayed, and any trailing record that is incomplete
+/// or fails its checksum (i.e. from an interrupted write) is discarded, so batches are crash-safe.
+///
+/// The log is compacted automatically, on open and before a batch is appended, once it reaches the
+/// compaction threshold and twice its size after the last compaction, so compaction is amortized over
+/// the appended batches. The whole state is held in memory, and compacting it temporarily requires
+/// about twice as much memory again, for the serialized copy that is written to the compacted log.
+///
+#[derive(Debug)]
+pub struct FileStorage {
+    /// The directory containing the log file.
+    directory: PathBuf,
+    /// The append-only log file.
+    log: Mutex<Log>,
+    /// The in-memory view of the log.
+    cache: MemoryStorage,
+    /// The minimum size of the log file, in bytes, before it is compacted automatically.
+    compaction_threshold: u64,
+}
+
+/// The append-only log file, and its size.
+#[derive(Debug)]
+struct Log {
+    /// The log file.
+    file: File,
+    /// The size of the log file, in bytes.
+    size: u64,
+    /// The size of the log file after it was last compacted, in bytes, or zero if it is not compacted.
+    compacted_size: u64,
+}
+
+impl FileStorage {
+    /// Opens the file storage in the given directory, creating it if it does not exist.
+    pub fn open<P: AsRef<Path>>(directory: P) -> Result<Self> {
+        Self::open_with_compaction_threshold(directory, DEFAULT_COMPACTION_THRESHOLD_IN_BYTES)
+    }
+
+    ///
+    /// Opens the file storage in the given directory, creating it if it does not exist,
+    /// and compacts its log automatically once it reaches the given size in bytes.
+    ///
+    pub fn open_with_compaction_threshold<P: AsRef<Path>>(directory: P, compaction_threshold: u64) -> Result<Self> {
+        let directory = directory.as_ref().to_path_buf();
+        fs::create_dir_all(&directory)?;
+
+        let mut log = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(directory.join(LOG_FILE_NAME))?;
+
+        // Replay the log into memory.
+        let cache = MemoryStorage::new();
+        let mut bytes = Vec::new();
+        log.read_to_end(&mut bytes)?;
+
+        let mut offset = 0;
+        let mut num_records = 0;
+        while let Some((batch, length)) = Self::decode_record(&bytes[offset..]) {
+            cache.write_batch(batch)?;
+            offset += length;
+            num_records += 1;
+        }
+
+        // Discard the trailing bytes of an interrupted write, if they exist.
+        if offset != bytes.len() {
+            log.set_len(offset as u64)?;
+            log.sync_all()?;
+        }
+        let size = log.seek(SeekFrom::End(0))?;
+
+        // A log of at most one record is already compacted.
+        let compacted_size = if num_records <= 1 { size } else { 0 };
+        let log = Log { file: log, size, compacted_size };
+
+        let storage = Self { directory, log: Mutex::new(log), cache, compaction_threshold };
+        storage.compact_if_needed()?;
+        Ok(storage)
+    }
+
+    /// Returns the directory of the file storage.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns the size of the log file, in bytes.
+    pub fn log_size(&self) -> Result<u64> {
+        Ok(self.log.lock().map_err(|_| anyhow!("File storage lock is poisoned"))?.size)
+    }
+
+    ///
+    /// Rewrites the log as a single record of the current state, discarding removed and overwritten entries.
+    /// The compacted log is written to a temporary file, and atomically renamed over the existing log.
+    ///
+    pub fn compact(&self) -> Result<()> {
+        let mut log = self.log.lock().map_err(|_| anyhow!("File storage lock is poisoned"))?;
+        self.compact_log(&mut log)
+    }
+
+    /// Compacts the log, if it reaches the compaction threshold and twice its size after the last compaction.
+    fn compact_if_needed(&self) -> Result<()> {
+        let mut log = self.log.lock().map_err(|_| anyhow!("File storage lock is poisoned"))?;
+        match self.is_compaction_needed(&log) {
+            true => self.compact_log(&mut log),
+            false => Ok(()),
+        }
+    }
+
+    /// Returns `true` if the log reaches the compaction threshold and twice its size after the last compaction.
+    fn is_compaction_needed(&self, log: &Log) -> bool {
+        log.size >= self.compaction_threshold && log.size >= log.compacted_size.saturating_mul(2)
+    }
+
+    /// Rewrites the given log as a single record of the current state.
+    fn compact_log(&self, log: &mut Log) -> Result<()> {
+        let compaction_path = self.directory.join(COMPACTION_FILE_NAME);
+        let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&compaction_path)?;
+        file.write_all(&Self::encode_record(&self.cache.to_batch()?)?)?;
+        file.sync_all()?;
+
+        // The compacted file remains open for appending, once it replaces the existing log.
+        fs::rename(&compaction_path, self.directory.join(LOG_FILE_NAME))?;
+        Self::sync_directory(&self.directory)?;
+
+        let size = file.seek(SeekFrom::End(0))?;
+        *log = Log { file, size, compacted_size: size };
+        Ok(())
+    }
+
+    /// Returns the log record for the given batch, as `(payload length || checksum || payload)`.
+    fn encode_record(batch: &StorageBatch) -> Result<Vec<u8>> {
+        let payload = bincode::serialize(batch)?;
+
+        let mut record = Vec::with_capacity(RECORD_HEADER_SIZE + payload.len());
+        record.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+        record.extend_from_slice(&Blake2s256::digest(&payload));
+        record.extend_from_slice(&payload);
+        Ok(record)
+    }
+
+    /// Returns the batch and the record length of the first log record in the given bytes,
+    /// or `None` if the record is incomplete or corrupt.
+    fn decode_record(bytes: &[u8]) -> Option<(StorageBatch, usize)> {
+        if bytes.len() < RECORD_HEADER_SIZE {
+            return None;
+        }
+
+        let mut length = [0u8; 8];
+        length.copy_from_slice(&bytes[..8]);
+        let length = usize::try_from(u64::from_le_bytes(length)).ok()?;
+
+        let record_length = RECORD_HEADER_SIZE.checked_add(length)?;
+        if bytes.len() < record_length {
+            return None;
+        }
+
+        let checksum = &bytes[8..RECORD_HEADER_SIZE];
+        let payload = &bytes[RECORD_HEADER_SIZE..record_length];
+        if Blake2s256::digest(payload).as_slice() != checksum {
+            return None;
+        }
+
+        let batch = bincode::deserialize(payload).ok()?;
+        Some((batch, record_length))
+    }
+
+    /// Syncs the given directory, to persist a rename on platforms that support it.
+    #[cfg(unix)]
+    fn sync_directory(directory: &Path) -> Result<()> {
+        Ok(File::open(directory)?.sync_all()?)
+    }
+
+    /// Syncs the given directory, to persist a rename on platforms that support it.
+    #[cfg(not(unix))]
+    fn sync_directory(_directory: &Path) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Storage for FileStorage {
+    fn get(&self, map: DataMap, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        self.cache.get(map, key)
+    }
+
+    fn entries(&self, map: DataMap) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.cache.entries(map)
+    }
+
+    fn write_batch(&self, batch: StorageBatch) -> Result<()> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let record = Self::encode_record(&batch)?;
+
+        let mut log = self.log.lock().map_err(|_| anyhow!("File storage lock is poisoned"))?;
+
+        // Compact the log before appending the record, so a failed compaction does not affect the batch.
+        if self.is_compaction_needed(&log) {
+            self.compact_log(&mut log)?;
+        }
+
+        // Append the record to the log, and sync it to disk.
+        let offset = log.file.seek(SeekFrom::End(0))?;
+        if let Err(error) = log.file.write_all(&record).and_then(|_| log.file.sync_data()) {
+            // Roll back the partially-written record.
+            log.file.set_len(offset)?;
+            log.file.seek(SeekFrom::End(0))?;
+            return Err(error.into());
+        }
+        log.size = offset + record.len() as u64;
+
+        // Apply the batch in memory, only after it is durable.
+        self.cache.write_batch(batch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{thread_rng, Rng};
+
+    fn temp_directory() -> PathBuf {
+        std::env::temp_dir().join(format!("snarkvm-file-storage-{}", thread_rng().gen::<u64>()))
+    }
+
+    fn sample_batch(key: u8) -> StorageBatch {
+        let mut batch = StorageBatch::new();
+        batch.insert(DataMap::Metadata, vec![key], vec![key; 32]);
+        batch.insert(DataMap::SerialNumbers, vec![key, key], vec![key; 4]);
+        batch
+    }
+
+    #[test]
+    fn test_reopen() {
+        let directory = temp_directory();
+        {
+            let storage = FileStorage::open(&directory).unwrap();
+            storage.write_batch(sample_batch(1)).unwrap();
+            storage.write_batch(sample_batch(2)).unwrap();
+
+            let mut batch = StorageBatch::new();
+            batch.remove(DataMap::Metadata, vec![1]);
+            storage.write_batch(batch).unwrap();
+        }
+        {
+            let storage = FileStorage::open(&directory).unwrap();
+            assert_eq!(None, storage.get(DataMap::Metadata, &[1]).unwrap());
+            assert_eq!(Some(vec![2; 32]), storage.get(DataMap::Metadata, &[2]).unwrap());
+            assert_eq!(2, storage.entries(DataMap::SerialNumbers).unwrap().len());
+        }
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_interrupted_write() {
+        let directory = temp_directory();
+        {
+            let storage = FileStorage::open(&directory).unwrap();
+            storage.write_batch(sample_batch(1)).unwrap();
+        }
+
+        // Simulate a crash midway through appending a record.
+        let record = FileStorage::encode_record(&sample_batch(2)).unwrap();
+        let mut log = OpenOptions::new().append(true).open(directory.join(LOG_FILE_NAME)).unwrap();
+        log.write_all(&record[..record.len() - 1]).unwrap();
+        drop(log);
+
+        {
+            let storage = FileStorage::open(&directory).unwrap();
+            assert_eq!(Some(vec![1; 32]), storage.get(DataMap::Metadata, &[1]).unwrap());
+            assert_eq!(None, storage.get(DataMap::Metadata, &[2]).unwrap());
+
+            // Ensure the log remains writable after recovery.
+            storage.write_batch(sample_batch(3)).unwrap();
+        }
+        {
+            let storage = FileStorage::open(&directory).unwrap();
+            assert_eq!(Some(vec![3; 32]), storage.get(DataMap::Metadata, &[3]).unwrap());
+            assert_eq!(2, storage.entries(DataMap::Metadata).unwrap().len());
+        }
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_automatic_compaction() {
+        let directory = temp_directory();
+        let write_and_remove = |storage: &FileStorage, key: u8| {
+            storage.write_batch(sample_batch(key)).unwrap();
+            let mut batch = StorageBatch::new();
+            batch.remove(DataMap::SerialNumbers, vec![key, key]);
+            storage.write_batch(batch).unwrap();
+        };
+
+        // Ensure the log is not compacted below the compaction threshold.
+        {
+            let storage = FileStorage::open(&directory).unwrap();
+            for key in 0..10 {
+                write_and_remove(&storage, key);
+            }
+        }
+        let uncompacted_size = fs::metadata(directory.join(LOG_FILE_NAME)).unwrap().len();
+
+        // Ensure the log is compacted on open, once it reaches the compaction threshold.
+        let threshold = uncompacted_size / 2;
+        {
+            let storage = FileStorage::open_with_compaction_threshold(&directory, threshold).unwrap();
+            let compacted_size = storage.log_size().unwrap();
+            assert!(compacted_size < uncompacted_size);
+
+            // Ensure the log is compacted on commit, to less than half the size of the uncompacted log.
+            for key in 10..100 {
+                write_and_remove(&storage, key);
+            }
+            assert!(storage.log_size().unwrap() < 5 * uncompacted_size);
+        }
+        {
+            let storage = FileStorage::open(&directory).unwrap();
+            assert_eq!(100, storage.entries(DataMap::Metadata).unwrap().len());
+            assert!(storage.entries(DataMap::SerialNumbers).unwrap().is_empty());
+        }
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_compact() {
+        let directory = temp_directory();
+        {
+            let storage = FileStorage::open(&directory).unwrap();
+            for key in 0..10 {
+                storage.write_batch(sample_batch(key)).unwrap();
+                let mut batch = StorageBatch::new();
+                batch.remove(DataMap::SerialNumbers, vec![key, key]);
+                storage.write_batch(batch).unwrap();
+            }
+
+            let size_before = fs::metadata(directory.join(LOG_FILE_NAME)).unwrap().len();
+            storage.compact().unwrap();
+            let size_after = fs::metadata(directory.join(LOG_FILE_NAME)).unwrap().len();
+            assert!(size_after < size_before);
+
+            storage.write_batch(sample_batch(10)).unwrap();
+        }
+        {
+            let storage = FileStorage::open(&directory).unwrap();
+            assert_eq!(11, storage.entries(DataMap::Metadata).unwrap().len());
+            assert_eq!(1, storage.entries(DataMap::SerialNumbers).unwrap().len());
+        }
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
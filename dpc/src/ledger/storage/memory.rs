@@ -0,0 +1,109 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use anyhow::{anyhow, Result};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::RwLock,
+};
+
+///
+/// An in-memory storage backend for the ledger.
+///
+/// This storage does not persist across restarts, and is intended for testing.
+///
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    maps: RwLock<HashMap<DataMap, BTreeMap<Vec<u8>, Vec<u8>>>>,
+}
+
+impl MemoryStorage {
+    /// Initializes a new, empty instance of in-memory storage.
+    pub fn new() -> Self {
+        Self { maps: Default::default() }
+    }
+
+    /// Returns a batch that inserts every key-value pair currently in storage.
+    pub(crate) fn to_batch(&self) -> Result<StorageBatch> {
+        let maps = self.maps.read().map_err(|_| anyhow!("Memory storage lock is poisoned"))?;
+        let mut batch = StorageBatch::new();
+        for (map, entries) in maps.iter() {
+            for (key, value) in entries {
+                batch.insert(*map, key.clone(), value.clone());
+            }
+        }
+        Ok(batch)
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, map: DataMap, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let maps = self.maps.read().map_err(|_| anyhow!("Memory storage lock is poisoned"))?;
+        Ok(maps.get(&map).and_then(|entries| entries.get(key).cloned()))
+    }
+
+    fn entries(&self, map: DataMap) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let maps = self.maps.read().map_err(|_| anyhow!("Memory storage lock is poisoned"))?;
+        Ok(maps.get(&map).map(|entries| entries.clone().into_iter().collect()).unwrap_or_default())
+    }
+
+    fn write_batch(&self, batch: StorageBatch) -> Result<()> {
+        let mut maps = self.maps.write().map_err(|_| anyhow!("Memory storage lock is poisoned"))?;
+        for operation in batch.operations {
+            match operation {
+                StorageOperation::Insert(map, key, value) => {
+                    maps.entry(map).or_default().insert(key, value);
+                }
+                StorageOperation::Remove(map, key) => {
+                    if let Some(entries) = maps.get_mut(&map) {
+                        entries.remove(&key);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_batch() {
+        let storage = MemoryStorage::new();
+
+        let mut batch = StorageBatch::new();
+        batch.insert(DataMap::Metadata, b"a".to_vec(), b"1".to_vec());
+        batch.insert(DataMap::Metadata, b"b".to_vec(), b"2".to_vec());
+        batch.insert(DataMap::Commitments, b"a".to_vec(), b"3".to_vec());
+        storage.write_batch(batch).unwrap();
+
+        assert_eq!(Some(b"1".to_vec()), storage.get(DataMap::Metadata, b"a").unwrap());
+        assert_eq!(Some(b"3".to_vec()), storage.get(DataMap::Commitments, b"a").unwrap());
+        assert_eq!(2, storage.entries(DataMap::Metadata).unwrap().len());
+
+        let mut batch = StorageBatch::new();
+        batch.remove(DataMap::Metadata, b"a".to_vec());
+        storage.write_batch(batch).unwrap();
+
+        assert!(!storage.contains_key(DataMap::Metadata, b"a").unwrap());
+        assert!(storage.contains_key(DataMap::Metadata, b"b").unwrap());
+        assert!(storage.contains_key(DataMap::Commitments, b"a").unwrap());
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+pub mod file;
+pub use file::*;
+
+pub mod memory;
+pub use memory::*;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// The logical maps of the ledger storage.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DataMap {
+    /// The map of block heights to block headers.
+    BlockHeaders,
+    /// The map of block heights to block transactions.
+    BlockTransactions,
+    /// The map of block heights to previous block hashes.
+    PreviousBlockHashes,
+    /// The map of serial numbers to (block height, transaction index, transition index).
+    SerialNumbers,
+    /// The map of commitments to (block height, transaction index, transition index).
+    Commitments,
+    /// The map of ledger tree indices to block hashes.
+    LedgerTree,
+    /// The map of ledger metadata, such as the latest block height and block hash.
+    Metadata,
+    /// The map of deployed program IDs to (block height, deployment).
+    Programs,
+}
+
+/// A single operation in a storage batch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorageOperation {
+    /// Inserts the given key-value pair into the given map.
+    Insert(DataMap, Vec<u8>, Vec<u8>),
+    /// Removes the given key from the given map.
+    Remove(DataMap, Vec<u8>),
+}
+
+/// A list of storage operations that is committed atomically.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageBatch {
+    operations: Vec<StorageOperation>,
+}
+
+impl StorageBatch {
+    /// Initializes a new, empty storage batch.
+    pub fn new() -> Self {
+        Self { operations: Vec::new() }
+    }
+
+    /// Adds an insert operation for the given key-value pair to the batch.
+    pub fn insert(&mut self, map: DataMap, key: impl Into<Vec<u8>>, value: impl Into<Vec<u8>>) {
+        self.operations.push(StorageOperation::Insert(map, key.into(), value.into()));
+    }
+
+    /// Adds a remove operation for the given key to the batch.
+    pub fn remove(&mut self, map: DataMap, key: impl Into<Vec<u8>>) {
+        self.operations.push(StorageOperation::Remove(map, key.into()));
+    }
+
+    /// Appends the operations of the given batch to this batch.
+    pub fn extend(&mut self, batch: StorageBatch) {
+        self.operations.extend(batch.operations);
+    }
+
+    /// Returns the operations in the batch.
+    pub fn operations(&self) -> &[StorageOperation] {
+        &self.operations
+    }
+
+    /// Returns `true` if the batch contains no operations.
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+}
+
+///
+/// A key-value storage backend for the ledger.
+///
+/// Implementations must guarantee that `write_batch` is atomic, meaning a crash
+/// during a write leaves the storage with either all or none of the operations applied.
+///
+pub trait Storage: Debug + Send + Sync {
+    /// Returns the value for the given key in the given map, if it exists.
+    fn get(&self, map: DataMap, key: &[u8]) -> Result<Option<Vec<u8>>>;
+
+    /// Returns `true` if the given key exists in the given map.
+    fn contains_key(&self, map: DataMap, key: &[u8]) -> Result<bool> {
+        Ok(self.get(map, key)?.is_some())
+    }
+
+    /// Returns all key-value pairs in the given map, in ascending order of keys.
+    fn entries(&self, map: DataMap) -> Result<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// Atomically applies the given batch of operations.
+    fn write_batch(&self, batch: StorageBatch) -> Result<()>;
+}
@@ -46,6 +46,9 @@ pub use network::*;
 pub mod posw;
 pub use posw::*;
 
+pub mod prover;
+pub use prover::*;
+
 pub mod record;
 pub use record::*;
 
@@ -61,6 +64,9 @@ pub use transition::*;
 pub mod virtual_machine;
 pub use virtual_machine::*;
 
+pub mod wallet;
+pub use wallet::*;
+
 pub mod prelude {
     pub use crate::{
         account::*,
@@ -72,5 +78,6 @@ pub mod prelude {
         transaction::*,
         transition::*,
         virtual_machine::*,
+        wallet::*,
     };
 }
@@ -0,0 +1,585 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    account::ACCOUNT_ENCRYPTION_AND_SIGNATURE_INPUT,
+    posw::{PoSW, PoSWCircuit},
+    Address,
+    AleoLocator,
+    AleoObject,
+    Block,
+    Ciphertext,
+    InnerCircuit,
+    InnerPublicVariables,
+    Network,
+    PoSWScheme,
+    PrivateKey,
+    Program,
+    ProgramPublicVariables,
+    SynthesizedCircuit,
+};
+use snarkvm_algorithms::{
+    crh::{PedersenCompressedCRH, PoseidonCRH, BHPCRH},
+    crypto_hash::poseidon::PoseidonSponge,
+    encryption::ECIESPoseidonEncryption,
+    merkle_tree::{MaskedMerkleTreeParameters, MerklePath, MerkleTreeParameters},
+    polycommit::sonic_pc::SonicKZG10,
+    prelude::*,
+    prf::PoseidonPRF,
+    signature::AleoSignatureScheme,
+    snark::{
+        groth16::Groth16,
+        marlin::{
+            ahp::AHPForR1CS,
+            FiatShamirAlgebraicSpongeRng,
+            FiatShamirChaChaRng,
+            MarlinHidingMode,
+            MarlinNonHidingMode,
+            MarlinSNARK,
+        },
+    },
+};
+use snarkvm_curves::{
+    bls12_377::Bls12_377,
+    edwards_bls12::{
+        EdwardsAffine as EdwardsBls12Affine,
+        EdwardsParameters,
+        EdwardsProjective as EdwardsBls12Projective,
+    },
+    edwards_bw6::EdwardsProjective as EdwardsBW6,
+    traits::*,
+};
+use snarkvm_gadgets::{
+    algorithms::{
+        crh::{BHPCRHGadget, PedersenCompressedCRHGadget, PoseidonCRHGadget},
+        encryption::ECIESPoseidonEncryptionGadget,
+        prf::PoseidonPRFGadget,
+        signature::AleoSignatureSchemeGadget,
+    },
+    curves::edwards_bls12::EdwardsBls12Gadget,
+};
+use snarkvm_parameters::ParameterResolver;
+use snarkvm_utilities::{FromBytes, ToBytes, ToMinimalBits};
+
+use anyhow::{anyhow, Result};
+use blake2::{Blake2s256, Digest};
+use once_cell::sync::OnceCell;
+use rand::{CryptoRng, Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    fs,
+    io::{Read, Result as IoResult, Write},
+    path::PathBuf,
+    rc::Rc,
+};
+
+/// The environment variable for the seed that the devnet parameters are generated from.
+pub const DEVNET_SEED_ENV: &str = "SNARKVM_DEVNET_SEED";
+/// The environment variable for the directory that the devnet parameters are cached in.
+pub const DEVNET_DIRECTORY_ENV: &str = "SNARKVM_DEVNET_DIRECTORY";
+
+/// The version of the devnet parameters. This must be incremented whenever a circuit changes,
+/// as previously cached keys would no longer correspond to it.
+const DEVNET_PARAMETERS_VERSION: u16 = 0;
+
+/// A local development network with small Merkle trees and fast blocks.
+///
+/// Unlike the testnets, the devnet does not depend on downloaded parameters. Its SNARK keys
+/// and genesis block are generated deterministically from a seed on first use, and cached on disk.
+/// A custom genesis block, such as one from a `GenesisBuilder`, may be placed in the cache
+/// directory as `genesis.block` before the devnet is first used.
+///
+/// The devnet is insecure and must only be used for local development. Its seed defaults to zero,
+/// and is otherwise given in the environment, so anyone can regenerate its setup randomness
+/// (the SNARK trapdoors) to forge proofs, and the private key of its genesis account.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Devnet;
+
+#[rustfmt::skip]
+impl Network for Devnet {
+    const NETWORK_ID: u16 = u16::MAX;
+    const NETWORK_NAME: &'static str = "devnet";
+
+    const NUM_INPUT_RECORDS: usize = 2;
+    const NUM_OUTPUT_RECORDS: usize = 2;
+    const NUM_TRANSITIONS: u8 = u8::pow(2, Self::TRANSACTION_TREE_DEPTH as u32);
+    const NUM_EVENTS: u16 = 2;
+
+    const BLOCK_HASH_PREFIX: u16 = hrp2!("ab");
+    const LEDGER_ROOT_PREFIX: u16 = hrp2!("al");
+    const PROGRAM_ID_PREFIX: u16 = hrp2!("ap");
+    const TRANSITION_ID_PREFIX: u16 = hrp2!("as");
+    const TRANSACTION_ID_PREFIX: u16 = hrp2!("at");
+
+    const COMMITMENT_PREFIX: u16 = hrp2!("cm");
+    const FUNCTION_INPUTS_HASH_PREFIX: u16 = hrp2!("fi");
+    const FUNCTION_ID_PREFIX: u16 = hrp2!("fn");
+    const HEADER_NONCE_PREFIX: u16 = hrp2!("hn");
+    const HEADER_ROOT_PREFIX: u16 = hrp2!("hr");
+    const HEADER_TRANSACTIONS_ROOT_PREFIX: u16 = hrp2!("ht");
+    const INNER_CIRCUIT_ID_PREFIX: u16 = hrp2!("ic");
+    const RECORD_RANDOMIZER_PREFIX: u16 = hrp2!("rr");
+    const RECORD_VIEW_KEY_COMMITMENT_PREFIX: u16 = hrp2!("rc");
+    const SERIAL_NUMBER_PREFIX: u16 = hrp2!("sn");
+
+    const HEADER_PROOF_PREFIX: u32 = hrp4!("hzkp");
+    const INNER_PROOF_PREFIX: u32 = hrp4!("izkp");
+    const PROGRAM_PROOF_PREFIX: u32 = hrp4!("pzkp");
+    const RECORD_CIPHERTEXT_PREFIX: u32 = hrp4!("recd");
+    const RECORD_VIEW_KEY_PREFIX: u32 = hrp4!("rcvk");
+    const SIGNATURE_PREFIX: u32 = hrp4!("sign");
+
+    const ADDRESS_SIZE_IN_BYTES: usize = 32;
+    const HEADER_SIZE_IN_BYTES: usize = 1015;
+    const HEADER_PROOF_SIZE_IN_BYTES: usize = 883;
+    const INNER_PROOF_SIZE_IN_BYTES: usize = 193;
+    const PROGRAM_PROOF_SIZE_IN_BYTES: usize = 963;
+    const RECORD_SIZE_IN_BYTES: usize = 280;
+    const RECORD_CIPHERTEXT_SIZE_IN_BYTES: usize = 288;
+    const RECORD_PAYLOAD_SIZE_IN_BYTES: usize = 128;
+    const RECORD_VIEW_KEY_SIZE_IN_BYTES: usize = 32;
+    const SIGNATURE_SIZE_IN_BYTES: usize = 128;
+
+    const HEADER_TRANSACTIONS_TREE_DEPTH: usize = 8;
+    const HEADER_TREE_DEPTH: usize = 2;
+    const LEDGER_TREE_DEPTH: usize = 16;
+    const PROGRAM_TREE_DEPTH: usize = 8;
+    const TRANSITION_TREE_DEPTH: usize = 2;
+    const TRANSACTION_TREE_DEPTH: usize = 3;
+
+    const ALEO_BLOCK_TIME_IN_SECS: i64 = 1i64;
+    const ALEO_STARTING_SUPPLY_IN_CREDITS: i64 = 1_000_000_000;
+    const ALEO_FUTURE_TIME_LIMIT_IN_SECS: i64 = 90;
+    const ALEO_MAXIMUM_FORK_DEPTH: u32 = 4096;
+
+    type InnerCurve = Bls12_377;
+    type InnerScalarField = <Self::InnerCurve as PairingEngine>::Fr;
+    type InnerBaseField = <Self::InnerCurve as PairingEngine>::Fq;
+
+    type ProgramAffineCurve = EdwardsBls12Affine;
+    type ProgramAffineCurveGadget = EdwardsBls12Gadget;
+    type ProgramProjectiveCurve = EdwardsBls12Projective;
+    type ProgramCurveParameters = EdwardsParameters;
+    type ProgramBaseField = <Self::ProgramCurveParameters as ModelParameters>::BaseField;
+    type ProgramScalarField = <Self::ProgramCurveParameters as ModelParameters>::ScalarField;
+
+    type InnerSNARK = Groth16<Self::InnerCurve, InnerPublicVariables<Devnet>>;
+    type InnerProof = AleoObject<<Self::InnerSNARK as SNARK>::Proof, { Self::INNER_PROOF_PREFIX }, { Self::INNER_PROOF_SIZE_IN_BYTES }>;
+
+    type ProgramSNARK = MarlinSNARK<Self::InnerScalarField, Self::InnerBaseField, SonicKZG10<Self::InnerCurve>, FiatShamirAlgebraicSpongeRng<Self::InnerScalarField, Self::InnerBaseField, PoseidonSponge<Self::InnerBaseField, 6, 1>>, MarlinHidingMode, ProgramPublicVariables<Self>>;
+    type ProgramProvingKey = <Self::ProgramSNARK as SNARK>::ProvingKey;
+    type ProgramVerifyingKey = <Self::ProgramSNARK as SNARK>::VerifyingKey;
+    type ProgramProof = AleoObject<<Self::ProgramSNARK as SNARK>::Proof, { Self::PROGRAM_PROOF_PREFIX }, { Self::PROGRAM_PROOF_SIZE_IN_BYTES }>;
+
+    type PoSWSNARK = MarlinSNARK<Self::InnerScalarField, Self::InnerBaseField, SonicKZG10<Self::InnerCurve>, FiatShamirChaChaRng<Self::InnerScalarField, Self::InnerBaseField, Blake2s256>, MarlinNonHidingMode, Vec<Self::InnerScalarField>>;
+    type PoSWProof = AleoObject<<Self::PoSWSNARK as SNARK>::Proof, { Self::HEADER_PROOF_PREFIX }, { Self::HEADER_PROOF_SIZE_IN_BYTES }>;
+    type PoSW = PoSW<Self>;
+
+    type AccountEncryptionScheme = ECIESPoseidonEncryption<Self::ProgramCurveParameters>;
+    type AccountEncryptionGadget = ECIESPoseidonEncryptionGadget<Self::ProgramCurveParameters, Self::InnerScalarField>;
+
+    type AccountSeedPRF = PoseidonPRF<Self::ProgramScalarField, 4, false>;
+    type AccountSeed = <Self::AccountSeedPRF as PRF>::Seed;
+
+    type AccountSignatureScheme = AleoSignatureScheme<Self::ProgramCurveParameters>;
+    type AccountSignatureGadget = AleoSignatureSchemeGadget<Self::ProgramCurveParameters, Self::InnerScalarField>;
+    type AccountSignaturePublicKey = <Self::AccountSignatureScheme as SignatureScheme>::PublicKey;
+    type AccountSignature = AleoObject<<Self::AccountSignatureScheme as SignatureScheme>::Signature, { Self::SIGNATURE_PREFIX }, { Self::SIGNATURE_SIZE_IN_BYTES }>;
+
+    type BlockHashCRH = BHPCRH<Self::ProgramProjectiveCurve, 16, 32>;
+    type BlockHashCRHGadget = BHPCRHGadget<Self::ProgramProjectiveCurve, Self::InnerScalarField, Self::ProgramAffineCurveGadget, 16, 32>;
+    type BlockHash = AleoLocator<<Self::BlockHashCRH as CRH>::Output, { Self::BLOCK_HASH_PREFIX }>;
+
+    type BlockHeaderRootCRH = PedersenCompressedCRH<Self::ProgramProjectiveCurve, 4, 128>;
+    type BlockHeaderRootCRHGadget = PedersenCompressedCRHGadget<Self::ProgramProjectiveCurve, Self::InnerScalarField, Self::ProgramAffineCurveGadget, 4, 128>;
+    type BlockHeaderRootParameters = MaskedMerkleTreeParameters<Self::BlockHeaderRootCRH, { Self::HEADER_TREE_DEPTH }>;
+    type BlockHeaderRoot = AleoLocator<<Self::BlockHeaderRootCRH as CRH>::Output, { Self::HEADER_ROOT_PREFIX }>;
+
+    type CommitmentScheme = BHPCRH<Self::ProgramProjectiveCurve, 41, 63>;
+    type CommitmentGadget = BHPCRHGadget<Self::ProgramProjectiveCurve, Self::InnerScalarField, Self::ProgramAffineCurveGadget, 41, 63>;
+    type Commitment = AleoLocator<<Self::CommitmentScheme as CRH>::Output, { Self::COMMITMENT_PREFIX }>;
+
+    type FunctionIDCRH = PoseidonCRH<Self::InnerBaseField, 34>;
+    type FunctionIDCRHGadget = PoseidonCRHGadget<Self::InnerBaseField, 34>;
+    type FunctionID = AleoLocator<<Self::FunctionIDCRH as CRH>::Output, { Self::FUNCTION_ID_PREFIX }>;
+
+    type FunctionInputsCRH = PoseidonCRH<Self::InnerScalarField, 128>;
+    type FunctionInputsCRHGadget = PoseidonCRHGadget<Self::InnerScalarField, 128>;
+    type FunctionInputsHash = AleoLocator<<Self::FunctionInputsCRH as CRH>::Output, { Self::FUNCTION_INPUTS_HASH_PREFIX }>;
+
+    type InnerCircuitIDCRH = BHPCRH<EdwardsBW6, 85, 63>;
+    type InnerCircuitID = AleoLocator<<Self::InnerCircuitIDCRH as CRH>::Output, { Self::INNER_CIRCUIT_ID_PREFIX }>;
+
+    type LedgerRootCRH = BHPCRH<Self::ProgramProjectiveCurve, 16, 32>;
+    type LedgerRootCRHGadget = BHPCRHGadget<Self::ProgramProjectiveCurve, Self::InnerScalarField, Self::ProgramAffineCurveGadget, 16, 32>;
+    type LedgerRootParameters = MerkleTreeParameters<Self::LedgerRootCRH, { Self::LEDGER_TREE_DEPTH }>;
+    type LedgerRoot = AleoLocator<<Self::LedgerRootCRH as CRH>::Output, { Self::LEDGER_ROOT_PREFIX }>;
+
+    type PoSWMaskPRF = PoseidonPRF<Self::InnerScalarField, 4, false>;
+    type PoSWMaskPRFGadget = PoseidonPRFGadget<Self::InnerScalarField, 4, false>;
+    type PoSWNonce = AleoLocator<Self::InnerScalarField, { Self::HEADER_NONCE_PREFIX }>;
+
+    type ProgramIDCRH = BHPCRH<EdwardsBW6, 16, 48>;
+    type ProgramIDParameters = MerkleTreeParameters<Self::ProgramIDCRH, { Self::PROGRAM_TREE_DEPTH }>;
+    type ProgramID = AleoLocator<<Self::ProgramIDCRH as CRH>::Output, { Self::PROGRAM_ID_PREFIX }>;
+
+    type RecordCiphertext = AleoObject<Ciphertext<Self>, { Self::RECORD_CIPHERTEXT_PREFIX }, { Self::RECORD_CIPHERTEXT_SIZE_IN_BYTES }>;
+    type RecordRandomizer = AleoLocator<<Self::AccountEncryptionScheme as EncryptionScheme>::CiphertextRandomizer, { Self::RECORD_RANDOMIZER_PREFIX }>;
+    type RecordViewKey = AleoObject<<Self::AccountEncryptionScheme as EncryptionScheme>::SymmetricKey, { Self::RECORD_VIEW_KEY_PREFIX }, { Self::RECORD_VIEW_KEY_SIZE_IN_BYTES }>;
+    type RecordViewKeyCommitment = AleoLocator<<Self::AccountEncryptionScheme as EncryptionScheme>::SymmetricKeyCommitment, { Self::RECORD_VIEW_KEY_COMMITMENT_PREFIX }>;
+
+    type SerialNumberPRF = PoseidonPRF<Self::InnerScalarField, 4, false>;
+    type SerialNumberPRFGadget = PoseidonPRFGadget<Self::InnerScalarField, 4, false>;
+    type SerialNumber = AleoLocator<<Self::SerialNumberPRF as PRF>::Output, { Self::SERIAL_NUMBER_PREFIX }>;
+
+    type TransactionsRootCRH = BHPCRH<Self::ProgramProjectiveCurve, 16, 32>;
+    type TransactionsRootCRHGadget = BHPCRHGadget<Self::ProgramProjectiveCurve, Self::InnerScalarField, Self::ProgramAffineCurveGadget, 16, 32>;
+    type TransactionsRootParameters = MerkleTreeParameters<Self::TransactionsRootCRH, { Self::HEADER_TRANSACTIONS_TREE_DEPTH }>;
+    type TransactionsRoot = AleoLocator<<Self::TransactionsRootCRH as CRH>::Output, { Self::HEADER_TRANSACTIONS_ROOT_PREFIX }>;
+
+    type TransactionIDCRH = BHPCRH<Self::ProgramProjectiveCurve, 16, 32>;
+    type TransactionIDCRHGadget = BHPCRHGadget<Self::ProgramProjectiveCurve, Self::InnerScalarField, Self::ProgramAffineCurveGadget, 16, 32>;
+    type TransactionIDParameters = MerkleTreeParameters<Self::TransactionIDCRH, { Self::TRANSACTION_TREE_DEPTH }>;
+    type TransactionID = AleoLocator<<Self::TransactionIDCRH as CRH>::Output, { Self::TRANSACTION_ID_PREFIX }>;
+
+    type TransitionIDCRH = BHPCRH<Self::ProgramProjectiveCurve, 16, 32>;
+    type TransitionIDCRHGadget = BHPCRHGadget<Self::ProgramProjectiveCurve, Self::InnerScalarField, Self::ProgramAffineCurveGadget, 16, 32>;
+    type TransitionIDParameters = MerkleTreeParameters<Self::TransitionIDCRH, { Self::TRANSITION_TREE_DEPTH }>;
+    type TransitionID = AleoLocator<<Self::TransitionIDCRH as CRH>::Output, { Self::TRANSITION_ID_PREFIX }>;
+
+    dpc_setup!{Devnet, account_encryption_scheme, AccountEncryptionScheme, ACCOUNT_ENCRYPTION_AND_SIGNATURE_INPUT}
+    dpc_setup!{Devnet, account_signature_scheme, AccountSignatureScheme, ACCOUNT_ENCRYPTION_AND_SIGNATURE_INPUT}
+    dpc_setup!{Devnet, block_hash_crh, BlockHashCRH, "AleoBlockHashCRH0"}
+    dpc_setup!{Devnet, block_header_root_parameters, BlockHeaderRootParameters, "AleoBlockHeaderRootCRH0"}
+    dpc_setup!{Devnet, commitment_scheme, CommitmentScheme, "AleoCommitmentScheme0"}
+    dpc_setup!{Devnet, function_id_crh, FunctionIDCRH, "AleoFunctionIDCRH0"}
+    dpc_setup!{Devnet, inner_circuit_id_crh, InnerCircuitIDCRH, "AleoInnerCircuitIDCRH0"}
+    dpc_setup!{Devnet, ledger_root_parameters, LedgerRootParameters, "AleoLedgerRootCRH0"}
+    dpc_setup!{Devnet, program_id_parameters, ProgramIDParameters, "AleoProgramIDCRH0"}
+    dpc_setup!{Devnet, transactions_root_parameters, TransactionsRootParameters, "AleoTransactionsRootCRH0"}
+    dpc_setup!{Devnet, transaction_id_parameters, TransactionIDParameters, "AleoTransactionIDCRH0"}
+    dpc_setup!{Devnet, transition_id_parameters, TransitionIDParameters, "AleoTransitionIDCRH0"}
+
+    fn inner_circuit_id() -> &'static Self::InnerCircuitID {
+        static INNER_CIRCUIT_ID: OnceCell<<Devnet as Network>::InnerCircuitID> = OnceCell::new();
+        INNER_CIRCUIT_ID.get_or_init(|| Self::inner_circuit_id_crh()
+            .hash_bits(&Self::inner_verifying_key().to_minimal_bits())
+            .expect("Failed to hash inner circuit verifying key elements").into())
+    }
+
+    fn inner_proving_key() -> &'static <Self::InnerSNARK as SNARK>::ProvingKey {
+        &Self::inner_keys().proving_key
+    }
+
+    fn inner_verifying_key() -> &'static <Self::InnerSNARK as SNARK>::VerifyingKey {
+        &Self::inner_keys().verifying_key
+    }
+
+    fn noop_program() -> &'static Program<Self> {
+        static NOOP_PROGRAM: OnceCell<Program<Devnet>> = OnceCell::new();
+        NOOP_PROGRAM.get_or_init(|| Program::<Devnet>::new_noop().expect("Failed to fetch the noop program"))
+    }
+
+    fn noop_program_id() -> &'static Self::ProgramID {
+        static NOOP_PROGRAM_ID: OnceCell<<Devnet as Network>::ProgramID> = OnceCell::new();
+        NOOP_PROGRAM_ID.get_or_init(|| Devnet::noop_program().program_id())
+    }
+
+    fn noop_program_path() -> &'static MerklePath<Self::ProgramIDParameters> {
+        static NOOP_PROGRAM_PATH: OnceCell<MerklePath<<Devnet as Network>::ProgramIDParameters>> = OnceCell::new();
+        NOOP_PROGRAM_PATH.get_or_init(|| Self::noop_program().to_program_path(Self::noop_function_id()).expect("Failed to fetch the noop program path"))
+    }
+
+    fn noop_function_id() -> &'static Self::FunctionID {
+        static NOOP_FUNCTION_ID: OnceCell<<Devnet as Network>::FunctionID> = OnceCell::new();
+        NOOP_FUNCTION_ID.get_or_init(|| Self::function_id(Self::noop_circuit_verifying_key()).expect("Failed to hash noop circuit verifying key"))
+    }
+
+    fn noop_circuit_proving_key() -> &'static <Self::ProgramSNARK as SNARK>::ProvingKey {
+        &Self::noop_keys().proving_key
+    }
+
+    fn noop_circuit_verifying_key() -> &'static <Self::ProgramSNARK as SNARK>::VerifyingKey {
+        &Self::noop_keys().verifying_key
+    }
+
+    fn posw_proving_key() -> &'static <Self::PoSWSNARK as SNARK>::ProvingKey {
+        &Self::posw_keys().proving_key
+    }
+
+    fn posw_verifying_key() -> &'static <Self::PoSWSNARK as SNARK>::VerifyingKey {
+        &Self::posw_keys().verifying_key
+    }
+
+    fn posw() -> &'static Self::PoSW {
+        static POSW: OnceCell<<Devnet as Network>::PoSW> = OnceCell::new();
+        POSW.get_or_init(|| <Self::PoSW as PoSWScheme<Self>>::load(true).expect("Failed to load PoSW"))
+    }
+
+    fn genesis_block() -> &'static Block<Self> {
+        static BLOCK: OnceCell<Block<Devnet>> = OnceCell::new();
+        BLOCK.get_or_init(|| Self::load_or_setup("genesis.block", |rng| {
+            let recipient = Address::from_private_key(Self::genesis_private_key());
+            Block::new_genesis(recipient, rng)
+        }).expect("Failed to set up the devnet genesis block"))
+    }
+
+    /// Returns the program SRS for Aleo applications.
+    fn program_srs<R: Rng + CryptoRng>(_rng: &mut R) -> Rc<RefCell<SRS<R, <Self::ProgramSNARK as SNARK>::UniversalSetupParameters>>> {
+        Rc::new(RefCell::new(SRS::<_, _>::Universal(Self::universal_srs())))
+    }
+}
+
+/// The proving key and verifying key of a circuit, as cached on disk.
+struct CircuitKeys<S: SNARK> {
+    proving_key: S::ProvingKey,
+    verifying_key: S::VerifyingKey,
+}
+
+impl<S: SNARK> FromBytes for CircuitKeys<S> {
+    #[inline]
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let proving_key = FromBytes::read_le(&mut reader)?;
+        let verifying_key = FromBytes::read_le(&mut reader)?;
+        Ok(Self { proving_key, verifying_key })
+    }
+}
+
+impl<S: SNARK> ToBytes for CircuitKeys<S> {
+    #[inline]
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.proving_key.write_le(&mut writer)?;
+        self.verifying_key.write_le(&mut writer)
+    }
+}
+
+impl Devnet {
+    /// Returns the seed that the devnet parameters are generated from,
+    /// as given by `SNARKVM_DEVNET_SEED`, or zero by default.
+    pub fn seed() -> u64 {
+        static SEED: OnceCell<u64> = OnceCell::new();
+        *SEED.get_or_init(|| match std::env::var(DEVNET_SEED_ENV) {
+            Ok(seed) => seed.trim().parse().expect("Failed to parse the devnet seed"),
+            Err(_) => 0,
+        })
+    }
+
+    /// Returns the directory that the devnet parameters are cached in, as given by `SNARKVM_DEVNET_DIRECTORY`,
+    /// or a directory for the current seed in the parameter cache by default.
+    pub fn cache_directory() -> &'static PathBuf {
+        static DIRECTORY: OnceCell<PathBuf> = OnceCell::new();
+        DIRECTORY.get_or_init(|| match std::env::var_os(DEVNET_DIRECTORY_ENV) {
+            Some(directory) => PathBuf::from(directory),
+            None => ParameterResolver::global().cache_directory().join(format!(
+                "devnet-{}-v{}",
+                Self::seed(),
+                DEVNET_PARAMETERS_VERSION
+            )),
+        })
+    }
+
+    /// Returns the private key of the account that receives the coinbase of the genesis block.
+    /// As it is derived from the devnet seed, this key is public and must never hold real funds.
+    pub fn genesis_private_key() -> &'static PrivateKey<Devnet> {
+        static PRIVATE_KEY: OnceCell<PrivateKey<Devnet>> = OnceCell::new();
+        PRIVATE_KEY.get_or_init(|| PrivateKey::new(&mut Self::rng("genesis.account")))
+    }
+
+    /// Returns the universal SRS for the program and PoSW circuits.
+    fn universal_srs() -> &'static <<Self as Network>::ProgramSNARK as SNARK>::UniversalSetupParameters {
+        static UNIVERSAL_SRS: OnceCell<<<Devnet as Network>::ProgramSNARK as SNARK>::UniversalSetupParameters> =
+            OnceCell::new();
+        UNIVERSAL_SRS.get_or_init(|| {
+            Self::load_or_setup("universal.srs", |rng| {
+                // The PoSW circuit is the largest circuit that is set up from this SRS.
+                let max_degree = AHPForR1CS::<<Self as Network>::InnerScalarField, MarlinHidingMode>::max_degree(
+                    65536, 65536, 131072,
+                )
+                .map_err(|error| anyhow!("{:?}", error))?;
+                Ok(<<Self as Network>::ProgramSNARK as SNARK>::universal_setup(&max_degree, rng)?)
+            })
+            .expect("Failed to set up the devnet universal SRS")
+        })
+    }
+
+    /// Returns the keys for the noop circuit.
+    fn noop_keys() -> &'static CircuitKeys<<Self as Network>::ProgramSNARK> {
+        static NOOP_KEYS: OnceCell<CircuitKeys<<Devnet as Network>::ProgramSNARK>> = OnceCell::new();
+        NOOP_KEYS.get_or_init(|| {
+            Self::load_or_setup("noop.keys", |rng| {
+                let (proving_key, verifying_key) = <<Self as Network>::ProgramSNARK as SNARK>::setup(
+                    &SynthesizedCircuit::<Self>::Noop(Default::default()),
+                    &mut *Self::program_srs(rng).borrow_mut(),
+                )?;
+                Ok(CircuitKeys { proving_key, verifying_key })
+            })
+            .expect("Failed to set up the devnet noop circuit keys")
+        })
+    }
+
+    /// Returns the keys for the inner circuit.
+    fn inner_keys() -> &'static CircuitKeys<<Self as Network>::InnerSNARK> {
+        static INNER_KEYS: OnceCell<CircuitKeys<<Devnet as Network>::InnerSNARK>> = OnceCell::new();
+        INNER_KEYS.get_or_init(|| {
+            Self::load_or_setup("inner.keys", |rng| {
+                let (proving_key, verifying_key) = <<Self as Network>::InnerSNARK as SNARK>::setup(
+                    &InnerCircuit::<Self>::blank(),
+                    &mut SRS::CircuitSpecific(rng),
+                )?;
+                Ok(CircuitKeys { proving_key, verifying_key })
+            })
+            .expect("Failed to set up the devnet inner circuit keys")
+        })
+    }
+
+    /// Returns the keys for the PoSW circuit.
+    fn posw_keys() -> &'static CircuitKeys<<Self as Network>::PoSWSNARK> {
+        static POSW_KEYS: OnceCell<CircuitKeys<<Devnet as Network>::PoSWSNARK>> = OnceCell::new();
+        POSW_KEYS.get_or_init(|| {
+            Self::load_or_setup("posw.keys", |_| {
+                let (proving_key, verifying_key) = <<Self as Network>::PoSWSNARK as SNARK>::setup::<_, ChaChaRng>(
+                    &PoSWCircuit::<Self>::blank()?,
+                    &mut SRS::Universal(Self::universal_srs()),
+                )?;
+                Ok(CircuitKeys { proving_key, verifying_key })
+            })
+            .expect("Failed to set up the devnet PoSW keys")
+        })
+    }
+
+    /// Returns a deterministic RNG for the given setup stage, derived from the devnet seed.
+    /// Each stage has its own RNG, so that the parameters do not depend on which ones are cached.
+    fn rng(stage: &str) -> ChaChaRng {
+        let digest = Blake2s256::digest(format!("AleoDevnet{}:{}", Self::seed(), stage).as_bytes());
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&digest);
+        ChaChaRng::from_seed(seed)
+    }
+
+    /// Returns the parameter stored in the given file in the cache directory,
+    /// or runs its setup and stores the result, if it has not been cached yet.
+    /// A cached parameter that fails to load is not overwritten, as the cache directory may be shared with
+    /// a different devnet seed or parameters version. Instead, the parameter is set up in memory, as it is
+    /// when the cache directory is not writable, and a warning is logged. An error is only returned
+    /// if the setup itself fails.
+    fn load_or_setup<T: FromBytes + ToBytes>(
+        filename: &str,
+        setup: impl FnOnce(&mut ChaChaRng) -> Result<T>,
+    ) -> Result<T> {
+        let path = Self::cache_directory().join(filename);
+        let is_cached = path.exists();
+        if is_cached {
+            match fs::read(&path).map_err(anyhow::Error::from).and_then(|bytes| Ok(T::read_le(&bytes[..])?)) {
+                Ok(parameter) => return Ok(parameter),
+                Err(error) => eprintln!(
+                    "Failed to load the devnet parameter {:?}, setting it up in memory (remove it to cache it): {}",
+                    path, error
+                ),
+            }
+        }
+
+        let parameter = setup(&mut Self::rng(filename))
+            .map_err(|error| anyhow!("Failed to set up the devnet parameter {}: {}", filename, error))?;
+
+        // Write to a temporary file first, so that an interrupted write is never mistaken for a cached parameter.
+        let store = || -> Result<()> {
+            fs::create_dir_all(Self::cache_directory())?;
+            let temporary_path = path.with_extension("partial");
+            fs::write(&temporary_path, parameter.to_bytes_le()?)?;
+            fs::rename(&temporary_path, &path)?;
+            Ok(())
+        };
+        if !is_cached {
+            if let Err(error) = store() {
+                eprintln!(
+                    "Failed to cache the devnet parameter {:?} (see {}), it will be set up again on the next run: {}",
+                    path, DEVNET_DIRECTORY_ENV, error
+                );
+            }
+        }
+
+        Ok(parameter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_name_sanity_check() {
+        assert_eq!(Devnet::NETWORK_NAME, "devnet");
+    }
+
+    #[test]
+    fn test_tree_depths_sanity_check() {
+        // Verify the Merkle tree depths match the declared depths.
+        assert_eq!(
+            Devnet::HEADER_TREE_DEPTH,
+            <<Devnet as Network>::BlockHeaderRootParameters as MerkleParameters>::DEPTH
+        );
+        assert_eq!(Devnet::LEDGER_TREE_DEPTH, <<Devnet as Network>::LedgerRootParameters as MerkleParameters>::DEPTH);
+        assert_eq!(
+            Devnet::HEADER_TRANSACTIONS_TREE_DEPTH,
+            <<Devnet as Network>::TransactionsRootParameters as MerkleParameters>::DEPTH
+        );
+        assert_eq!(Devnet::NUM_TRANSITIONS, 8);
+    }
+
+    #[test]
+    fn test_rng_is_deterministic() {
+        assert_eq!(Devnet::rng("test").gen::<u64>(), Devnet::rng("test").gen::<u64>());
+        assert_ne!(Devnet::rng("test").gen::<u64>(), Devnet::rng("other").gen::<u64>());
+    }
+
+    #[test]
+    fn test_inner_circuit_sanity_check() {
+        // Verify the inner circuit verifying key matches the one derived from the inner circuit proving key.
+        assert_eq!(
+            Devnet::inner_verifying_key(),
+            &Devnet::inner_proving_key().vk,
+            "The inner circuit verifying key does not correspond to the inner circuit proving key"
+        );
+    }
+
+    #[test]
+    fn test_load_or_setup_corrupt_parameter() {
+        let path = Devnet::cache_directory().join("test.corrupt");
+        fs::create_dir_all(Devnet::cache_directory()).unwrap();
+        fs::write(&path, [0u8; 2]).unwrap();
+
+        // Ensure a corrupt cached parameter is set up in memory, and is not overwritten.
+        assert_eq!(7u64, Devnet::load_or_setup("test.corrupt", |_| Ok(7u64)).unwrap());
+        assert_eq!(vec![0u8; 2], fs::read(&path).unwrap());
+
+        // Ensure a failed setup is returned as an error.
+        assert!(Devnet::load_or_setup::<u64>("test.corrupt", |_| Err(anyhow!("Setup failed"))).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_genesis_block() {
+        let block = Devnet::genesis_block();
+        assert!(block.is_genesis());
+        assert!(Devnet::posw().verify_from_block_header(block.header()));
+
+        // Verify the cached genesis block is read back unchanged.
+        let bytes = fs::read(Devnet::cache_directory().join("genesis.block")).unwrap();
+        assert_eq!(block, &Block::<Devnet>::read_le(&bytes[..]).unwrap());
+    }
+}
@@ -23,6 +23,9 @@ pub mod macros;
 pub mod object;
 pub use object::*;
 
+#[cfg(feature = "devnet")]
+pub mod devnet;
+
 #[cfg(feature = "testnet1")]
 pub mod testnet1;
 
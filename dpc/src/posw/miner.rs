@@ -0,0 +1,365 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{posw::PoSWCircuit, BlockHeader, BlockHeaderMetadata, BlockTemplate, Network, PoSWError, PoSWScheme};
+use snarkvm_utilities::UniformRand;
+
+use core::{
+    convert::TryFrom,
+    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+};
+use rand::{rngs::StdRng, CryptoRng, Rng, SeedableRng};
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use time::OffsetDateTime;
+
+/// The default maximum duration to mine a block template, counted from its block timestamp.
+pub const DEFAULT_MAXIMUM_MINING_DURATION: Duration = Duration::from_secs(600);
+/// The default interval at which mining statistics are reported.
+pub const DEFAULT_STATISTICS_INTERVAL: Duration = Duration::from_secs(10);
+/// The interval at which the miner checks for termination and the mining duration.
+const POLLING_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The statistics of mining a block template.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MiningStatistics {
+    /// The number of PoSW proofs computed.
+    num_proofs: u64,
+    /// The lowest (best) proof difficulty computed, if any.
+    best_proof_difficulty: Option<u64>,
+    /// The total duration spent mining.
+    elapsed: Duration,
+}
+
+impl MiningStatistics {
+    /// Returns the number of PoSW proofs computed.
+    pub fn num_proofs(&self) -> u64 {
+        self.num_proofs
+    }
+
+    /// Returns the lowest (best) proof difficulty computed, if any.
+    pub fn best_proof_difficulty(&self) -> Option<u64> {
+        self.best_proof_difficulty
+    }
+
+    /// Returns the total duration spent mining.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the number of PoSW proofs computed per second.
+    pub fn proofs_per_second(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            elapsed if elapsed > 0.0 => self.num_proofs as f64 / elapsed,
+            _ => 0.0,
+        }
+    }
+}
+
+///
+/// A multi-threaded PoSW miner, which runs worker threads over the same block template.
+/// Each worker samples nonces from its own independently-seeded RNG, so the workers search disjoint nonces.
+///
+/// Mining the same block template again, for instance after it was terminated, resumes its statistics.
+/// The mining deadline is always the block timestamp of the template plus the maximum mining duration,
+/// regardless of when mining started, so a template may not be mined past its deadline by resuming it.
+/// Each worker attempts at least one proof, so a template with a past timestamp, such as a genesis
+/// block template, may still be mined if its difficulty target is reached on the first attempt.
+///
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"))]
+pub struct Miner<N: Network> {
+    /// The number of worker threads.
+    num_threads: usize,
+    /// The maximum duration to mine a block template, counted from its block timestamp.
+    maximum_mining_duration: Duration,
+    /// The interval at which mining statistics are reported.
+    statistics_interval: Duration,
+    /// The block header root and the statistics of the latest block template that was mined.
+    session: Option<(N::BlockHeaderRoot, MiningStatistics)>,
+}
+
+impl<N: Network> Miner<N> {
+    /// Initializes a new instance of a miner with the given number of worker threads.
+    pub fn new(num_threads: usize) -> Self {
+        Self {
+            num_threads: num_threads.max(1),
+            maximum_mining_duration: DEFAULT_MAXIMUM_MINING_DURATION,
+            statistics_interval: DEFAULT_STATISTICS_INTERVAL,
+            session: None,
+        }
+    }
+
+    /// Sets the maximum duration to mine a block template, counted from its block timestamp.
+    pub fn with_maximum_mining_duration(mut self, maximum_mining_duration: Duration) -> Self {
+        self.maximum_mining_duration = maximum_mining_duration;
+        self
+    }
+
+    /// Sets the interval at which mining statistics are reported.
+    pub fn with_statistics_interval(mut self, statistics_interval: Duration) -> Self {
+        self.statistics_interval = statistics_interval;
+        self
+    }
+
+    /// Returns the number of worker threads.
+    pub fn num_threads(&self) -> usize {
+        self.num_threads
+    }
+
+    /// Returns the maximum duration to mine a block template, counted from its block timestamp.
+    pub fn maximum_mining_duration(&self) -> Duration {
+        self.maximum_mining_duration
+    }
+
+    /// Returns the interval at which mining statistics are reported.
+    pub fn statistics_interval(&self) -> Duration {
+        self.statistics_interval
+    }
+
+    /// Returns the statistics of the latest block template that was mined, if any.
+    pub fn statistics(&self) -> Option<MiningStatistics> {
+        self.session.as_ref().map(|(_, statistics)| *statistics)
+    }
+
+    ///
+    /// Given the block template, compute a PoSW proof and nonce that satisfies the difficulty target,
+    /// and returns the resulting block header. Mining stops once the terminator is set, or once the
+    /// maximum mining duration has elapsed since the block timestamp of the template. The mining
+    /// statistics are reported to `on_statistics` at every statistics interval, and once mining stops.
+    ///
+    pub fn mine<P: PoSWScheme<N>, F: Fn(&MiningStatistics), R: Rng + CryptoRng>(
+        &mut self,
+        posw: &P,
+        block_template: &BlockTemplate<N>,
+        terminator: &AtomicBool,
+        on_statistics: F,
+        rng: &mut R,
+    ) -> Result<BlockHeader<N>, PoSWError> {
+        // Resume the statistics of the latest session, if it mined the same block template.
+        let block_header_root = block_template.to_header_root()?;
+        let resumed = match &self.session {
+            Some((root, statistics)) if *root == block_header_root => *statistics,
+            _ => MiningStatistics::default(),
+        };
+
+        // The deadline is relative to the block timestamp, as a block must be mined shortly after its timestamp.
+        let deadline = block_template
+            .block_timestamp()
+            .saturating_add(i64::try_from(self.maximum_mining_duration.as_secs()).unwrap_or(i64::MAX));
+
+        let start = Instant::now();
+        let num_proofs = AtomicU64::new(0);
+        let best_proof_difficulty = AtomicU64::new(resumed.best_proof_difficulty.unwrap_or(u64::MAX));
+        let to_statistics = || MiningStatistics {
+            num_proofs: resumed.num_proofs + num_proofs.load(Ordering::SeqCst),
+            best_proof_difficulty: match best_proof_difficulty.load(Ordering::SeqCst) {
+                u64::MAX => resumed.best_proof_difficulty,
+                difficulty => Some(difficulty),
+            },
+            elapsed: resumed.elapsed + start.elapsed(),
+        };
+
+        let is_stopped = AtomicBool::new(false);
+        let num_active_workers = AtomicUsize::new(self.num_threads);
+        let result = Mutex::new(None);
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.num_threads)
+            .build()
+            .map_err(|error| PoSWError::Message(format!("Failed to initialize the mining threads: {}", error)))?;
+        let seeds = (0..self.num_threads).map(|_| rng.gen::<[u8; 32]>()).collect::<Vec<_>>();
+
+        pool.in_place_scope(|scope| {
+            for seed in seeds {
+                let (num_proofs, best_proof_difficulty) = (&num_proofs, &best_proof_difficulty);
+                let (is_stopped, num_active_workers, result) = (&is_stopped, &num_active_workers, &result);
+
+                scope.spawn(move |_| {
+                    let rng = &mut StdRng::from_seed(seed);
+                    let outcome = Self::mine_worker(
+                        posw,
+                        block_template,
+                        deadline,
+                        is_stopped,
+                        num_proofs,
+                        best_proof_difficulty,
+                        rng,
+                    );
+
+                    // Store the first block header or error, and stop the other workers.
+                    if let Some(outcome) = outcome.transpose() {
+                        if let Ok(mut result) = result.lock() {
+                            result.get_or_insert(outcome);
+                        }
+                        is_stopped.store(true, Ordering::SeqCst);
+                    }
+                    num_active_workers.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+
+            // Monitor the workers, until they stop.
+            let mut last_report = Instant::now();
+            while num_active_workers.load(Ordering::SeqCst) > 0 {
+                if terminator.load(Ordering::SeqCst) {
+                    is_stopped.store(true, Ordering::SeqCst);
+                }
+                if last_report.elapsed() >= self.statistics_interval {
+                    on_statistics(&to_statistics());
+                    last_report = Instant::now();
+                }
+                std::thread::sleep(POLLING_INTERVAL);
+            }
+        });
+
+        // Save the statistics, to resume mining the same block template.
+        let statistics = to_statistics();
+        self.session = Some((block_header_root, statistics));
+        on_statistics(&statistics);
+
+        match result.into_inner().map_err(|_| PoSWError::Message("Failed to retrieve the mining result".to_string()))? {
+            Some(result) => result,
+            None if terminator.load(Ordering::SeqCst) => Err(PoSWError::Message("Mining was terminated".to_string())),
+            None => Err(PoSWError::Message("Failed mine block in the allowed mining duration".to_string())),
+        }
+    }
+
+    ///
+    /// Runs one mining worker until it finds a block header, until mining is stopped,
+    /// or until the given deadline has passed after an attempt.
+    ///
+    fn mine_worker<P: PoSWScheme<N>, R: Rng + CryptoRng>(
+        posw: &P,
+        block_template: &BlockTemplate<N>,
+        deadline: i64,
+        is_stopped: &AtomicBool,
+        num_proofs: &AtomicU64,
+        best_proof_difficulty: &AtomicU64,
+        rng: &mut R,
+    ) -> Result<Option<BlockHeader<N>>, PoSWError> {
+        // Instantiate the circuit.
+        let mut circuit = PoSWCircuit::<N>::new(block_template, UniformRand::rand(rng))?;
+
+        while !is_stopped.load(Ordering::SeqCst) {
+            // Run one iteration of PoSW. If mining stopped, the proof is terminated.
+            let proof = match posw.prove_once_unchecked(&mut circuit, is_stopped, rng) {
+                Ok(proof) => proof,
+                Err(_) if is_stopped.load(Ordering::SeqCst) => return Ok(None),
+                Err(error) => return Err(error),
+            };
+
+            num_proofs.fetch_add(1, Ordering::SeqCst);
+            if let Ok(proof_difficulty) = proof.to_proof_difficulty() {
+                best_proof_difficulty.fetch_min(proof_difficulty, Ordering::SeqCst);
+            }
+
+            // Check if the updated block header is valid.
+            if posw.verify(block_template.difficulty_target(), &circuit.to_public_inputs(), &proof) {
+                // Construct a block header.
+                return Ok(Some(BlockHeader::from(
+                    block_template.previous_ledger_root(),
+                    block_template.transactions().transactions_root(),
+                    BlockHeaderMetadata::new(block_template),
+                    circuit.nonce(),
+                    proof,
+                )?));
+            }
+
+            // Stop once the deadline has passed, after at least one attempt.
+            if OffsetDateTime::now_utc().unix_timestamp() >= deadline {
+                return Ok(None);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    use rand::thread_rng;
+
+    /// Returns a block template for the genesis block, with the given block timestamp and difficulty target.
+    fn genesis_template(block_timestamp: i64, difficulty_target: u64) -> BlockTemplate<Testnet2> {
+        let block = Testnet2::genesis_block();
+        BlockTemplate::new(
+            block.previous_block_hash(),
+            block.height(),
+            block_timestamp,
+            difficulty_target,
+            block.cumulative_weight(),
+            block.previous_ledger_root(),
+            block.transactions().clone(),
+            block.to_coinbase_transaction().unwrap().to_records().next().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_mine() {
+        let block_template =
+            genesis_template(OffsetDateTime::now_utc().unix_timestamp(), Testnet2::genesis_block().difficulty_target());
+
+        let mut miner = Miner::<Testnet2>::new(2);
+        let block_header =
+            miner.mine(Testnet2::posw(), &block_template, &AtomicBool::new(false), |_| (), &mut thread_rng()).unwrap();
+        assert!(Testnet2::posw().verify_from_block_header(&block_header));
+
+        let statistics = miner.statistics().unwrap();
+        assert!(statistics.num_proofs() >= 1);
+        assert!(statistics.best_proof_difficulty().is_some());
+    }
+
+    #[test]
+    fn test_mine_stops_and_resumes() {
+        // Construct a block template with an unreachable difficulty target.
+        let block_template = genesis_template(OffsetDateTime::now_utc().unix_timestamp(), 0);
+
+        // Ensure mining stops once the terminator is set.
+        let mut miner = Miner::<Testnet2>::new(2).with_maximum_mining_duration(Duration::from_secs(2));
+        let result = miner.mine(Testnet2::posw(), &block_template, &AtomicBool::new(true), |_| (), &mut thread_rng());
+        assert!(result.is_err());
+        let terminated_statistics = miner.statistics().unwrap();
+
+        // Ensure mining resumes the statistics, and stops once the deadline of the block template passed.
+        let reports = Mutex::new(0);
+        let on_statistics = |_: &MiningStatistics| *reports.lock().unwrap() += 1;
+        let result =
+            miner.mine(Testnet2::posw(), &block_template, &AtomicBool::new(false), on_statistics, &mut thread_rng());
+        assert!(result.is_err());
+
+        let statistics = miner.statistics().unwrap();
+        assert!(statistics.elapsed() >= Duration::from_secs(1));
+        assert!(statistics.num_proofs() >= terminated_statistics.num_proofs());
+        assert!(*reports.lock().unwrap() >= 1);
+    }
+
+    #[test]
+    fn test_mine_expired_template() {
+        // Construct a block template whose deadline has already passed.
+        let block_template = genesis_template(OffsetDateTime::now_utc().unix_timestamp() - 3, 0);
+
+        // Ensure mining stops after one attempt, as the deadline is relative to the block timestamp.
+        let mut miner = Miner::<Testnet2>::new(1).with_maximum_mining_duration(Duration::from_secs(2));
+        let result = miner.mine(Testnet2::posw(), &block_template, &AtomicBool::new(false), |_| (), &mut thread_rng());
+        assert!(result.is_err());
+        assert_eq!(1, miner.statistics().unwrap().num_proofs());
+    }
+}
@@ -17,6 +17,12 @@
 pub mod circuit;
 pub use circuit::*;
 
+pub mod miner;
+pub use miner::*;
+
+pub mod pool;
+pub use pool::*;
+
 mod posw;
 pub(crate) use posw::PoSW;
 
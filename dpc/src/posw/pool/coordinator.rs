@@ -0,0 +1,181 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Block, BlockHeader, BlockHeaderMetadata, BlockTemplate, Network, PoSWScheme, PoolJob, PoolShare};
+
+use anyhow::{anyhow, Result};
+use std::collections::{HashMap, HashSet};
+
+///
+/// A mining pool coordinator, which assigns jobs for a block template to workers,
+/// validates and credits the shares they submit, and detects when a share solves the block.
+///
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"), Default(bound = "N: Network"))]
+pub struct PoolCoordinator<N: Network> {
+    /// The block template and job currently assigned to workers, if any.
+    current: Option<(BlockTemplate<N>, PoolJob<N>)>,
+    /// The ID of the next job.
+    next_job_id: u64,
+    /// The nonces of the shares submitted for the current job.
+    nonces: HashSet<N::PoSWNonce>,
+    /// The number of shares credited to each worker, across all jobs.
+    credits: HashMap<String, u64>,
+    /// The latest block solved by a share, if it was not yet taken.
+    solved_block: Option<Block<N>>,
+}
+
+impl<N: Network> PoolCoordinator<N> {
+    /// Initializes a new instance of a pool coordinator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Assigns a new job for the given block template to workers, with the given share target,
+    /// which must be easier than (greater than or equal to) the block difficulty target.
+    /// Shares for previous jobs are rejected from now on.
+    ///
+    pub fn new_job(&mut self, block_template: &BlockTemplate<N>, share_target: u64) -> Result<PoolJob<N>> {
+        let job = PoolJob::new(self.next_job_id, block_template, share_target)?;
+
+        self.next_job_id += 1;
+        self.current = Some((block_template.clone(), job.clone()));
+        self.nonces.clear();
+
+        Ok(job)
+    }
+
+    /// Returns the job currently assigned to workers, if any.
+    pub fn job(&self) -> Option<&PoolJob<N>> {
+        self.current.as_ref().map(|(_, job)| job)
+    }
+
+    /// Returns the number of shares credited to the given worker.
+    pub fn credits(&self, worker_id: &str) -> u64 {
+        self.credits.get(worker_id).copied().unwrap_or(0)
+    }
+
+    /// Returns the number of shares credited to each worker.
+    pub fn to_credits(&self) -> &HashMap<String, u64> {
+        &self.credits
+    }
+
+    /// Returns the latest block solved by a share, if any, and removes it from the coordinator.
+    pub fn take_solved_block(&mut self) -> Option<Block<N>> {
+        self.solved_block.take()
+    }
+
+    ///
+    /// Validates the given share from the given worker, and credits it to the worker.
+    /// Returns `true` if the share also meets the block difficulty target, in which case the solved block
+    /// is available from `take_solved_block`, and the job is finished until a new job is assigned.
+    ///
+    pub fn submit_share(&mut self, worker_id: &str, share: &PoolShare<N>) -> Result<bool> {
+        let (block_template, job) = match &self.current {
+            Some(current) => current,
+            None => return Err(anyhow!("There is no pool job to submit shares for")),
+        };
+
+        // Ensure the share is for the current job.
+        if share.job_id() != job.job_id() {
+            return Err(anyhow!("The share is for stale job {}", share.job_id()));
+        }
+
+        // Ensure the share was not already submitted.
+        if self.nonces.contains(&share.nonce()) {
+            return Err(anyhow!("The share with nonce {} was already submitted", share.nonce()));
+        }
+
+        // Ensure the share meets the share target.
+        if !N::posw().verify(job.share_target(), &job.to_public_inputs(share.nonce()), share.proof()) {
+            return Err(anyhow!("The share does not meet the share target {}", job.share_target()));
+        }
+
+        self.nonces.insert(share.nonce());
+        *self.credits.entry(worker_id.to_string()).or_insert(0) += 1;
+
+        // Check if the share meets the block difficulty target.
+        if share.proof().to_proof_difficulty()? > job.difficulty_target() {
+            return Ok(false);
+        }
+
+        // Construct the solved block.
+        let block_header = BlockHeader::from(
+            block_template.previous_ledger_root(),
+            block_template.transactions().transactions_root(),
+            BlockHeaderMetadata::new(block_template),
+            share.nonce(),
+            share.proof().clone(),
+        )?;
+        let block =
+            Block::from(block_template.previous_block_hash(), block_header, block_template.transactions().clone())?;
+
+        self.solved_block = Some(block);
+        self.current = None;
+        self.nonces.clear();
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testnet2::Testnet2, Account, Ledger, PoolWorker};
+
+    use rand::thread_rng;
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn test_submit_share() {
+        let rng = &mut thread_rng();
+
+        // Construct a block template with an unreachable difficulty target.
+        let ledger = Ledger::<Testnet2>::new().unwrap();
+        let recipient = Account::<Testnet2>::new(rng);
+        let template = ledger.get_block_template(recipient.address(), true, rng).unwrap();
+        let template = BlockTemplate::new(
+            template.previous_block_hash(),
+            template.block_height(),
+            template.block_timestamp(),
+            0,
+            template.cumulative_weight(),
+            template.previous_ledger_root(),
+            template.transactions().clone(),
+            template.coinbase_record().clone(),
+        );
+
+        // Ensure a valid share is credited, but does not solve the block.
+        let mut coordinator = PoolCoordinator::<Testnet2>::new();
+        let job = coordinator.new_job(&template, u64::MAX).unwrap();
+        let share = PoolWorker::mine_share(&job, &AtomicBool::new(false), rng).unwrap();
+        assert!(PoolCoordinator::<Testnet2>::new().submit_share("worker", &share).is_err());
+        assert!(!coordinator.submit_share("worker", &share).unwrap());
+        assert_eq!(1, coordinator.credits("worker"));
+        assert!(coordinator.take_solved_block().is_none());
+
+        // Ensure duplicate shares and shares with an invalid nonce are rejected.
+        assert!(coordinator.submit_share("worker", &share).is_err());
+        let invalid_share = PoolShare::new(job.job_id(), Default::default(), share.proof().clone());
+        assert!(coordinator.submit_share("worker", &invalid_share).is_err());
+
+        // Ensure shares for a stale job are rejected.
+        coordinator.new_job(&template, u64::MAX).unwrap();
+        assert!(coordinator.submit_share("worker", &share).is_err());
+        assert_eq!(1, coordinator.credits("worker"));
+    }
+}
@@ -0,0 +1,340 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{posw::PoSWCircuit, BlockHeaderMetadata, BlockTemplate, Network, PoSWProof};
+use snarkvm_utilities::{error, FromBytes, ToBytes};
+
+use anyhow::{anyhow, Result};
+use std::io::{Read, Result as IoResult, Write};
+
+/// The maximum length (in bytes) of a string in a pool message.
+const MAXIMUM_STRING_LENGTH: u16 = 1024;
+
+///
+/// A mining job for the workers of a pool. The job commits to the block header root of a block template,
+/// and includes the leaves of the block header tree, so workers may compute PoSW proofs for it.
+///
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "N: Network"),
+    Debug(bound = "N: Network"),
+    PartialEq(bound = "N: Network"),
+    Eq(bound = "N: Network")
+)]
+pub struct PoolJob<N: Network> {
+    /// The ID of the job, which is unique to the coordinator.
+    job_id: u64,
+    /// The block header root of the block template.
+    block_header_root: N::BlockHeaderRoot,
+    /// The previous ledger root of the block template.
+    previous_ledger_root: N::LedgerRoot,
+    /// The transactions root of the block template.
+    transactions_root: N::TransactionsRoot,
+    /// The block header metadata of the block template.
+    metadata: BlockHeaderMetadata,
+    /// The difficulty target for shares, which is easier than (greater than or equal to) the block difficulty target.
+    share_target: u64,
+}
+
+impl<N: Network> PoolJob<N> {
+    /// Initializes a new instance of a pool job for the given block template.
+    pub fn new(job_id: u64, block_template: &BlockTemplate<N>, share_target: u64) -> Result<Self> {
+        // Ensure the share target is not harder than the block difficulty target.
+        if share_target < block_template.difficulty_target() {
+            return Err(anyhow!(
+                "The share target {} is harder than the block difficulty target {}",
+                share_target,
+                block_template.difficulty_target()
+            ));
+        }
+
+        Ok(Self {
+            job_id,
+            block_header_root: block_template.to_header_root()?,
+            previous_ledger_root: block_template.previous_ledger_root(),
+            transactions_root: block_template.transactions().transactions_root(),
+            metadata: BlockHeaderMetadata::new(block_template),
+            share_target,
+        })
+    }
+
+    /// Returns the ID of the job.
+    pub fn job_id(&self) -> u64 {
+        self.job_id
+    }
+
+    /// Returns the block header root of the block template.
+    pub fn block_header_root(&self) -> N::BlockHeaderRoot {
+        self.block_header_root
+    }
+
+    /// Returns the block height of the block template.
+    pub fn block_height(&self) -> u32 {
+        self.metadata.height()
+    }
+
+    /// Returns the block difficulty target of the block template.
+    pub fn difficulty_target(&self) -> u64 {
+        self.metadata.difficulty_target()
+    }
+
+    /// Returns the difficulty target for shares.
+    pub fn share_target(&self) -> u64 {
+        self.share_target
+    }
+
+    /// Returns the public inputs of a PoSW proof for the job, with the given nonce.
+    pub fn to_public_inputs(&self, nonce: N::PoSWNonce) -> Vec<N::InnerScalarField> {
+        vec![*self.block_header_root, *nonce]
+    }
+
+    /// Returns the PoSW circuit for the job, with the given nonce.
+    pub fn to_circuit(&self, nonce: N::PoSWNonce) -> Result<PoSWCircuit<N>> {
+        let tree = BlockTemplate::<N>::compute_block_header_tree(
+            self.previous_ledger_root,
+            self.transactions_root,
+            &self.metadata,
+        )?;
+
+        // Ensure the block header tree matches the block header root of the job.
+        let block_header_root: N::BlockHeaderRoot = (*tree.root()).into();
+        match block_header_root == self.block_header_root {
+            true => Ok(PoSWCircuit::from_header_tree(&tree, nonce)),
+            false => Err(anyhow!("The pool job {} does not match its block header root", self.job_id)),
+        }
+    }
+}
+
+impl<N: Network> FromBytes for PoolJob<N> {
+    #[inline]
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let job_id = FromBytes::read_le(&mut reader)?;
+        let block_header_root = FromBytes::read_le(&mut reader)?;
+        let previous_ledger_root = FromBytes::read_le(&mut reader)?;
+        let transactions_root = FromBytes::read_le(&mut reader)?;
+        let metadata = FromBytes::read_le(&mut reader)?;
+        let share_target = FromBytes::read_le(&mut reader)?;
+
+        Ok(Self { job_id, block_header_root, previous_ledger_root, transactions_root, metadata, share_target })
+    }
+}
+
+impl<N: Network> ToBytes for PoolJob<N> {
+    #[inline]
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.job_id.write_le(&mut writer)?;
+        self.block_header_root.write_le(&mut writer)?;
+        self.previous_ledger_root.write_le(&mut writer)?;
+        self.transactions_root.write_le(&mut writer)?;
+        self.metadata.write_le(&mut writer)?;
+        self.share_target.write_le(&mut writer)
+    }
+}
+
+/// A share submitted by a pool worker, which is a PoSW proof for a job that meets its share target.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "N: Network"),
+    Debug(bound = "N: Network"),
+    PartialEq(bound = "N: Network"),
+    Eq(bound = "N: Network")
+)]
+pub struct PoolShare<N: Network> {
+    /// The ID of the job.
+    job_id: u64,
+    /// The nonce of the PoSW proof.
+    nonce: N::PoSWNonce,
+    /// The PoSW proof.
+    proof: PoSWProof<N>,
+}
+
+impl<N: Network> PoolShare<N> {
+    /// Initializes a new instance of a pool share.
+    pub fn new(job_id: u64, nonce: N::PoSWNonce, proof: PoSWProof<N>) -> Self {
+        Self { job_id, nonce, proof }
+    }
+
+    /// Returns the ID of the job.
+    pub fn job_id(&self) -> u64 {
+        self.job_id
+    }
+
+    /// Returns the nonce of the PoSW proof.
+    pub fn nonce(&self) -> N::PoSWNonce {
+        self.nonce
+    }
+
+    /// Returns a reference to the PoSW proof.
+    pub fn proof(&self) -> &PoSWProof<N> {
+        &self.proof
+    }
+}
+
+impl<N: Network> FromBytes for PoolShare<N> {
+    #[inline]
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let job_id = FromBytes::read_le(&mut reader)?;
+        let nonce = FromBytes::read_le(&mut reader)?;
+        let proof = FromBytes::read_le(&mut reader)?;
+
+        Ok(Self { job_id, nonce, proof })
+    }
+}
+
+impl<N: Network> ToBytes for PoolShare<N> {
+    #[inline]
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.job_id.write_le(&mut writer)?;
+        self.nonce.write_le(&mut writer)?;
+        self.proof.write_le(&mut writer)
+    }
+}
+
+/// A message between a pool coordinator and a pool worker.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "N: Network"),
+    Debug(bound = "N: Network"),
+    PartialEq(bound = "N: Network"),
+    Eq(bound = "N: Network")
+)]
+pub enum PoolMessage<N: Network> {
+    /// Subscribes a worker to the pool, with the given worker ID.
+    Subscribe(String),
+    /// Assigns the given job to a worker.
+    Job(PoolJob<N>),
+    /// Submits the given share to the coordinator.
+    Submit(PoolShare<N>),
+    /// Accepts a share, indicating whether the share solved the block.
+    Accepted(bool),
+    /// Rejects a share, with the given reason.
+    Rejected(String),
+}
+
+impl<N: Network> PoolMessage<N> {
+    /// Returns the id of the message.
+    pub fn id(&self) -> u8 {
+        match self {
+            Self::Subscribe(..) => 0,
+            Self::Job(..) => 1,
+            Self::Submit(..) => 2,
+            Self::Accepted(..) => 3,
+            Self::Rejected(..) => 4,
+        }
+    }
+
+    /// Reads a string from the given reader.
+    fn read_string<R: Read>(mut reader: R) -> IoResult<String> {
+        let length: u16 = FromBytes::read_le(&mut reader)?;
+        if length > MAXIMUM_STRING_LENGTH {
+            return Err(error("The pool message string exceeds the maximum length"));
+        }
+
+        let mut bytes = vec![0u8; length as usize];
+        reader.read_exact(&mut bytes)?;
+        String::from_utf8(bytes).map_err(|_| error("Invalid string in pool message"))
+    }
+
+    /// Writes the given string to the given writer.
+    fn write_string<W: Write>(string: &str, mut writer: W) -> IoResult<()> {
+        if string.len() > MAXIMUM_STRING_LENGTH as usize {
+            return Err(error("The pool message string exceeds the maximum length"));
+        }
+
+        (string.len() as u16).write_le(&mut writer)?;
+        writer.write_all(string.as_bytes())
+    }
+}
+
+impl<N: Network> FromBytes for PoolMessage<N> {
+    #[inline]
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let id: u8 = FromBytes::read_le(&mut reader)?;
+        Ok(match id {
+            0 => Self::Subscribe(Self::read_string(&mut reader)?),
+            1 => Self::Job(FromBytes::read_le(&mut reader)?),
+            2 => Self::Submit(FromBytes::read_le(&mut reader)?),
+            3 => Self::Accepted(FromBytes::read_le(&mut reader)?),
+            4 => Self::Rejected(Self::read_string(&mut reader)?),
+            _ => return Err(error("Invalid pool message id")),
+        })
+    }
+}
+
+impl<N: Network> ToBytes for PoolMessage<N> {
+    #[inline]
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.id().write_le(&mut writer)?;
+        match self {
+            Self::Subscribe(worker_id) => Self::write_string(worker_id, &mut writer),
+            Self::Job(job) => job.write_le(&mut writer),
+            Self::Submit(share) => share.write_le(&mut writer),
+            Self::Accepted(is_block) => is_block.write_le(&mut writer),
+            Self::Rejected(reason) => Self::write_string(reason, &mut writer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    #[test]
+    fn test_pool_message_serialization() {
+        let block = Testnet2::genesis_block();
+        let block_template = BlockTemplate::new(
+            block.previous_block_hash(),
+            block.height(),
+            block.timestamp(),
+            block.difficulty_target(),
+            block.cumulative_weight(),
+            block.previous_ledger_root(),
+            block.transactions().clone(),
+            block.to_coinbase_transaction().unwrap().to_records().next().unwrap(),
+        );
+        let job = PoolJob::new(7, &block_template, u64::MAX).unwrap();
+        assert_eq!(block.height(), job.block_height());
+        assert_eq!(block.header().to_header_root().unwrap(), job.block_header_root());
+        assert!(job.to_circuit(block.header().nonce()).is_ok());
+
+        let share = PoolShare::new(7, block.header().nonce(), block.header().proof().clone());
+        let messages = vec![
+            PoolMessage::<Testnet2>::Subscribe("worker".to_string()),
+            PoolMessage::Job(job),
+            PoolMessage::Submit(share),
+            PoolMessage::Accepted(true),
+            PoolMessage::Rejected("stale job".to_string()),
+        ];
+        for message in messages {
+            let bytes = message.to_bytes_le().unwrap();
+            assert_eq!(message, PoolMessage::read_le(&bytes[..]).unwrap());
+        }
+
+        // Ensure the share target may not be harder than the block difficulty target.
+        let block_template = BlockTemplate::new(
+            block.previous_block_hash(),
+            block.height(),
+            block.timestamp(),
+            1000,
+            block.cumulative_weight(),
+            block.previous_ledger_root(),
+            block.transactions().clone(),
+            block.to_coinbase_transaction().unwrap().to_records().next().unwrap(),
+        );
+        assert!(PoolJob::new(0, &block_template, 999).is_err());
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Network, PoolCoordinator, PoolMessage};
+use snarkvm_utilities::{FromBytes, ToBytes};
+
+use anyhow::{anyhow, Result};
+use std::{
+    io::{ErrorKind, Read, Write},
+    marker::PhantomData,
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+};
+
+/// The maximum size (in bytes) of a pool message.
+const MAXIMUM_MESSAGE_SIZE: u32 = 64 * 1024;
+
+///
+/// A TCP connection between a pool coordinator and a pool worker.
+/// Each message is framed by its size in bytes, as a little-endian `u32`.
+///
+pub struct PoolConnection<N: Network> {
+    /// The TCP stream.
+    stream: TcpStream,
+    _network: PhantomData<N>,
+}
+
+impl<N: Network> PoolConnection<N> {
+    /// Connects to the given address.
+    pub fn connect<A: ToSocketAddrs>(address: A) -> Result<Self> {
+        Ok(Self::from(TcpStream::connect(address)?))
+    }
+
+    /// Returns the address of the peer.
+    pub fn peer_addr(&self) -> Result<SocketAddr> {
+        Ok(self.stream.peer_addr()?)
+    }
+
+    /// Sends the given message.
+    pub fn send(&mut self, message: &PoolMessage<N>) -> Result<()> {
+        let bytes = message.to_bytes_le()?;
+        if bytes.len() > MAXIMUM_MESSAGE_SIZE as usize {
+            return Err(anyhow!("The pool message of {} bytes exceeds the maximum size", bytes.len()));
+        }
+
+        (bytes.len() as u32).write_le(&mut self.stream)?;
+        self.stream.write_all(&bytes)?;
+        Ok(self.stream.flush()?)
+    }
+
+    /// Receives the next message, blocking until it arrives.
+    pub fn receive(&mut self) -> Result<PoolMessage<N>> {
+        let size = u32::read_le(&mut self.stream)?;
+        if size > MAXIMUM_MESSAGE_SIZE {
+            return Err(anyhow!("The pool message of {} bytes exceeds the maximum size", size));
+        }
+
+        let mut bytes = vec![0u8; size as usize];
+        self.stream.read_exact(&mut bytes)?;
+        Ok(PoolMessage::read_le(&bytes[..])?)
+    }
+}
+
+impl<N: Network> From<TcpStream> for PoolConnection<N> {
+    fn from(stream: TcpStream) -> Self {
+        Self { stream, _network: PhantomData }
+    }
+}
+
+///
+/// A TCP server for a pool coordinator, which serves each worker connection on its own thread.
+/// Workers are assigned the current job when they subscribe, and a new job in response to
+/// their next share, once the coordinator assigns one.
+///
+pub struct PoolServer<N: Network> {
+    /// The TCP listener.
+    listener: TcpListener,
+    /// The pool coordinator.
+    coordinator: Arc<Mutex<PoolCoordinator<N>>>,
+}
+
+impl<N: Network> PoolServer<N> {
+    /// Binds a pool server for the given coordinator to the given address.
+    pub fn bind<A: ToSocketAddrs>(address: A, coordinator: Arc<Mutex<PoolCoordinator<N>>>) -> Result<Self> {
+        Ok(Self { listener: TcpListener::bind(address)?, coordinator })
+    }
+
+    /// Returns the local address of the server.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Accepts the next worker connection, and serves it on a new thread until the worker disconnects.
+    pub fn accept(&self) -> Result<JoinHandle<Result<()>>> {
+        let (stream, _) = self.listener.accept()?;
+        let coordinator = self.coordinator.clone();
+        Ok(thread::spawn(move || Self::serve(&coordinator, PoolConnection::from(stream))))
+    }
+
+    /// Serves the given worker connection, until the worker disconnects.
+    fn serve(coordinator: &Mutex<PoolCoordinator<N>>, mut connection: PoolConnection<N>) -> Result<()> {
+        let lock = || coordinator.lock().map_err(|_| anyhow!("The pool coordinator is poisoned"));
+
+        // Ensure the worker subscribes, and assign it the current job.
+        let worker_id = match connection.receive()? {
+            PoolMessage::Subscribe(worker_id) => worker_id,
+            message => {
+                connection.send(&PoolMessage::Rejected("The worker must subscribe first".to_string()))?;
+                return Err(anyhow!("Unexpected pool message {} from {}", message.id(), connection.peer_addr()?));
+            }
+        };
+        let mut job = match lock()?.job().cloned() {
+            Some(job) => job,
+            None => return connection.send(&PoolMessage::Rejected("There is no pool job".to_string())),
+        };
+        connection.send(&PoolMessage::Job(job.clone()))?;
+
+        loop {
+            let message = match connection.receive() {
+                Ok(message) => message,
+                // The worker disconnected.
+                Err(error) if Self::is_disconnected(&error) => return Ok(()),
+                Err(error) => return Err(error),
+            };
+
+            match message {
+                PoolMessage::Submit(share) => {
+                    let (result, current_job) = {
+                        let mut coordinator = lock()?;
+                        (coordinator.submit_share(&worker_id, &share), coordinator.job().cloned())
+                    };
+
+                    // Assign the current job to the worker, if it changed.
+                    if let Some(current_job) = current_job {
+                        if current_job != job {
+                            job = current_job;
+                            connection.send(&PoolMessage::Job(job.clone()))?;
+                        }
+                    }
+
+                    match result {
+                        Ok(is_block) => connection.send(&PoolMessage::Accepted(is_block))?,
+                        Err(error) => connection.send(&PoolMessage::Rejected(error.to_string()))?,
+                    }
+                }
+                message => {
+                    connection.send(&PoolMessage::Rejected(format!("Unexpected pool message {}", message.id())))?
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the given error is due to the peer closing the connection.
+    fn is_disconnected(error: &anyhow::Error) -> bool {
+        error.downcast_ref::<std::io::Error>().map_or(false, |error| {
+            matches!(error.kind(), ErrorKind::UnexpectedEof | ErrorKind::ConnectionReset | ErrorKind::BrokenPipe)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testnet2::Testnet2, Account, Ledger, PoolWorker};
+
+    use rand::thread_rng;
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn test_pool_on_loopback() {
+        let rng = &mut thread_rng();
+
+        let ledger = Ledger::<Testnet2>::new().unwrap();
+        let recipient = Account::<Testnet2>::new(rng);
+        let template = ledger.get_block_template(recipient.address(), true, rng).unwrap();
+
+        let coordinator = Arc::new(Mutex::new(PoolCoordinator::<Testnet2>::new()));
+        let job = coordinator.lock().unwrap().new_job(&template, u64::MAX).unwrap();
+
+        let server = PoolServer::bind("127.0.0.1:0", coordinator.clone()).unwrap();
+        let address = server.local_addr().unwrap();
+        let server_thread = thread::spawn(move || server.accept().unwrap().join().unwrap());
+
+        // Ensure the worker is assigned the current job, and its share is credited.
+        let mut worker = PoolWorker::<Testnet2>::connect(address, "worker").unwrap();
+        assert_eq!(&job, worker.job());
+
+        let share = PoolWorker::mine_share(worker.job(), &AtomicBool::new(false), rng).unwrap();
+        let is_block = worker.submit_share(&share).unwrap();
+        assert_eq!(1, coordinator.lock().unwrap().credits("worker"));
+
+        // Ensure a share that solves the block yields a valid block.
+        match coordinator.lock().unwrap().take_solved_block() {
+            Some(block) => {
+                assert!(is_block);
+                assert!(block.is_valid());
+                assert_eq!(template.block_height(), block.height());
+            }
+            None => assert!(!is_block),
+        }
+
+        // Ensure a resubmitted share is rejected.
+        assert!(worker.submit_share(&share).is_err());
+        assert_eq!(1, coordinator.lock().unwrap().credits("worker"));
+
+        // Ensure the server stops serving the worker once it disconnects.
+        drop(worker);
+        server_thread.join().unwrap().unwrap();
+    }
+}
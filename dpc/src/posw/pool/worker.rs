@@ -0,0 +1,99 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Network, PoSWScheme, PoolConnection, PoolJob, PoolMessage, PoolShare};
+use snarkvm_utilities::UniformRand;
+
+use anyhow::{anyhow, Result};
+use core::sync::atomic::AtomicBool;
+use rand::{CryptoRng, Rng};
+use std::net::ToSocketAddrs;
+
+///
+/// A mining pool worker, which is subscribed to a pool coordinator,
+/// and submits shares for the job it is assigned.
+///
+pub struct PoolWorker<N: Network> {
+    /// The ID of the worker, which the coordinator credits shares to.
+    worker_id: String,
+    /// The connection to the pool coordinator.
+    connection: PoolConnection<N>,
+    /// The job currently assigned to the worker.
+    job: PoolJob<N>,
+}
+
+impl<N: Network> PoolWorker<N> {
+    /// Connects to the pool coordinator at the given address, and subscribes with the given worker ID.
+    pub fn connect<A: ToSocketAddrs>(address: A, worker_id: &str) -> Result<Self> {
+        let mut connection = PoolConnection::connect(address)?;
+        connection.send(&PoolMessage::Subscribe(worker_id.to_string()))?;
+
+        match connection.receive()? {
+            PoolMessage::Job(job) => Ok(Self { worker_id: worker_id.to_string(), connection, job }),
+            PoolMessage::Rejected(reason) => Err(anyhow!("The pool rejected the worker: {}", reason)),
+            message => Err(anyhow!("Unexpected pool message {}", message.id())),
+        }
+    }
+
+    /// Returns the ID of the worker.
+    pub fn worker_id(&self) -> &str {
+        &self.worker_id
+    }
+
+    /// Returns the job currently assigned to the worker.
+    pub fn job(&self) -> &PoolJob<N> {
+        &self.job
+    }
+
+    ///
+    /// Submits the given share to the pool coordinator, and returns `true` if the share solved the block.
+    /// If the coordinator assigned a new job, the job of the worker is updated.
+    ///
+    pub fn submit_share(&mut self, share: &PoolShare<N>) -> Result<bool> {
+        self.connection.send(&PoolMessage::Submit(share.clone()))?;
+
+        loop {
+            match self.connection.receive()? {
+                PoolMessage::Job(job) => self.job = job,
+                PoolMessage::Accepted(is_block) => return Ok(is_block),
+                PoolMessage::Rejected(reason) => return Err(anyhow!("The pool rejected the share: {}", reason)),
+                message => return Err(anyhow!("Unexpected pool message {}", message.id())),
+            }
+        }
+    }
+
+    ///
+    /// Computes PoSW proofs for the given job until one meets its share target, and returns it as a share.
+    ///
+    pub fn mine_share<R: Rng + CryptoRng>(
+        job: &PoolJob<N>,
+        terminator: &AtomicBool,
+        rng: &mut R,
+    ) -> Result<PoolShare<N>> {
+        // Instantiate the circuit.
+        let mut circuit = job.to_circuit(UniformRand::rand(rng))?;
+
+        loop {
+            // Run one iteration of PoSW.
+            let proof = N::posw().prove_once_unchecked(&mut circuit, terminator, rng)?;
+
+            // Check if the proof meets the share target.
+            if N::posw().verify(job.share_target(), &circuit.to_public_inputs(), &proof) {
+                return Ok(PoolShare::new(job.job_id(), circuit.nonce(), proof));
+            }
+        }
+    }
+}
@@ -17,9 +17,8 @@
 //! Generic PoSW Miner and Verifier, compatible with any implementer of the SNARK trait.
 
 use crate::{
-    posw::PoSWCircuit,
+    posw::{Miner, PoSWCircuit},
     BlockHeader,
-    BlockHeaderMetadata,
     BlockTemplate,
     Network,
     PoSWError,
@@ -31,7 +30,6 @@ use snarkvm_utilities::UniformRand;
 
 use core::sync::atomic::AtomicBool;
 use rand::{CryptoRng, Rng};
-use time::OffsetDateTime;
 
 /// A Proof of Succinct Work miner and verifier.
 #[derive(Clone)]
@@ -92,39 +90,7 @@ impl<N: Network> PoSWScheme<N> for PoSW<N> {
         terminator: &AtomicBool,
         rng: &mut R,
     ) -> Result<BlockHeader<N>, PoSWError> {
-        const MAXIMUM_MINING_DURATION: i64 = 600; // 600 seconds = 10 minutes.
-
-        // Instantiate the circuit.
-        let mut circuit = PoSWCircuit::<N>::new(block_template, UniformRand::rand(rng))?;
-
-        let mut iteration = 1;
-        loop {
-            // Every 100 iterations, check that the miner is still within the allowed mining duration.
-            if iteration % 100 == 0
-                && OffsetDateTime::now_utc().unix_timestamp()
-                    >= block_template.block_timestamp() + MAXIMUM_MINING_DURATION
-            {
-                return Err(PoSWError::Message("Failed mine block in the allowed mining duration".to_string()));
-            }
-
-            // Run one iteration of PoSW.
-            let proof = self.prove_once_unchecked(&mut circuit, terminator, rng)?;
-
-            // Check if the updated block header is valid.
-            if self.verify(block_template.difficulty_target(), &circuit.to_public_inputs(), &proof) {
-                // Construct a block header.
-                return Ok(BlockHeader::from(
-                    block_template.previous_ledger_root(),
-                    block_template.transactions().transactions_root(),
-                    BlockHeaderMetadata::new(block_template),
-                    circuit.nonce(),
-                    proof,
-                )?);
-            }
-
-            // Increment the iteration by one.
-            iteration += 1;
-        }
+        Miner::new(1).mine(self, block_template, terminator, |_| (), rng)
     }
 
     ///
@@ -0,0 +1,177 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Event, Network, ProvedTransaction, Record, Request, VirtualMachine};
+use snarkvm_utilities::{FromBytes, ToBytes};
+
+use anyhow::{anyhow, Result};
+use std::io::{Error, ErrorKind, Read, Result as IoResult, Write};
+
+///
+/// An authorization for an untrusted prover to produce a transaction on behalf of the caller.
+/// It consists of a request signed by the caller, which includes the ledger proofs for its
+/// input records, and the ledger root those ledger proofs are for.
+///
+/// An authorization does not contain the private key of the caller, and the caller verifies
+/// the proved transaction matches the authorization before broadcasting it.
+///
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"))]
+pub struct Authorization<N: Network> {
+    /// The root of the ledger tree used to prove inclusion of the input records.
+    ledger_root: N::LedgerRoot,
+    /// The signed request.
+    request: Request<N>,
+}
+
+impl<N: Network> Authorization<N> {
+    /// Returns a new instance of an authorization, for the given ledger root and signed request.
+    pub fn new(ledger_root: N::LedgerRoot, request: Request<N>) -> Result<Self> {
+        // Ensure the request is valid.
+        if !request.is_valid() {
+            return Err(anyhow!("Authorization contains an invalid request"));
+        }
+
+        // Ensure the ledger proofs for the input records are for the given ledger root.
+        for (i, (record, ledger_proof)) in request.records().iter().zip(request.ledger_proofs()).enumerate() {
+            if !record.is_dummy() && ledger_proof.ledger_root() != ledger_root {
+                return Err(anyhow!("Ledger proof for input record {} is not for ledger root {}", i, ledger_root));
+            }
+        }
+
+        Ok(Self { ledger_root, request })
+    }
+
+    /// Returns the ledger root.
+    pub fn ledger_root(&self) -> N::LedgerRoot {
+        self.ledger_root
+    }
+
+    /// Returns a reference to the signed request.
+    pub fn request(&self) -> &Request<N> {
+        &self.request
+    }
+
+    ///
+    /// Returns `Ok(())` if the given proved transaction executes the request in this authorization.
+    /// The transaction must be valid, consume the input records of the request, pay the fee of the request,
+    /// and produce exactly the output records the request entitles, with the given records as their plaintext.
+    ///
+    pub fn verify(&self, proved_transaction: &ProvedTransaction<N>) -> Result<()> {
+        let transaction = proved_transaction.transaction();
+        let records = proved_transaction.records();
+
+        // Ensure the transaction is valid.
+        if !transaction.is_valid() {
+            return Err(anyhow!("Proved transaction {} is invalid", transaction.transaction_id()));
+        }
+
+        // Ensure the transaction is for the ledger root of the authorization.
+        if transaction.ledger_root() != self.ledger_root {
+            return Err(anyhow!(
+                "Proved transaction is for ledger root {}, expected {}",
+                transaction.ledger_root(),
+                self.ledger_root
+            ));
+        }
+
+        // Ensure the transaction contains exactly one transition.
+        let transition = match &transaction.transitions()[..] {
+            [transition] => transition,
+            transitions => {
+                return Err(anyhow!("Proved transaction contains {} transitions, expected 1", transitions.len()))
+            }
+        };
+
+        // Ensure the transition consumes the input records of the request.
+        let serial_numbers = self.request.to_serial_numbers()?;
+        if !transition.serial_numbers().eq(serial_numbers.iter()) {
+            return Err(anyhow!("Proved transaction does not consume the input records of the request"));
+        }
+
+        // Ensure the given records are the plaintext of the output records in the transition.
+        if records.len() != N::NUM_OUTPUT_RECORDS {
+            return Err(anyhow!("Expected {} output records, found {}", N::NUM_OUTPUT_RECORDS, records.len()));
+        }
+        for (i, (record, ciphertext)) in records.iter().zip(transition.ciphertexts()).enumerate() {
+            if record.ciphertext() != ciphertext {
+                return Err(anyhow!("Output record {} does not match its ciphertext in the transition", i));
+            }
+        }
+
+        // Ensure the output records are exactly the ones the virtual machine produces for the request.
+        // The response is built with fresh randomness, so only the plaintext of its records is compared.
+        let response =
+            VirtualMachine::<N>::new(self.ledger_root)?.to_response(&self.request, &mut rand::thread_rng())?;
+        let to_plaintexts = |records: &[Record<N>]| -> Vec<_> {
+            records
+                .iter()
+                .filter(|record| !record.is_dummy())
+                .map(|record| (record.owner(), record.value(), record.payload().clone(), record.program_id()))
+                .collect()
+        };
+        if to_plaintexts(records) != to_plaintexts(response.records()) {
+            return Err(anyhow!("Proved transaction does not produce the output records of the request"));
+        }
+
+        // Ensure the value balance is the fee of the request.
+        if *transition.value_balance() != response.value_balance() {
+            return Err(anyhow!(
+                "Proved transaction has a value balance of {}, expected {}",
+                transition.value_balance(),
+                response.value_balance()
+            ));
+        }
+
+        // Ensure the transition only emits events for the request operation and its output records.
+        for event in transition.events() {
+            match event {
+                Event::Operation(operation) if operation == self.request.operation() => continue,
+                Event::RecordViewKey(i, record_view_key)
+                    if records.get(*i as usize).map(|record| record.record_view_key()) == Some(record_view_key) =>
+                {
+                    continue
+                }
+                _ => return Err(anyhow!("Proved transaction emits an event that is not in the request")),
+            }
+        }
+
+        // Ensure the deployment in the request is published.
+        if self.request.operation().is_deploy() && !transaction.is_deployment() {
+            return Err(anyhow!("Proved transaction does not publish the deployment of the request"));
+        }
+
+        Ok(())
+    }
+}
+
+impl<N: Network> FromBytes for Authorization<N> {
+    #[inline]
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let ledger_root = FromBytes::read_le(&mut reader)?;
+        let request = FromBytes::read_le(&mut reader)?;
+
+        Self::new(ledger_root, request).map_err(|error| Error::new(ErrorKind::Other, format!("{}", error)))
+    }
+}
+
+impl<N: Network> ToBytes for Authorization<N> {
+    #[inline]
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.ledger_root.write_le(&mut writer)?;
+        self.request.write_le(&mut writer)
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Network, Record, Transaction};
+use snarkvm_utilities::{FromBytes, ToBytes};
+
+use std::io::{Read, Result as IoResult, Write};
+
+///
+/// A transaction produced by a prover for an authorization, along with the plaintext of its output records.
+/// The output records allow the caller to verify the transaction, and to spend its change.
+///
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"))]
+pub struct ProvedTransaction<N: Network> {
+    /// The proved transaction.
+    transaction: Transaction<N>,
+    /// The output records of the transaction.
+    records: Vec<Record<N>>,
+}
+
+impl<N: Network> ProvedTransaction<N> {
+    /// Returns a new instance of a proved transaction.
+    pub fn new(transaction: Transaction<N>, records: Vec<Record<N>>) -> Self {
+        Self { transaction, records }
+    }
+
+    /// Returns a reference to the transaction.
+    pub fn transaction(&self) -> &Transaction<N> {
+        &self.transaction
+    }
+
+    /// Returns a reference to the output records.
+    pub fn records(&self) -> &Vec<Record<N>> {
+        &self.records
+    }
+}
+
+impl<N: Network> FromBytes for ProvedTransaction<N> {
+    #[inline]
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let transaction = FromBytes::read_le(&mut reader)?;
+
+        let mut records = Vec::with_capacity(N::NUM_OUTPUT_RECORDS);
+        for _ in 0..N::NUM_OUTPUT_RECORDS {
+            records.push(FromBytes::read_le(&mut reader)?);
+        }
+
+        Ok(Self { transaction, records })
+    }
+}
+
+impl<N: Network> ToBytes for ProvedTransaction<N> {
+    #[inline]
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.transaction.write_le(&mut writer)?;
+        self.records.write_le(&mut writer)
+    }
+}
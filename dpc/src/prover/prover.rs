@@ -0,0 +1,106 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Authorization, Network, ProvedTransaction, VirtualMachine};
+
+use anyhow::Result;
+use rand::{CryptoRng, Rng};
+use std::marker::PhantomData;
+
+///
+/// A prover that produces transactions for authorizations, without the private keys of their callers.
+/// The prover is untrusted, and callers verify each proved transaction against their authorization.
+///
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"), Default(bound = "N: Network"))]
+pub struct Prover<N: Network> {
+    _network: PhantomData<N>,
+}
+
+impl<N: Network> Prover<N> {
+    /// Initializes a new instance of a prover.
+    pub fn new() -> Self {
+        Self { _network: PhantomData }
+    }
+
+    /// Executes the request in the given authorization, and returns the proved transaction.
+    pub fn prove<R: Rng + CryptoRng>(
+        &self,
+        authorization: &Authorization<N>,
+        rng: &mut R,
+    ) -> Result<ProvedTransaction<N>> {
+        let (vm, response) =
+            VirtualMachine::<N>::new(authorization.ledger_root())?.execute(authorization.request(), rng)?;
+        Ok(ProvedTransaction::new(vm.finalize()?, response.records().clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testnet2::Testnet2, Account, AleoAmount, Ledger, LedgerProof, Request};
+
+    use rand::thread_rng;
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn test_delegated_transfer() {
+        let rng = &mut thread_rng();
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let caller = Account::<Testnet2>::new(rng);
+        let recipient = Account::<Testnet2>::new(rng);
+
+        // Sign a transfer request as a light client.
+        let record = ledger.mine_next_block(caller.address(), true, &AtomicBool::new(false), rng).unwrap();
+        let ledger_proof = ledger.to_ledger_proof(record.commitment()).unwrap();
+        let request = Request::new_transfer(
+            caller.private_key(),
+            vec![record],
+            vec![ledger_proof, LedgerProof::default()],
+            recipient.address(),
+            AleoAmount::from_i64(100),
+            AleoAmount::from_i64(10),
+            false,
+            rng,
+        )
+        .unwrap();
+        let authorization = Authorization::new(ledger.latest_ledger_root(), request).unwrap();
+
+        // Ensure the proved transaction matches the authorization, and is accepted by the ledger.
+        let proved_transaction = Prover::new().prove(&authorization, rng).unwrap();
+        assert!(authorization.verify(&proved_transaction).is_ok());
+        assert!(ledger.add_unconfirmed_transaction(proved_transaction.transaction()).is_ok());
+
+        // Ensure the recipient receives the transferred amount.
+        let records = proved_transaction.records();
+        assert!(records
+            .iter()
+            .any(|record| record.owner() == recipient.address() && record.value() == AleoAmount::from_i64(100)));
+
+        // Ensure a proved transaction for a different request is rejected.
+        let request = Request::new_noop(vec![LedgerProof::default(), LedgerProof::default()], rng).unwrap();
+        let other_authorization = Authorization::new(ledger.latest_ledger_root(), request).unwrap();
+        assert!(other_authorization.verify(&proved_transaction).is_err());
+
+        // Ensure output records that do not match the transaction are rejected.
+        let mut records = records.clone();
+        records.reverse();
+        assert!(authorization
+            .verify(&ProvedTransaction::new(proved_transaction.transaction().clone(), records))
+            .is_err());
+    }
+}
@@ -0,0 +1,346 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Authorization, Network, ProvedTransaction, Prover};
+use snarkvm_utilities::{FromBytes, ToBytes};
+
+use anyhow::{anyhow, Result};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    marker::PhantomData,
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Condvar, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// The maximum size (in bytes) of an HTTP body to the prover service.
+const MAXIMUM_BODY_SIZE: usize = 16 * 1024 * 1024;
+/// The maximum size (in bytes) of an HTTP start line or header line.
+const MAXIMUM_LINE_SIZE: usize = 8 * 1024;
+/// The maximum number of HTTP headers in a message.
+const MAXIMUM_HEADERS: usize = 32;
+/// The maximum number of connections the prover service serves concurrently.
+const MAXIMUM_CONNECTIONS: usize = 4;
+/// The timeout for reading or writing an HTTP message.
+const MESSAGE_TIMEOUT: Duration = Duration::from_secs(30);
+/// The timeout for the client to receive the proved transaction, which includes the time to prove it.
+const PROVE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// The path of the endpoint that proves an authorization.
+const PROVE_PATH: &str = "/prove";
+
+///
+/// A local HTTP stand-in for a prover service, which serves each connection on its own thread,
+/// for up to `MAXIMUM_CONNECTIONS` connections at a time. The service accepts `POST /prove` with
+/// an authorization in bytes as the body, and responds with the proved transaction in bytes,
+/// or with the error as plaintext.
+///
+pub struct ProverServer<N: Network> {
+    /// The TCP listener.
+    listener: TcpListener,
+    /// The prover.
+    prover: Prover<N>,
+    /// The number of connections being served.
+    connections: Arc<(Mutex<usize>, Condvar)>,
+}
+
+impl<N: Network> ProverServer<N> {
+    /// Binds a prover server for the given prover to the given address.
+    pub fn bind<A: ToSocketAddrs>(address: A, prover: Prover<N>) -> Result<Self> {
+        Ok(Self { listener: TcpListener::bind(address)?, prover, connections: Default::default() })
+    }
+
+    /// Returns the local address of the server.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    ///
+    /// Accepts the next connection, and serves its HTTP request on a new thread.
+    /// If `MAXIMUM_CONNECTIONS` connections are being served, this waits for one of them to finish first.
+    ///
+    pub fn accept(&self) -> Result<JoinHandle<Result<()>>> {
+        let connection = Connection::acquire(&self.connections)?;
+
+        let (stream, _) = self.listener.accept()?;
+        stream.set_read_timeout(Some(MESSAGE_TIMEOUT))?;
+        stream.set_write_timeout(Some(MESSAGE_TIMEOUT))?;
+
+        let prover = self.prover.clone();
+        Ok(thread::spawn(move || {
+            let result = Self::serve(&prover, stream);
+            drop(connection);
+            result
+        }))
+    }
+
+    /// Serves the HTTP request on the given connection.
+    fn serve(prover: &Prover<N>, mut stream: TcpStream) -> Result<()> {
+        let (method, path, body) = read_message(&mut stream)?;
+
+        let response = match (method.as_str(), path.as_str()) {
+            ("POST", PROVE_PATH) => Authorization::<N>::read_le(&body[..])
+                .map_err(|error| ("400 Bad Request", error.to_string()))
+                .and_then(|authorization| {
+                    prover
+                        .prove(&authorization, &mut rand::thread_rng())
+                        .and_then(|proved_transaction| Ok(proved_transaction.to_bytes_le()?))
+                        .map_err(|error| ("422 Unprocessable Entity", error.to_string()))
+                }),
+            (_, PROVE_PATH) => Err(("405 Method Not Allowed", format!("Unsupported method {}", method))),
+            _ => Err(("404 Not Found", format!("Unknown path {}", path))),
+        };
+
+        match response {
+            Ok(bytes) => write_message(&mut stream, "HTTP/1.1 200 OK", "application/octet-stream", &bytes),
+            Err((status, error)) => {
+                write_message(&mut stream, &format!("HTTP/1.1 {}", status), "text/plain", error.as_bytes())
+            }
+        }
+    }
+}
+
+/// A connection being served, which is released when dropped.
+struct Connection {
+    connections: Arc<(Mutex<usize>, Condvar)>,
+}
+
+impl Connection {
+    /// Waits until fewer than `MAXIMUM_CONNECTIONS` connections are being served, and returns a new one.
+    fn acquire(connections: &Arc<(Mutex<usize>, Condvar)>) -> Result<Self> {
+        let (count, condvar) = &**connections;
+        let mut count = count.lock().map_err(|_| anyhow!("Prover server lock is poisoned"))?;
+        while *count >= MAXIMUM_CONNECTIONS {
+            count = condvar.wait(count).map_err(|_| anyhow!("Prover server lock is poisoned"))?;
+        }
+        *count += 1;
+        Ok(Self { connections: connections.clone() })
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        let (count, condvar) = &*self.connections;
+        if let Ok(mut count) = count.lock() {
+            *count -= 1;
+            condvar.notify_one();
+        }
+    }
+}
+
+///
+/// A client of a prover service over HTTP, which sends an authorization and
+/// returns the proved transaction after verifying it against the authorization.
+///
+pub struct ProverClient<N: Network> {
+    /// The address of the prover service.
+    address: SocketAddr,
+    _network: PhantomData<N>,
+}
+
+impl<N: Network> ProverClient<N> {
+    /// Initializes a client for the prover service at the given address.
+    pub fn new<A: ToSocketAddrs>(address: A) -> Result<Self> {
+        match address.to_socket_addrs()?.next() {
+            Some(address) => Ok(Self { address, _network: PhantomData }),
+            None => Err(anyhow!("Failed to resolve the address of the prover service")),
+        }
+    }
+
+    /// Returns the address of the prover service.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Requests a proved transaction for the given authorization, and ensures it matches the authorization.
+    pub fn prove(&self, authorization: &Authorization<N>) -> Result<ProvedTransaction<N>> {
+        let mut stream = TcpStream::connect_timeout(&self.address, MESSAGE_TIMEOUT)?;
+        stream.set_read_timeout(Some(PROVE_TIMEOUT))?;
+        stream.set_write_timeout(Some(MESSAGE_TIMEOUT))?;
+        let request_line = format!("POST {} HTTP/1.1\r\nHost: {}", PROVE_PATH, self.address);
+        write_message(&mut stream, &request_line, "application/octet-stream", &authorization.to_bytes_le()?)?;
+
+        let (_, status, body) = read_message(&mut stream)?;
+        if status != "200" {
+            return Err(anyhow!("The prover service responded with {}: {}", status, String::from_utf8_lossy(&body)));
+        }
+
+        let proved_transaction = ProvedTransaction::read_le(&body[..])?;
+        authorization.verify(&proved_transaction)?;
+        Ok(proved_transaction)
+    }
+}
+
+///
+/// Writes an HTTP message with the given start line (and any preceding headers), content type, and body.
+/// The connection is closed after each message.
+///
+fn write_message(stream: &mut TcpStream, start_line: &str, content_type: &str, body: &[u8]) -> Result<()> {
+    write!(
+        stream,
+        "{}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        start_line,
+        content_type,
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    Ok(stream.flush()?)
+}
+
+///
+/// Reads an HTTP message, and returns the first two fields of its start line and its body.
+/// For a request, these are the method and path. For a response, these are the version and status code.
+/// The start line and each header must not exceed `MAXIMUM_LINE_SIZE` bytes, and there may be
+/// at most `MAXIMUM_HEADERS` headers.
+///
+fn read_message(stream: &mut TcpStream) -> Result<(String, String, Vec<u8>)> {
+    let mut reader = BufReader::new(stream);
+
+    let start_line = read_line(&mut reader)?;
+    let mut fields = start_line.split_whitespace();
+    let (first, second) = match (fields.next(), fields.next()) {
+        (Some(first), Some(second)) => (first.to_string(), second.to_string()),
+        _ => return Err(anyhow!("Malformed HTTP start line {:?}", start_line.trim_end())),
+    };
+
+    // Read the headers, retaining the content length.
+    let mut content_length = 0;
+    for num_headers in 0.. {
+        let header = read_line(&mut reader)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if num_headers == MAXIMUM_HEADERS {
+            return Err(anyhow!("The HTTP message exceeds the maximum of {} headers", MAXIMUM_HEADERS));
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse()?;
+            }
+        }
+    }
+
+    // Ensure the body does not exceed the maximum size.
+    if content_length > MAXIMUM_BODY_SIZE {
+        return Err(anyhow!("The HTTP body of {} bytes exceeds the maximum size", content_length));
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+    Ok((first, second, body))
+}
+
+/// Reads a line of an HTTP message, which must not exceed `MAXIMUM_LINE_SIZE` bytes.
+fn read_line<R: BufRead>(reader: &mut R) -> Result<String> {
+    let mut line = String::new();
+    reader.by_ref().take(MAXIMUM_LINE_SIZE as u64 + 1).read_line(&mut line)?;
+    match line.ends_with('\n') {
+        true => Ok(line),
+        false if line.len() > MAXIMUM_LINE_SIZE => {
+            Err(anyhow!("The HTTP line exceeds the maximum size of {} bytes", MAXIMUM_LINE_SIZE))
+        }
+        false => Err(anyhow!("The HTTP message ended before its body")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testnet2::Testnet2, Account, AleoAmount, Ledger, LedgerProof, Request};
+
+    use rand::thread_rng;
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn test_prover_on_loopback() {
+        let rng = &mut thread_rng();
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let caller = Account::<Testnet2>::new(rng);
+
+        let server = ProverServer::bind("127.0.0.1:0", Prover::<Testnet2>::new()).unwrap();
+        let address = server.local_addr().unwrap();
+        let server_thread = thread::spawn(move || {
+            for _ in 0..2 {
+                server.accept().unwrap().join().unwrap().unwrap();
+            }
+        });
+        let client = ProverClient::<Testnet2>::new(address).unwrap();
+
+        // Ensure the proved transaction is verified by the client, and is accepted by the ledger.
+        let record = ledger.mine_next_block(caller.address(), true, &AtomicBool::new(false), rng).unwrap();
+        let ledger_proof = ledger.to_ledger_proof(record.commitment()).unwrap();
+        let request = Request::new_transfer(
+            caller.private_key(),
+            vec![record],
+            vec![ledger_proof, LedgerProof::default()],
+            caller.address(),
+            AleoAmount::from_i64(100),
+            AleoAmount::ZERO,
+            false,
+            rng,
+        )
+        .unwrap();
+        let authorization = Authorization::new(ledger.latest_ledger_root(), request).unwrap();
+        let proved_transaction = client.prove(&authorization).unwrap();
+        assert!(ledger.add_unconfirmed_transaction(proved_transaction.transaction()).is_ok());
+
+        // Ensure a malformed authorization is rejected by the service.
+        let mut stream = TcpStream::connect(address).unwrap();
+        write_message(&mut stream, &format!("POST {} HTTP/1.1", PROVE_PATH), "application/octet-stream", &[0u8; 8])
+            .unwrap();
+        let (_, status, _) = read_message(&mut stream).unwrap();
+        assert_eq!("400", status);
+
+        server_thread.join().unwrap();
+    }
+
+    #[test]
+    fn test_read_message_limits() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let read = |message: Vec<u8>| {
+            let mut client = TcpStream::connect(address).unwrap();
+            let (mut stream, _) = listener.accept().unwrap();
+            client.write_all(&message).unwrap();
+            drop(client);
+            read_message(&mut stream)
+        };
+
+        // Ensure a well-formed message is read.
+        let (method, path, body) = read(b"POST /prove HTTP/1.1\r\nContent-Length: 2\r\n\r\nab".to_vec()).unwrap();
+        assert_eq!(("POST", PROVE_PATH, &b"ab"[..]), (method.as_str(), path.as_str(), &body[..]));
+
+        // Ensure a header line that exceeds the maximum size is rejected.
+        let mut message = b"POST /prove HTTP/1.1\r\nX-Padding: ".to_vec();
+        message.extend(vec![b'a'; MAXIMUM_LINE_SIZE]);
+        message.extend(b"\r\n\r\n");
+        assert!(read(message).is_err());
+
+        // Ensure a message that exceeds the maximum number of headers is rejected.
+        let mut message = b"POST /prove HTTP/1.1\r\n".to_vec();
+        for _ in 0..=MAXIMUM_HEADERS {
+            message.extend(b"X-Padding: a\r\n");
+        }
+        message.extend(b"\r\n");
+        assert!(read(message).is_err());
+
+        // Ensure a message that ends before its body is rejected.
+        assert!(read(b"POST /prove HTTP/1.1\r\nContent-Length: 2\r\n".to_vec()).is_err());
+    }
+}
@@ -23,6 +23,9 @@ pub use decryption_key::*;
 pub mod payload;
 pub use payload::*;
 
+pub mod payload_schema;
+pub use payload_schema::*;
+
 pub mod record;
 pub use record::*;
 
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Network, RecordError};
+use crate::{Network, RecordError, PAYLOAD_SCHEMA_ID_SIZE_IN_BYTES, PAYLOAD_SCHEMA_MAGIC};
 use snarkvm_utilities::{FromBytes, FromBytesDeserializer, ToBytes, ToBytesSerializer};
 
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
@@ -45,6 +45,20 @@ impl<N: Network> Payload<N> {
         payload == vec![0u8; N::RECORD_PAYLOAD_SIZE_IN_BYTES]
     }
 
+    /// Returns the schema ID of the payload, if it is encoded with a `PayloadSchema`,
+    /// or `None` if it is a raw payload, which does not start with the schema magic bytes.
+    pub fn schema_id(&self) -> Option<u32> {
+        let (magic, bytes) = self.0.split_at(PAYLOAD_SCHEMA_MAGIC.len());
+        match magic == PAYLOAD_SCHEMA_MAGIC {
+            true => {
+                let mut schema_id = [0u8; PAYLOAD_SCHEMA_ID_SIZE_IN_BYTES];
+                schema_id.copy_from_slice(&bytes[..PAYLOAD_SCHEMA_ID_SIZE_IN_BYTES]);
+                Some(u32::from_le_bytes(schema_id))
+            }
+            false => None,
+        }
+    }
+
     pub fn size() -> usize {
         N::RECORD_PAYLOAD_SIZE_IN_BYTES
     }
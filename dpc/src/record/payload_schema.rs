@@ -0,0 +1,449 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Address, Network, Payload, RecordError};
+use snarkvm_fields::{ConstraintFieldError, PrimeField, ToConstraintField};
+use snarkvm_utilities::{error, FromBytes, ToBytes};
+
+use anyhow::anyhow;
+use blake2::{Blake2s256, Digest};
+use serde::{Deserialize, Serialize};
+use std::{
+    convert::TryInto,
+    fmt,
+    io::{Cursor, Read, Result as IoResult, Write},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+/// The magic bytes at the start of a typed record payload, which distinguish it from a raw payload.
+pub const PAYLOAD_SCHEMA_MAGIC: [u8; 4] = *b"apsc";
+/// The size (in bytes) of the schema ID, which follows the magic bytes in a typed record payload.
+pub const PAYLOAD_SCHEMA_ID_SIZE_IN_BYTES: usize = 4;
+/// The size (in bytes) of the header of a typed record payload, which prefixes its fields.
+pub const PAYLOAD_SCHEMA_HEADER_SIZE_IN_BYTES: usize = PAYLOAD_SCHEMA_MAGIC.len() + PAYLOAD_SCHEMA_ID_SIZE_IN_BYTES;
+
+/// The type of a field in a typed record payload.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PayloadType {
+    Field,
+    Boolean,
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    Address,
+}
+
+impl PayloadType {
+    /// Return the id of the payload type.
+    pub fn id(&self) -> u8 {
+        match self {
+            Self::Field => 0,
+            Self::Boolean => 1,
+            Self::U8 => 2,
+            Self::U16 => 3,
+            Self::U32 => 4,
+            Self::U64 => 5,
+            Self::I8 => 6,
+            Self::I16 => 7,
+            Self::I32 => 8,
+            Self::I64 => 9,
+            Self::Address => 10,
+        }
+    }
+
+    /// Return a payload type given an id.
+    pub fn from_id(id: u8) -> IoResult<Self> {
+        Ok(match id {
+            0 => Self::Field,
+            1 => Self::Boolean,
+            2 => Self::U8,
+            3 => Self::U16,
+            4 => Self::U32,
+            5 => Self::U64,
+            6 => Self::I8,
+            7 => Self::I16,
+            8 => Self::I32,
+            9 => Self::I64,
+            10 => Self::Address,
+            _ => return Err(error("Invalid payload type id")),
+        })
+    }
+
+    /// Returns the number of bytes a field of this type occupies in a payload.
+    pub fn size_in_bytes<N: Network>(&self) -> usize {
+        match self {
+            Self::Field => (N::InnerScalarField::size_in_bits() + 7) / 8,
+            Self::Boolean | Self::U8 | Self::I8 => 1,
+            Self::U16 | Self::I16 => 2,
+            Self::U32 | Self::I32 => 4,
+            Self::U64 | Self::I64 => 8,
+            Self::Address => N::ADDRESS_SIZE_IN_BYTES,
+        }
+    }
+}
+
+impl FromBytes for PayloadType {
+    #[inline]
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let payload_type_id: u8 = FromBytes::read_le(&mut reader)?;
+        Self::from_id(payload_type_id)
+    }
+}
+
+impl ToBytes for PayloadType {
+    #[inline]
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.id().write_le(&mut writer)
+    }
+}
+
+impl FromStr for PayloadType {
+    type Err = RecordError;
+
+    fn from_str(payload_type: &str) -> Result<Self, Self::Err> {
+        Ok(match payload_type {
+            "field" => Self::Field,
+            "boolean" => Self::Boolean,
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "i32" => Self::I32,
+            "i64" => Self::I64,
+            "address" => Self::Address,
+            _ => return Err(anyhow!("Invalid payload type {}", payload_type).into()),
+        })
+    }
+}
+
+impl fmt::Display for PayloadType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Field => write!(f, "field"),
+            Self::Boolean => write!(f, "boolean"),
+            Self::U8 => write!(f, "u8"),
+            Self::U16 => write!(f, "u16"),
+            Self::U32 => write!(f, "u32"),
+            Self::U64 => write!(f, "u64"),
+            Self::I8 => write!(f, "i8"),
+            Self::I16 => write!(f, "i16"),
+            Self::I32 => write!(f, "i32"),
+            Self::I64 => write!(f, "i64"),
+            Self::Address => write!(f, "address"),
+        }
+    }
+}
+
+/// The value of a field in a typed record payload.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"), PartialEq(bound = "N: Network"))]
+pub enum PayloadValue<N: Network> {
+    Field(N::InnerScalarField),
+    Boolean(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    Address(Address<N>),
+}
+
+impl<N: Network> PayloadValue<N> {
+    /// Returns the type of the value.
+    pub fn payload_type(&self) -> PayloadType {
+        match self {
+            Self::Field(..) => PayloadType::Field,
+            Self::Boolean(..) => PayloadType::Boolean,
+            Self::U8(..) => PayloadType::U8,
+            Self::U16(..) => PayloadType::U16,
+            Self::U32(..) => PayloadType::U32,
+            Self::U64(..) => PayloadType::U64,
+            Self::I8(..) => PayloadType::I8,
+            Self::I16(..) => PayloadType::I16,
+            Self::I32(..) => PayloadType::I32,
+            Self::I64(..) => PayloadType::I64,
+            Self::Address(..) => PayloadType::Address,
+        }
+    }
+
+    /// Reads a value of the given type from the given reader.
+    fn read_le<R: Read>(payload_type: PayloadType, mut reader: R) -> IoResult<Self> {
+        Ok(match payload_type {
+            PayloadType::Field => Self::Field(FromBytes::read_le(&mut reader)?),
+            PayloadType::Boolean => Self::Boolean(FromBytes::read_le(&mut reader)?),
+            PayloadType::U8 => Self::U8(FromBytes::read_le(&mut reader)?),
+            PayloadType::U16 => Self::U16(FromBytes::read_le(&mut reader)?),
+            PayloadType::U32 => Self::U32(FromBytes::read_le(&mut reader)?),
+            PayloadType::U64 => Self::U64(FromBytes::read_le(&mut reader)?),
+            PayloadType::I8 => Self::I8(u8::read_le(&mut reader)? as i8),
+            PayloadType::I16 => Self::I16(u16::read_le(&mut reader)? as i16),
+            PayloadType::I32 => Self::I32(u32::read_le(&mut reader)? as i32),
+            PayloadType::I64 => Self::I64(u64::read_le(&mut reader)? as i64),
+            PayloadType::Address => Self::Address(FromBytes::read_le(&mut reader)?),
+        })
+    }
+}
+
+impl<N: Network> ToBytes for PayloadValue<N> {
+    #[inline]
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        match self {
+            Self::Field(value) => value.write_le(&mut writer),
+            Self::Boolean(value) => value.write_le(&mut writer),
+            Self::U8(value) => value.write_le(&mut writer),
+            Self::U16(value) => value.write_le(&mut writer),
+            Self::U32(value) => value.write_le(&mut writer),
+            Self::U64(value) => value.write_le(&mut writer),
+            Self::I8(value) => (*value as u8).write_le(&mut writer),
+            Self::I16(value) => (*value as u16).write_le(&mut writer),
+            Self::I32(value) => (*value as u32).write_le(&mut writer),
+            Self::I64(value) => (*value as u64).write_le(&mut writer),
+            Self::Address(value) => value.write_le(&mut writer),
+        }
+    }
+}
+
+///
+/// A field element is its own constraint field element. Every other value is packed from
+/// its little-endian bytes in the payload, which matches the field elements of the same
+/// bytes allocated as `UInt8` gadgets in a program circuit.
+///
+impl<N: Network> ToConstraintField<N::InnerScalarField> for PayloadValue<N> {
+    fn to_field_elements(&self) -> Result<Vec<N::InnerScalarField>, ConstraintFieldError> {
+        match self {
+            Self::Field(value) => Ok(vec![*value]),
+            _ => self.to_bytes_le()?.to_field_elements(),
+        }
+    }
+}
+
+///
+/// A schema for a typed record payload, which is an ordered list of field types.
+///
+/// A payload is encoded as the schema magic bytes and the schema ID, followed by the little-endian
+/// bytes of each field, and padded with zeros. The schema ID is stored at the start of the payload,
+/// as the record plaintext (and its circuits) treat the payload as opaque bytes next to the program ID,
+/// so the schema ID is committed to with the payload. Payloads without the magic bytes are raw payloads.
+/// Note that a raw payload which happens to start with the magic bytes is read as a typed payload.
+///
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"), PartialEq(bound = "N: Network"))]
+pub struct PayloadSchema<N: Network> {
+    /// The schema ID, which is derived from the field types.
+    schema_id: u32,
+    /// The types of the fields in the payload.
+    field_types: Vec<PayloadType>,
+    _network: PhantomData<N>,
+}
+
+impl<N: Network> PayloadSchema<N> {
+    /// Returns a new instance of a payload schema, if the given field types fit in a record payload.
+    pub fn new(field_types: Vec<PayloadType>) -> Result<Self, RecordError> {
+        let schema_id = Self::compute_schema_id(&field_types)?;
+        let schema = Self { schema_id, field_types, _network: PhantomData };
+
+        // Ensure the fields fit in a record payload.
+        if schema.size_in_bytes() > N::RECORD_PAYLOAD_SIZE_IN_BYTES {
+            return Err(anyhow!(
+                "Payload schema requires {} bytes, which exceeds the record payload of {} bytes",
+                schema.size_in_bytes(),
+                N::RECORD_PAYLOAD_SIZE_IN_BYTES
+            )
+            .into());
+        }
+
+        Ok(schema)
+    }
+
+    /// Returns the schema ID.
+    pub fn schema_id(&self) -> u32 {
+        self.schema_id
+    }
+
+    /// Returns a reference to the field types.
+    pub fn field_types(&self) -> &Vec<PayloadType> {
+        &self.field_types
+    }
+
+    /// Returns the number of bytes of the payload used by the schema ID and fields.
+    pub fn size_in_bytes(&self) -> usize {
+        PAYLOAD_SCHEMA_HEADER_SIZE_IN_BYTES
+            + self.field_types.iter().map(|field_type| field_type.size_in_bytes::<N>()).sum::<usize>()
+    }
+
+    /// Returns the payload encoding the given values, which must match the field types of the schema.
+    pub fn encode(&self, values: &[PayloadValue<N>]) -> Result<Payload<N>, RecordError> {
+        // Ensure the values match the field types.
+        if values.len() != self.field_types.len() {
+            return Err(anyhow!("Expected {} payload values, found {}", self.field_types.len(), values.len()).into());
+        }
+        for (i, (value, field_type)) in values.iter().zip(&self.field_types).enumerate() {
+            if value.payload_type() != *field_type {
+                return Err(
+                    anyhow!("Payload value {} is a {}, expected a {}", i, value.payload_type(), field_type).into()
+                );
+            }
+        }
+
+        let mut bytes = Vec::with_capacity(N::RECORD_PAYLOAD_SIZE_IN_BYTES);
+        PAYLOAD_SCHEMA_MAGIC.write_le(&mut bytes)?;
+        self.schema_id.write_le(&mut bytes)?;
+        values.write_le(&mut bytes)?;
+
+        Ok(Payload::from(&bytes))
+    }
+
+    /// Returns the values in the given payload, if it is encoded with this schema.
+    pub fn decode(&self, payload: &Payload<N>) -> Result<Vec<PayloadValue<N>>, RecordError> {
+        // Ensure the payload is encoded with this schema.
+        match payload.schema_id() {
+            Some(schema_id) if schema_id == self.schema_id => (),
+            Some(schema_id) => {
+                return Err(anyhow!("Payload has schema ID {}, expected {}", schema_id, self.schema_id).into());
+            }
+            None => return Err(anyhow!("Payload is a raw payload, expected schema ID {}", self.schema_id).into()),
+        }
+
+        let bytes = payload.to_bytes_le()?;
+        let mut cursor = Cursor::new(&bytes[PAYLOAD_SCHEMA_HEADER_SIZE_IN_BYTES..]);
+        let values = self
+            .field_types
+            .iter()
+            .map(|field_type| PayloadValue::read_le(*field_type, &mut cursor))
+            .collect::<IoResult<Vec<_>>>()?;
+
+        // Ensure the padding is zero, so each payload has exactly one encoding.
+        if bytes[self.size_in_bytes()..].iter().any(|byte| *byte != 0u8) {
+            return Err(anyhow!("Payload contains non-zero bytes after its fields").into());
+        }
+
+        Ok(values)
+    }
+
+    /// Returns the constraint field elements of the fields in the given payload, in order.
+    pub fn to_field_elements(&self, payload: &Payload<N>) -> Result<Vec<N::InnerScalarField>, RecordError> {
+        let mut field_elements = Vec::new();
+        for value in self.decode(payload)? {
+            field_elements.extend(value.to_field_elements().map_err(anyhow::Error::from)?);
+        }
+        Ok(field_elements)
+    }
+
+    /// Returns the schema ID for the given field types.
+    fn compute_schema_id(field_types: &[PayloadType]) -> Result<u32, RecordError> {
+        let mut preimage = b"AleoPayloadSchema0".to_vec();
+        field_types.write_le(&mut preimage)?;
+
+        let digest = Blake2s256::digest(&preimage);
+        Ok(u32::from_le_bytes(digest[..PAYLOAD_SCHEMA_ID_SIZE_IN_BYTES].try_into().map_err(anyhow::Error::from)?))
+    }
+}
+
+impl<N: Network> FromStr for PayloadSchema<N> {
+    type Err = RecordError;
+
+    /// Returns a payload schema from a comma-separated list of field types, such as `u64,address,boolean`.
+    fn from_str(field_types: &str) -> Result<Self, Self::Err> {
+        match field_types.trim().is_empty() {
+            true => Self::new(vec![]),
+            false => {
+                Self::new(field_types.split(',').map(|field_type| field_type.trim().parse()).collect::<Result<_, _>>()?)
+            }
+        }
+    }
+}
+
+impl<N: Network> fmt::Display for PayloadSchema<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let field_types: Vec<_> = self.field_types.iter().map(ToString::to_string).collect();
+        write!(f, "{}", field_types.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testnet2::Testnet2, Account};
+    use snarkvm_utilities::UniformRand;
+
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn test_encode_decode() {
+        let rng = &mut thread_rng();
+
+        let schema = PayloadSchema::<Testnet2>::from_str("field, boolean, u16, i64, address").unwrap();
+        let values = vec![
+            PayloadValue::Field(UniformRand::rand(rng)),
+            PayloadValue::Boolean(true),
+            PayloadValue::U16(rng.gen()),
+            PayloadValue::I64(-42),
+            PayloadValue::Address(Account::<Testnet2>::new(rng).address()),
+        ];
+
+        let payload = schema.encode(&values).unwrap();
+        assert_eq!(Some(schema.schema_id()), payload.schema_id());
+        assert_eq!(values, schema.decode(&payload).unwrap());
+
+        // Ensure the encoding is deterministic.
+        assert_eq!(payload, schema.encode(&values).unwrap());
+
+        // Ensure the payload does not decode with a different schema.
+        let other_schema = PayloadSchema::<Testnet2>::from_str("field, boolean, u16, i64").unwrap();
+        assert_ne!(schema.schema_id(), other_schema.schema_id());
+        assert!(other_schema.decode(&payload).is_err());
+
+        // Ensure mismatching values are rejected.
+        assert!(schema.encode(&values[1..]).is_err());
+        assert!(other_schema
+            .encode(&[values[1].clone(), values[0].clone(), values[2].clone(), values[3].clone()])
+            .is_err());
+    }
+
+    #[test]
+    fn test_to_field_elements() {
+        let rng = &mut thread_rng();
+
+        let field = UniformRand::rand(rng);
+        let schema = PayloadSchema::<Testnet2>::from_str("field,u64").unwrap();
+        let payload = schema.encode(&[PayloadValue::Field(field), PayloadValue::U64(7)]).unwrap();
+
+        let field_elements = schema.to_field_elements(&payload).unwrap();
+        assert_eq!(vec![field, <Testnet2 as Network>::InnerScalarField::from(7u64)], field_elements);
+    }
+
+    #[test]
+    fn test_schema_size() {
+        // Ensure a schema that exceeds the record payload is rejected.
+        assert!(PayloadSchema::<Testnet2>::from_str("field,field,field").is_ok());
+        assert!(PayloadSchema::<Testnet2>::from_str("field,field,field,field").is_err());
+
+        let schema = PayloadSchema::<Testnet2>::from_str("u8,address").unwrap();
+        assert_eq!(PAYLOAD_SCHEMA_HEADER_SIZE_IN_BYTES + 1 + Testnet2::ADDRESS_SIZE_IN_BYTES, schema.size_in_bytes());
+        assert_eq!(schema, PayloadSchema::from_str(&schema.to_string()).unwrap());
+    }
+}
@@ -121,6 +121,12 @@ impl<N: Network> Record<N> {
         self.program_id
     }
 
+    /// Returns the schema ID of this record, which is committed to as the start of its payload,
+    /// or `None` if the payload is a raw payload that is not encoded with a `PayloadSchema`.
+    pub fn schema_id(&self) -> Option<u32> {
+        self.payload.schema_id()
+    }
+
     /// Returns the randomizer used for the ciphertext.
     pub fn randomizer(&self) -> N::RecordRandomizer {
         self.ciphertext.deref().randomizer()
@@ -327,7 +333,7 @@ impl<N: Network> Default for Record<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{testnet2::Testnet2, Address, PrivateKey};
+    use crate::{testnet2::Testnet2, Address, PayloadSchema, PayloadValue, PrivateKey, PAYLOAD_SCHEMA_MAGIC};
 
     use rand::thread_rng;
 
@@ -418,4 +424,30 @@ mod tests {
         assert_eq!(expected_record, Record::read_le(&expected_bytes[..]).unwrap());
         assert_eq!(expected_record, bincode::deserialize(&expected_bytes[..]).unwrap());
     }
+
+    #[test]
+    fn test_schema_id() {
+        let rng = &mut thread_rng();
+        let address: Address<Testnet2> = PrivateKey::new(rng).into();
+
+        // Output record with a schema-encoded payload
+        let schema = PayloadSchema::<Testnet2>::from_str("u64, boolean").unwrap();
+        let payload = schema.encode(&[PayloadValue::U64(1234), PayloadValue::Boolean(true)]).unwrap();
+        let record = Record::new(address, AleoAmount::ZERO, payload, *Testnet2::noop_program_id(), rng).unwrap();
+        assert_eq!(Some(schema.schema_id()), record.schema_id());
+
+        // Ensure the schema ID survives serialization, without being serialized separately.
+        let record_bytes = record.to_bytes_le().unwrap();
+        assert_eq!(Testnet2::RECORD_SIZE_IN_BYTES, record_bytes.len());
+        assert_eq!(Some(schema.schema_id()), Record::<Testnet2>::read_le(&record_bytes[..]).unwrap().schema_id());
+
+        // Ensure raw payloads have no schema ID.
+        let mut payload = [0u8; Testnet2::RECORD_PAYLOAD_SIZE_IN_BYTES];
+        rng.fill(&mut payload[..]);
+        payload[0] = !PAYLOAD_SCHEMA_MAGIC[0];
+        let payload = Payload::from_bytes_le(&payload).unwrap();
+        let record = Record::new(address, AleoAmount::ZERO, payload, *Testnet2::noop_program_id(), rng).unwrap();
+        assert_eq!(None, record.schema_id());
+        assert_eq!(None, Record::new_noop(address, rng).unwrap().schema_id());
+    }
 }
@@ -19,7 +19,7 @@ use snarkvm_algorithms::{merkle_tree::MerklePath, prelude::*};
 use snarkvm_utilities::{to_bytes_le, FromBytes, ToBytes};
 
 use anyhow::{anyhow, Result};
-use std::io::{Read, Result as IoResult, Write};
+use std::io::{Error, ErrorKind, Read, Result as IoResult, Write};
 
 /// A proof of inclusion for a record in a block.
 #[derive(Derivative)]
@@ -159,7 +159,7 @@ impl<N: Network> RecordProof<N> {
 impl<N: Network> FromBytes for RecordProof<N> {
     #[inline]
     fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
-        let block_hash = FromBytes::read_le(&mut reader)?;
+        let block_hash: N::BlockHash = FromBytes::read_le(&mut reader)?;
         let previous_block_hash = FromBytes::read_le(&mut reader)?;
         let block_header_root = FromBytes::read_le(&mut reader)?;
         let block_header_inclusion_proof = FromBytes::read_le(&mut reader)?;
@@ -167,7 +167,20 @@ impl<N: Network> FromBytes for RecordProof<N> {
         let transactions_inclusion_proof = FromBytes::read_le(&mut reader)?;
         let local_proof = FromBytes::read_le(&mut reader)?;
 
-        Ok(Self::new(
+        // A dummy record proof, which is not for a record in a block, is read back as it was written.
+        if block_hash == Default::default() {
+            return Ok(Self {
+                block_hash,
+                previous_block_hash,
+                block_header_root,
+                block_header_inclusion_proof,
+                transactions_root,
+                transactions_inclusion_proof,
+                local_proof,
+            });
+        }
+
+        Self::new(
             block_hash,
             previous_block_hash,
             block_header_root,
@@ -176,7 +189,7 @@ impl<N: Network> FromBytes for RecordProof<N> {
             transactions_inclusion_proof,
             local_proof,
         )
-        .expect("Failed to deserialize a record inclusion proof"))
+        .map_err(|error| Error::new(ErrorKind::Other, format!("{}", error)))
     }
 }
 
@@ -242,4 +255,20 @@ mod tests {
         record_proof_new_test::<crate::testnet1::Testnet1>().unwrap();
         record_proof_new_test::<crate::testnet2::Testnet2>().unwrap();
     }
+
+    #[test]
+    fn test_dummy_from_bytes() {
+        // Ensure a dummy ledger proof is read back from its bytes.
+        let ledger_proof = LedgerProof::<crate::testnet2::Testnet2>::default();
+        let bytes = ledger_proof.to_bytes_le().unwrap();
+        assert_eq!(
+            bytes,
+            LedgerProof::<crate::testnet2::Testnet2>::read_le(&bytes[..]).unwrap().to_bytes_le().unwrap()
+        );
+
+        // Ensure a record proof for a block that it does not belong to is rejected, instead of panicking.
+        let mut bytes = RecordProof::<crate::testnet2::Testnet2>::default().to_bytes_le().unwrap();
+        bytes[0] = 1;
+        assert!(RecordProof::<crate::testnet2::Testnet2>::read_le(&bytes[..]).is_err());
+    }
 }
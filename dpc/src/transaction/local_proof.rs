@@ -19,7 +19,7 @@ use snarkvm_algorithms::merkle_tree::MerklePath;
 use snarkvm_utilities::{FromBytes, ToBytes};
 
 use anyhow::{anyhow, Result};
-use std::io::{Read, Result as IoResult, Write};
+use std::io::{Error, ErrorKind, Read, Result as IoResult, Write};
 
 /// A local proof of inclusion.
 #[derive(Derivative)]
@@ -89,16 +89,21 @@ impl<N: Network> FromBytes for LocalProof<N> {
         let transaction_inclusion_proof = FromBytes::read_le(&mut reader)?;
         let transition_id = FromBytes::read_le(&mut reader)?;
         let transition_inclusion_proof = FromBytes::read_le(&mut reader)?;
-        let commitment = FromBytes::read_le(&mut reader)?;
-
-        Ok(Self::new(
-            transaction_id,
-            transaction_inclusion_proof,
-            transition_id,
-            transition_inclusion_proof,
-            commitment,
-        )
-        .expect("Failed to deserialize a local inclusion proof"))
+        let commitment: N::Commitment = FromBytes::read_le(&mut reader)?;
+
+        // A dummy local proof, as used for a dummy input record, is read back as it was written.
+        if commitment == Default::default() {
+            return Ok(Self {
+                transaction_id,
+                transaction_inclusion_proof,
+                transition_id,
+                transition_inclusion_proof,
+                commitment,
+            });
+        }
+
+        Self::new(transaction_id, transaction_inclusion_proof, transition_id, transition_inclusion_proof, commitment)
+            .map_err(|error| Error::new(ErrorKind::Other, format!("{}", error)))
     }
 }
 
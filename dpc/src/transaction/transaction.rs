@@ -18,6 +18,7 @@ use crate::{
     record::*,
     Address,
     AleoAmount,
+    Deployment,
     Event,
     LedgerTree,
     LedgerTreeScheme,
@@ -28,9 +29,10 @@ use crate::{
     Transitions,
     VirtualMachine,
 };
+use snarkvm_algorithms::merkle_tree::MerkleTree;
 use snarkvm_utilities::{
     has_duplicates,
-    io::{Read, Result as IoResult, Write},
+    io::{Error, ErrorKind, Read, Result as IoResult, Write},
     FromBytes,
     FromBytesDeserializer,
     ToBytes,
@@ -45,6 +47,7 @@ use std::{
     fmt,
     hash::{Hash, Hasher},
     str::FromStr,
+    sync::Arc,
 };
 
 #[derive(Derivative)]
@@ -103,6 +106,18 @@ impl<N: Network> Transaction<N> {
         }
     }
 
+    /// Initializes an instance of `Transaction` from the given inputs, *without* checking its validity.
+    /// This method must only be used to restore transactions that were previously verified.
+    #[inline]
+    pub(crate) fn from_unchecked(
+        inner_circuit_id: N::InnerCircuitID,
+        ledger_root: N::LedgerRoot,
+        transitions: Vec<Transition<N>>,
+    ) -> Result<Self> {
+        let transaction_id = Self::compute_transaction_id(&transitions)?;
+        Ok(Self { transaction_id, inner_circuit_id, ledger_root, transitions })
+    }
+
     /// Returns `true` if the transaction is well-formed, meaning it contains
     /// the correct network ID, unique serial numbers, unique commitments,
     /// correct ciphertext IDs, and a valid proof.
@@ -158,8 +173,11 @@ impl<N: Network> Transaction<N> {
         }
 
         // Returns `false` if the transaction is not a coinbase, and has a transition with a negative value balance.
+        // A coinbase transaction may consist of multiple coinbase transitions, for the allocations in a genesis block.
+        // Blocks after the genesis block are restricted to one coinbase transition in `Block::is_valid`.
         if self.transitions.len() > 1
             && self.transitions.iter().any(|transition| transition.value_balance().is_negative())
+            && !self.transitions.iter().all(|transition| transition.value_balance().is_negative())
         {
             eprintln!("Transaction contains a transition with a negative value balance");
             return false;
@@ -273,6 +291,18 @@ impl<N: Network> Transaction<N> {
         self.transitions.iter().flat_map(Transition::events)
     }
 
+    /// Returns the program deployments published in this transaction, skipping those not bound to their transition.
+    #[inline]
+    pub fn deployments(&self) -> impl Iterator<Item = &Deployment<N>> + fmt::Debug + '_ {
+        self.transitions.iter().filter_map(|transition| transition.to_deployment().ok().flatten())
+    }
+
+    /// Returns `true` if this transaction publishes a program deployment.
+    #[inline]
+    pub fn is_deployment(&self) -> bool {
+        self.deployments().next().is_some()
+    }
+
     /// Returns a reference to the state transitions.
     #[inline]
     pub fn transitions(&self) -> &Vec<Transition<N>> {
@@ -305,6 +335,23 @@ impl<N: Network> Transaction<N> {
         transitions_tree.to_local_proof(record_commitment)
     }
 
+    /// Reads a transaction from the given reader, *without* checking its validity.
+    /// This method must only be used to restore transactions that were previously verified.
+    #[inline]
+    pub(crate) fn read_le_unchecked<R: Read>(mut reader: R) -> IoResult<Self> {
+        let inner_circuit_id = FromBytes::read_le(&mut reader)?;
+        let ledger_root = FromBytes::read_le(&mut reader)?;
+
+        let num_transitions: u16 = FromBytes::read_le(&mut reader)?;
+        let mut transitions = Vec::with_capacity(num_transitions as usize);
+        for _ in 0..num_transitions {
+            transitions.push(FromBytes::read_le(&mut reader)?);
+        }
+
+        Self::from_unchecked(inner_circuit_id, ledger_root, transitions)
+            .map_err(|error| Error::new(ErrorKind::Other, format!("{}", error)))
+    }
+
     /// Transaction ID := MerkleTree(transition IDs)
     #[inline]
     pub(crate) fn compute_transaction_id(transitions: &[Transition<N>]) -> Result<N::TransactionID> {
@@ -315,6 +362,25 @@ impl<N: Network> Transaction<N> {
         // Return the root of the transitions tree.
         Ok(transitions_tree.root())
     }
+
+    /// Transaction ID := MerkleTree(transition IDs)
+    #[inline]
+    pub(crate) fn compute_transaction_id_from_transition_ids(
+        transition_ids: &[N::TransitionID],
+    ) -> Result<N::TransactionID> {
+        // Ensure the number of transitions is permitted by the transitions tree.
+        if transition_ids.len() >= N::NUM_TRANSITIONS as usize {
+            return Err(anyhow!("The transitions tree has reached its maximum size"));
+        }
+        // Construct the transitions tree from the given transition IDs.
+        let tree = MerkleTree::<N::TransactionIDParameters>::new::<N::TransitionID>(
+            Arc::new(N::transaction_id_parameters().clone()),
+            &[],
+        )?
+        .rebuild(0, transition_ids)?;
+        // Return the root of the transitions tree.
+        Ok((*tree.root()).into())
+    }
 }
 
 impl<N: Network> FromBytes for Transaction<N> {
@@ -329,7 +395,8 @@ impl<N: Network> FromBytes for Transaction<N> {
             transitions.push(FromBytes::read_le(&mut reader)?);
         }
 
-        Ok(Self::from(inner_circuit_id, ledger_root, transitions).expect("Failed to deserialize a transaction"))
+        Self::from(inner_circuit_id, ledger_root, transitions)
+            .map_err(|error| Error::new(ErrorKind::Other, format!("{}", error)))
     }
 }
 
@@ -14,7 +14,18 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Address, AleoAmount, ComputeKey, FunctionType, LedgerProof, Network, Operation, PrivateKey, Record};
+use crate::{
+    Address,
+    AleoAmount,
+    ComputeKey,
+    Deployment,
+    FunctionType,
+    LedgerProof,
+    Network,
+    Operation,
+    PrivateKey,
+    Record,
+};
 use snarkvm_algorithms::SignatureScheme;
 use snarkvm_utilities::{to_bytes_le, FromBytes, ToBytes};
 
@@ -24,7 +35,7 @@ use rand::{CryptoRng, Rng};
 use std::{
     collections::HashSet,
     fmt,
-    io::{Read, Result as IoResult, Write},
+    io::{Error, ErrorKind, Read, Result as IoResult, Write},
 };
 
 #[derive(Clone, Debug)]
@@ -72,6 +83,20 @@ impl<N: Network> Request<N> {
         Self::new(caller, records, ledger_proofs, operation, fee, is_public, rng)
     }
 
+    /// Initializes a new request to deploy the given program, with the given records paying the fee.
+    /// A deployment request is always public, as its output record binds the deployment to the transition.
+    pub fn new_deploy<R: Rng + CryptoRng>(
+        caller: &PrivateKey<N>,
+        records: Vec<Record<N>>,
+        ledger_proofs: Vec<LedgerProof<N>>,
+        deployment: Deployment<N>,
+        fee: AleoAmount,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let operation = Operation::Deploy(caller.to_address(), deployment);
+        Self::new(caller, records, ledger_proofs, operation, fee, true, rng)
+    }
+
     /// Returns a new instance of a noop request.
     pub fn new_noop<R: Rng + CryptoRng>(ledger_proofs: Vec<LedgerProof<N>>, rng: &mut R) -> Result<Self> {
         // Sample a burner noop private key.
@@ -330,8 +355,8 @@ impl<N: Network> FromBytes for Request<N> {
         let signature = FromBytes::read_le(&mut reader)?;
         let is_public = FromBytes::read_le(&mut reader)?;
 
-        Ok(Self::from(records, ledger_proofs, operation, fee, signature, is_public)
-            .expect("Failed to deserialize a request"))
+        Self::from(records, ledger_proofs, operation, fee, signature, is_public)
+            .map_err(|error| Error::new(ErrorKind::Other, format!("{}", error)))
     }
 }
 
@@ -23,7 +23,7 @@ use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Seri
 use std::{
     fmt,
     hash::{Hash, Hasher},
-    io::{Read, Result as IoResult, Write},
+    io::{Error, ErrorKind, Read, Result as IoResult, Write},
     str::FromStr,
     sync::Arc,
 };
@@ -183,6 +183,38 @@ impl<N: Network> Transition<N> {
         &self.execution
     }
 
+    ///
+    /// Returns the deployment published in this transition, if it exists.
+    /// A deployment must be bound to the transition by a public output record of its caller,
+    /// whose payload commits to the program ID of the deployment.
+    ///
+    #[inline]
+    pub fn to_deployment(&self) -> Result<Option<&Deployment<N>>> {
+        let mut deployments = self.events.iter().filter_map(|event| match event {
+            Event::Operation(Operation::Deploy(caller, deployment)) => Some((caller, deployment)),
+            _ => None,
+        });
+
+        let (caller, deployment) = match (deployments.next(), deployments.next()) {
+            (None, _) => return Ok(None),
+            (Some(deployment), None) => deployment,
+            (Some(_), Some(_)) => {
+                return Err(anyhow!("Transition {} publishes multiple deployments", self.transition_id))
+            }
+        };
+
+        // Ensure the deployment is bound to an output record of the transition.
+        let payload = deployment.to_payload()?;
+        match self.to_records().any(|record| record.owner() == *caller && *record.payload() == payload) {
+            true => Ok(Some(deployment)),
+            false => Err(anyhow!(
+                "Deployment of program {} is not bound to transition {}",
+                deployment.program_id(),
+                self.transition_id
+            )),
+        }
+    }
+
     /// Returns records from the transaction belonging to the given account view key.
     #[inline]
     pub fn to_decrypted_records<'a>(
@@ -301,8 +333,8 @@ impl<N: Network> FromBytes for Transition<N> {
 
         let execution: Execution<N> = FromBytes::read_le(&mut reader)?;
 
-        Ok(Self::from(transition_id, serial_numbers, ciphertexts, value_balance, events, execution)
-            .expect("Failed to deserialize a transition from bytes"))
+        Self::from(transition_id, serial_numbers, ciphertexts, value_balance, events, execution)
+            .map_err(|error| Error::new(ErrorKind::Other, format!("{}", error)))
     }
 }
 
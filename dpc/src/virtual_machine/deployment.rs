@@ -0,0 +1,276 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+use crate::{Network, Payload, Program};
+use snarkvm_algorithms::{merkle_tree::MerklePath, SNARK};
+use snarkvm_utilities::{has_duplicates, FromBytes, FromBytesDeserializer, ToBytes, ToBytesSerializer};
+
+use anyhow::{anyhow, Result};
+use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt,
+    io::{Error, ErrorKind, Read, Result as IoResult, Write},
+    str::FromStr,
+};
+
+/// Program ID and function verifying keys, published on-chain to deploy a program.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "N: Network"),
+    Debug(bound = "N: Network"),
+    PartialEq(bound = "N: Network"),
+    Eq(bound = "N: Network")
+)]
+pub struct Deployment<N: Network> {
+    /// The program ID, which is the root of the program tree.
+    program_id: N::ProgramID,
+    /// The function verifying keys, in order of their function index.
+    #[derivative(Debug = "ignore")]
+    verifying_keys: Vec<N::ProgramVerifyingKey>,
+    /// The function IDs, in order of their function index.
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    function_ids: Vec<N::FunctionID>,
+}
+
+impl<N: Network> Deployment<N> {
+    /// Initializes a new deployment of the program with the given function verifying keys,
+    /// in order of their function index.
+    pub fn new(verifying_keys: Vec<N::ProgramVerifyingKey>) -> Result<Self> {
+        let function_ids = Self::compute_function_ids(&verifying_keys)?;
+        let program_id = Program::<N>::compute_program_id(&function_ids)?;
+        Ok(Self { program_id, verifying_keys, function_ids })
+    }
+
+    /// Initializes a new deployment of the noop program.
+    pub fn new_noop() -> Result<Self> {
+        Self::new(vec![N::noop_circuit_verifying_key().clone()])
+    }
+
+    /// Initializes an instance of `Deployment` from the given inputs,
+    /// ensuring the program ID is the root of the program tree of the given verifying keys.
+    pub fn from(program_id: N::ProgramID, verifying_keys: Vec<N::ProgramVerifyingKey>) -> Result<Self> {
+        let deployment = Self::new(verifying_keys)?;
+        match deployment.program_id == program_id {
+            true => Ok(deployment),
+            false => Err(anyhow!(
+                "Incorrect program ID in deployment. Expected {}, found {}",
+                deployment.program_id,
+                program_id
+            )),
+        }
+    }
+
+    /// Returns the program ID.
+    pub fn program_id(&self) -> N::ProgramID {
+        self.program_id
+    }
+
+    /// Returns the function verifying keys, in order of their function index.
+    pub fn verifying_keys(&self) -> &Vec<N::ProgramVerifyingKey> {
+        &self.verifying_keys
+    }
+
+    /// Returns the function IDs, in order of their function index.
+    pub fn function_ids(&self) -> &Vec<N::FunctionID> {
+        &self.function_ids
+    }
+
+    ///
+    /// Returns the payload of the record that binds this deployment to its transition.
+    /// The payload commits to the program ID, which is the root of the function IDs of the deployment.
+    ///
+    pub fn to_payload(&self) -> Result<Payload<N>> {
+        Ok(Payload::from(&self.program_id.to_bytes_le()?))
+    }
+
+    /// Returns `true` if the given function ID exists in the deployed program.
+    pub fn contains_function(&self, function_id: &N::FunctionID) -> bool {
+        self.function_ids.contains(function_id)
+    }
+
+    ///
+    /// Returns `true` if the given verifying key belongs to a function of the deployed program,
+    /// and the given program path proves the function is in the program tree.
+    ///
+    pub fn is_valid_function(
+        &self,
+        verifying_key: &<N::ProgramSNARK as SNARK>::VerifyingKey,
+        program_path: &MerklePath<N::ProgramIDParameters>,
+    ) -> bool {
+        let function_id = match N::function_id(verifying_key) {
+            Ok(function_id) => function_id,
+            Err(error) => {
+                eprintln!("Failed to compute the function ID: {}", error);
+                return false;
+            }
+        };
+
+        if !self.contains_function(&function_id) {
+            eprintln!("Function {} does not exist in program {}", function_id, self.program_id);
+            return false;
+        }
+
+        match program_path.verify(&self.program_id, &function_id) {
+            Ok(is_valid) => is_valid,
+            Err(error) => {
+                eprintln!("Failed to verify the program path: {}", error);
+                false
+            }
+        }
+    }
+
+    /// Returns the function IDs of the given verifying keys, ensuring the list is non-empty and unique.
+    fn compute_function_ids(verifying_keys: &[N::ProgramVerifyingKey]) -> Result<Vec<N::FunctionID>> {
+        // Ensure the number of functions is between 1 and `u8::MAX`.
+        if verifying_keys.is_empty() || verifying_keys.len() > u8::MAX as usize {
+            return Err(anyhow!("A deployment must contain between 1 and {} functions", u8::MAX));
+        }
+
+        let function_ids = verifying_keys.iter().map(N::function_id).collect::<Result<Vec<_>>>()?;
+
+        // Ensure the list of function IDs is unique.
+        if has_duplicates(function_ids.iter()) {
+            return Err(anyhow!("A deployment must not contain duplicate functions"));
+        }
+
+        Ok(function_ids)
+    }
+}
+
+impl<N: Network> FromBytes for Deployment<N> {
+    #[inline]
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let program_id = FromBytes::read_le(&mut reader)?;
+
+        let num_functions: u8 = FromBytes::read_le(&mut reader)?;
+        let mut verifying_keys = Vec::with_capacity(num_functions as usize);
+        for _ in 0..num_functions {
+            verifying_keys.push(FromBytes::read_le(&mut reader)?);
+        }
+
+        Self::from(program_id, verifying_keys).map_err(|error| Error::new(ErrorKind::Other, format!("{}", error)))
+    }
+}
+
+impl<N: Network> ToBytes for Deployment<N> {
+    #[inline]
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.program_id.write_le(&mut writer)?;
+        (self.verifying_keys.len() as u8).write_le(&mut writer)?;
+        self.verifying_keys.write_le(&mut writer)
+    }
+}
+
+impl<N: Network> FromStr for Deployment<N> {
+    type Err = anyhow::Error;
+
+    #[inline]
+    fn from_str(deployment: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(deployment)?)
+    }
+}
+
+impl<N: Network> fmt::Display for Deployment<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).map_err::<fmt::Error, _>(serde::ser::Error::custom)?)
+    }
+}
+
+impl<N: Network> Serialize for Deployment<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => {
+                let mut deployment = serializer.serialize_struct("Deployment", 2)?;
+                deployment.serialize_field("program_id", &self.program_id)?;
+                deployment.serialize_field("verifying_keys", &self.verifying_keys)?;
+                deployment.end()
+            }
+            false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for Deployment<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => {
+                let deployment = serde_json::Value::deserialize(deserializer)?;
+                // Recover the deployment.
+                Self::from(
+                    serde_json::from_value(deployment["program_id"].clone()).map_err(de::Error::custom)?,
+                    serde_json::from_value(deployment["verifying_keys"].clone()).map_err(de::Error::custom)?,
+                )
+                .map_err(de::Error::custom)
+            }
+            false => FromBytesDeserializer::<Self>::deserialize_with_size_encoding(deserializer, "deployment"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    #[test]
+    fn test_noop_deployment() {
+        let deployment = Deployment::<Testnet2>::new_noop().unwrap();
+        assert_eq!(*Testnet2::noop_program_id(), deployment.program_id());
+        assert_eq!(vec![*Testnet2::noop_function_id()], *deployment.function_ids());
+        assert!(deployment.is_valid_function(Testnet2::noop_circuit_verifying_key(), Testnet2::noop_program_path()));
+    }
+
+    #[test]
+    fn test_deployment_rejects_incorrect_program_id() {
+        let verifying_keys = vec![Testnet2::noop_circuit_verifying_key().clone()];
+        assert!(Deployment::<Testnet2>::from(Default::default(), verifying_keys.clone()).is_err());
+        assert!(Deployment::<Testnet2>::from(*Testnet2::noop_program_id(), verifying_keys).is_ok());
+
+        // Ensure a deployment must contain unique functions.
+        let verifying_keys = vec![Testnet2::noop_circuit_verifying_key().clone(); 2];
+        assert!(Deployment::<Testnet2>::new(verifying_keys).is_err());
+        assert!(Deployment::<Testnet2>::new(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_deployment_serde_json() {
+        let deployment = Deployment::<Testnet2>::new_noop().unwrap();
+
+        // Serialize
+        let expected_string = deployment.to_string();
+        let candidate_string = serde_json::to_string(&deployment).unwrap();
+        assert_eq!(expected_string, candidate_string);
+
+        // Deserialize
+        assert_eq!(deployment, Deployment::<Testnet2>::from_str(&candidate_string).unwrap());
+        assert_eq!(deployment, serde_json::from_str(&candidate_string).unwrap());
+    }
+
+    #[test]
+    fn test_deployment_bincode() {
+        let deployment = Deployment::<Testnet2>::new_noop().unwrap();
+
+        // Serialize
+        let expected_bytes = deployment.to_bytes_le().unwrap();
+        let candidate_bytes = bincode::serialize(&deployment).unwrap();
+        // TODO (howardwu): Serialization - Handle the inconsistency between ToBytes and Serialize (off by a length encoding).
+        assert_eq!(&expected_bytes[..], &candidate_bytes[8..]);
+
+        // Deserialize
+        assert_eq!(deployment, Deployment::<Testnet2>::read_le(&expected_bytes[..]).unwrap());
+        assert_eq!(deployment, bincode::deserialize(&candidate_bytes[..]).unwrap());
+    }
+}
@@ -20,6 +20,9 @@ pub use amount::*;
 pub mod builder;
 pub use builder::*;
 
+pub mod deployment;
+pub use deployment::*;
+
 pub mod event;
 pub use event::*;
 
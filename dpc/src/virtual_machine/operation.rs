@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Address, AleoAmount, FunctionInputs, FunctionType, Network};
+use crate::{Address, AleoAmount, Deployment, FunctionInputs, FunctionType, Network};
 use snarkvm_fields::{ConstraintFieldError, ToConstraintField};
 use snarkvm_utilities::{FromBytes, FromBytesDeserializer, ToBytes, ToBytesSerializer};
 
@@ -45,6 +45,8 @@ pub enum Operation<N: Network> {
     Transfer(Caller<N>, Recipient<N>, AleoAmount),
     /// Invokes the given records on the function and inputs.
     Evaluate(N::FunctionID, FunctionType, FunctionInputs<N>),
+    /// Publishes the given program deployment, with the caller paying the fee.
+    Deploy(Caller<N>, Deployment<N>),
 }
 
 impl<N: Network> Operation<N> {
@@ -55,12 +57,13 @@ impl<N: Network> Operation<N> {
             Self::Coinbase(..) => 1,
             Self::Transfer(..) => 2,
             Self::Evaluate(..) => 3,
+            Self::Deploy(..) => 4,
         }
     }
 
     pub fn function_id(&self) -> N::FunctionID {
         match self {
-            Self::Noop | Self::Coinbase(..) | Self::Transfer(..) => *N::noop_function_id(),
+            Self::Noop | Self::Coinbase(..) | Self::Transfer(..) | Self::Deploy(..) => *N::noop_function_id(),
             Self::Evaluate(function_id, _, _) => *function_id,
         }
     }
@@ -71,6 +74,7 @@ impl<N: Network> Operation<N> {
             Self::Coinbase(..) => FunctionType::Insert,
             Self::Transfer(..) => FunctionType::Full,
             Self::Evaluate(_, function_type, _) => *function_type,
+            Self::Deploy(..) => FunctionType::Full,
         }
     }
 
@@ -96,6 +100,10 @@ impl<N: Network> Operation<N> {
     pub fn is_evaluate(&self) -> bool {
         matches!(self, Self::Evaluate(..))
     }
+
+    pub fn is_deploy(&self) -> bool {
+        matches!(self, Self::Deploy(..))
+    }
 }
 
 impl<N: Network> FromBytes for Operation<N> {
@@ -121,6 +129,11 @@ impl<N: Network> FromBytes for Operation<N> {
                 let function_inputs = FromBytes::read_le(&mut reader)?;
                 Ok(Self::Evaluate(function_id, function_type, function_inputs))
             }
+            4 => {
+                let caller = FromBytes::read_le(&mut reader)?;
+                let deployment = FromBytes::read_le(&mut reader)?;
+                Ok(Self::Deploy(caller, deployment))
+            }
             _ => unreachable!("Invalid operation during deserialization"),
         }
     }
@@ -146,6 +159,10 @@ impl<N: Network> ToBytes for Operation<N> {
                 function_type.write_le(&mut writer)?;
                 function_inputs.write_le(&mut writer)
             }
+            Self::Deploy(caller, deployment) => {
+                caller.write_le(&mut writer)?;
+                deployment.write_le(&mut writer)
+            }
         }
     }
 }
@@ -176,6 +193,11 @@ impl<N: Network> FromStr for Operation<N> {
                 let function_inputs = serde_json::from_value(operation["function_inputs"].clone())?;
                 Ok(Self::Evaluate(function_id, function_type, function_inputs))
             }
+            4 => {
+                let caller = serde_json::from_value(operation["caller"].clone())?;
+                let deployment = serde_json::from_value(operation["deployment"].clone())?;
+                Ok(Self::Deploy(caller, deployment))
+            }
             _ => unreachable!(format!("Invalid operation id {}", operation_id)),
         }
     }
@@ -212,6 +234,13 @@ impl<N: Network> fmt::Display for Operation<N> {
                     "function_inputs": function_inputs
                 })
             }
+            Self::Deploy(caller, deployment) => {
+                serde_json::json!({
+                    "id": self.operation_id(),
+                    "caller": caller,
+                    "deployment": deployment
+                })
+            }
         };
 
         write!(f, "{}", operation)
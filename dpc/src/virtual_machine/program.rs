@@ -73,6 +73,16 @@ impl<N: Network> Program<N> {
         (*self.tree.root()).into()
     }
 
+    /// Returns the program ID of a program with the given function IDs, in order of their function index.
+    pub fn compute_program_id(function_ids: &[N::FunctionID]) -> Result<N::ProgramID> {
+        let tree = MerkleTree::<N::ProgramIDParameters>::new::<N::FunctionID>(
+            Arc::new(N::program_id_parameters().clone()),
+            &[],
+        )?
+        .rebuild(0, function_ids)?;
+        Ok((*tree.root()).into())
+    }
+
     /// Returns `true` if the given function ID exists in the program.
     pub fn contains_function(&self, function_id: &N::FunctionID) -> bool {
         self.functions.get(function_id).is_some()
@@ -48,29 +48,15 @@ impl<N: Network> VirtualMachine<N> {
         self.transitions.len()
     }
 
+    ///
     /// Executes the request, returning a transaction.
+    ///
+    /// Note: An evaluate request is resolved to the program of its records, which must be the noop program,
+    /// as only the noop function is proven here. Functions of deployed programs are executed with `execute_program`.
+    ///
     pub fn execute<R: Rng + CryptoRng>(mut self, request: &Request<N>, rng: &mut R) -> Result<(Self, Response<N>)> {
-        // Ensure the request is valid.
-        if !request.is_valid() {
-            return Err(anyhow!("Virtual machine received an invalid request"));
-        }
-
-        // Compute the operation.
-        let operation = request.operation().clone();
-        let response = match operation {
-            Operation::Noop => Self::noop(request, rng)?,
-            Operation::Coinbase(recipient, amount) => Self::coinbase(request, recipient, amount, rng)?,
-            Operation::Transfer(caller, recipient, amount) => Self::transfer(request, caller, recipient, amount, rng)?,
-            Operation::Evaluate(function_id, function_type, function_inputs) => self.evaluate(
-                request,
-                request.to_program_id()?,
-                &function_id,
-                &function_type,
-                &function_inputs,
-                vec![], // custom_events
-                rng,
-            )?,
-        };
+        // Compute the response.
+        let response = self.to_response(request, rng)?;
 
         let program_id = request.to_program_id()?;
         let transition_id = response.transition_id();
@@ -110,6 +96,47 @@ impl<N: Network> VirtualMachine<N> {
         Ok((self, response))
     }
 
+    ///
+    /// Returns the response of the request, which determines the output records of its transition.
+    /// This is also used to verify the output records of a transition proven by an untrusted prover.
+    ///
+    pub(crate) fn to_response<R: Rng + CryptoRng>(&self, request: &Request<N>, rng: &mut R) -> Result<Response<N>> {
+        // Ensure the request is valid.
+        if !request.is_valid() {
+            return Err(anyhow!("Virtual machine received an invalid request"));
+        }
+
+        // Compute the operation.
+        let operation = request.operation().clone();
+        Ok(match operation {
+            Operation::Noop => Self::noop(request, rng)?,
+            Operation::Coinbase(recipient, amount) => Self::coinbase(request, recipient, amount, rng)?,
+            Operation::Transfer(caller, recipient, amount) => Self::transfer(request, caller, recipient, amount, rng)?,
+            Operation::Deploy(caller, deployment) => Self::deploy(request, caller, &deployment, rng)?,
+            Operation::Evaluate(function_id, function_type, function_inputs) => {
+                let program_id = request.to_program_id()?;
+                if program_id != *N::noop_program_id() {
+                    return Err(anyhow!(
+                        "Program {} must be executed with its function and private variables",
+                        program_id
+                    ));
+                }
+                let function = N::noop_program()
+                    .to_function(&function_id)
+                    .map_err(|_| VMError::MissingFunction(function_id.to_string(), program_id.to_string()))?;
+                self.evaluate(
+                    request,
+                    program_id,
+                    &*function,
+                    &function_type,
+                    &function_inputs,
+                    vec![], // custom_events
+                    rng,
+                )?
+            }
+        })
+    }
+
     /// Finalizes the virtual machine state and returns a transaction.
     pub fn finalize(&self) -> Result<Transaction<N>> {
         Transaction::from(*N::inner_circuit_id(), self.ledger_root, self.transitions.clone())
@@ -165,24 +192,74 @@ impl<N: Network> VirtualMachine<N> {
             .build(rng)
     }
 
+    ///
+    /// Publishes the program deployment in the request, returning the balance after the fee to `caller`.
+    /// The output record commits to the program ID in its payload, and is public, which binds the deployment
+    /// to the transition ID, as the output commitments are proven in the transition.
+    /// Note: A deployment is well-formed by construction, as its program ID is computed from its verifying keys.
+    ///
+    fn deploy<R: Rng + CryptoRng>(
+        request: &Request<N>,
+        caller: Address<N>,
+        deployment: &Deployment<N>,
+        rng: &mut R,
+    ) -> Result<Response<N>> {
+        // Fetch the caller.
+        if request.caller()? != caller {
+            return Err(anyhow!("Caller in instruction does not match request caller"));
+        }
+
+        // Ensure the request is public, so the output record binding the deployment is public.
+        if !request.is_public() {
+            return Err(anyhow!("A program deployment must be requested as public"));
+        }
+
+        // Compute the final balance of the caller.
+        let caller_balance = request.to_balance().sub(request.fee());
+        if caller_balance.is_negative() {
+            return Err(VMError::BalanceInsufficient.into());
+        }
+
+        // The operation event is always emitted, as it publishes the deployment.
+        ResponseBuilder::new()
+            .add_request(request.clone())
+            .add_output(Output::new(caller, caller_balance, deployment.to_payload()?, None)?)
+            .add_event(Event::Operation(request.operation().clone()))
+            .build(rng)
+    }
+
     /// Returns a response based on the current state of the virtual machine.
     fn evaluate<R: Rng + CryptoRng>(
         &self,
         request: &Request<N>,
         program_id: N::ProgramID,
-        function_id: &N::FunctionID,
-        _function_type: &FunctionType,
+        function: &dyn Function<N>,
+        function_type: &FunctionType,
         function_inputs: &FunctionInputs<N>,
         custom_events: Vec<Vec<u8>>,
         rng: &mut R,
     ) -> Result<Response<N>> {
-        // TODO (raychu86): Do function type checks.
-
-        // Check that the function id exists in the program.
+        // Check that the program is the same as the request.
+        if program_id != request.to_program_id()? {
+            return Err(VMError::ProgramIDMismatch(program_id.to_string(), request.to_program_id()?.to_string()).into());
+        }
 
         // Check that the function id is the same as the request.
-        if function_id != &request.function_id() {
-            return Err(anyhow!("Invalid function id"));
+        let function_id = function.function_id();
+        if function_id != request.function_id() {
+            return Err(VMError::FunctionIDMismatch(function_id.to_string(), request.function_id().to_string()).into());
+        }
+
+        // Check that the function type is the one declared by the function.
+        // Note: The noop function imposes no constraints on records, and may be evaluated as any function type.
+        if function.function_type() != FunctionType::Noop && function.function_type() != *function_type {
+            return Err(VMError::FunctionTypeMismatch(function.function_type(), *function_type).into());
+        }
+
+        // Check that the number of input records matches the function type.
+        let num_inputs = request.records().iter().filter(|record| !record.is_dummy()).count();
+        if num_inputs != function_type.input_count() as usize {
+            return Err(VMError::InvalidInputCount(*function_type, function_type.input_count(), num_inputs).into());
         }
 
         // Fetch the caller.
@@ -202,21 +279,34 @@ impl<N: Network> VirtualMachine<N> {
             return Err(VMError::BalanceInsufficient.into());
         }
 
-        let mut response_builder = ResponseBuilder::new().add_request(request.clone()).add_output(Output::new(
-            function_inputs.recipient,
-            function_inputs.amount,
-            function_inputs.record_payload.clone(),
-            Some(program_id),
-        )?);
+        // Construct the output to the recipient, unless the function type has no outputs and it would be empty.
+        let mut outputs = Vec::with_capacity(N::NUM_OUTPUT_RECORDS);
+        if function_type.output_count() > 0
+            || !function_inputs.amount.is_zero()
+            || !function_inputs.record_payload.is_empty()
+        {
+            outputs.push(Output::new(
+                function_inputs.recipient,
+                function_inputs.amount,
+                function_inputs.record_payload.clone(),
+                Some(program_id),
+            )?);
+        }
 
         // Add the change address if the balance is not zero.
         if !caller_balance.is_zero() {
-            response_builder = response_builder.add_output(Output::new(
-                function_inputs.caller,
-                caller_balance,
-                Default::default(),
-                None,
-            )?)
+            outputs.push(Output::new(function_inputs.caller, caller_balance, Default::default(), None)?);
+        }
+
+        // Check that the number of output records does not exceed the function type.
+        // Note: Fewer outputs are permitted, such as a split or full transition without change.
+        if outputs.len() > function_type.output_count() as usize {
+            return Err(VMError::InvalidOutputCount(*function_type, function_type.output_count(), outputs.len()).into());
+        }
+
+        let mut response_builder = ResponseBuilder::new().add_request(request.clone());
+        for output in outputs {
+            response_builder = response_builder.add_output(output);
         }
 
         // Add custom events to the response.
@@ -237,9 +327,9 @@ impl<N: Network> VirtualMachine<N> {
     pub fn execute_program<R: Rng + CryptoRng>(
         mut self,
         request: &Request<N>,
-        program_id: <N as Network>::ProgramID,
+        program_id: N::ProgramID,
         function: &Arc<dyn Function<N>>,
-        function_path: &MerklePath<<N as Network>::ProgramIDParameters>,
+        function_path: &MerklePath<N::ProgramIDParameters>,
         function_verifying_key: <<N as Network>::ProgramSNARK as SNARK>::VerifyingKey,
         private_variables: &dyn ProgramPrivateVariables<N>,
         custom_events: Vec<Vec<u8>>,
@@ -250,11 +340,16 @@ impl<N: Network> VirtualMachine<N> {
             return Err(anyhow!("Virtual machine received an invalid request"));
         }
 
+        // Check that the function exists in the program.
+        if !function_path.verify(&program_id, &function.function_id())? {
+            return Err(VMError::MissingFunction(function.function_id().to_string(), program_id.to_string()).into());
+        }
+
         // Compute the operation.
         let operation = request.operation().clone();
         let response = match operation {
-            Operation::Evaluate(function_id, function_type, function_inputs) => {
-                self.evaluate(request, program_id, &function_id, &function_type, &function_inputs, custom_events, rng)?
+            Operation::Evaluate(_, function_type, function_inputs) => {
+                self.evaluate(request, program_id, &**function, &function_type, &function_inputs, custom_events, rng)?
             }
             _ => return Err(anyhow!("Invalid Operation")),
         };
@@ -267,7 +362,6 @@ impl<N: Network> VirtualMachine<N> {
         let public_variables = ProgramPublicVariables::new(transition_id);
 
         assert!(function.verify(&public_variables, &program_proof));
-        assert!(function_path.verify(&program_id, &function.function_id())?);
 
         // Compute the inner circuit proof, and verify that the inner proof passes.
         let inner_public = InnerPublicVariables::new(
@@ -301,3 +395,215 @@ impl<N: Network> VirtualMachine<N> {
         Ok((self, response))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    use rand::thread_rng;
+
+    /// Evaluates the noop function as the given function type, on records with the given values.
+    fn evaluate(function_type: FunctionType, values: &[i64], amount: i64, fee: i64) -> Result<Response<Testnet2>> {
+        let rng = &mut thread_rng();
+        let account = Account::<Testnet2>::new(rng);
+        let recipient = Account::<Testnet2>::new(rng);
+
+        let records = values
+            .iter()
+            .map(|value| {
+                Record::new(
+                    account.address(),
+                    AleoAmount(*value),
+                    Default::default(),
+                    *Testnet2::noop_program_id(),
+                    rng,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let function_id = *Testnet2::noop_function_id();
+        let function_inputs =
+            FunctionInputs::new(&account.address(), &recipient.address(), AleoAmount(amount), Default::default());
+        let request = Request::new(
+            account.private_key(),
+            records,
+            vec![LedgerProof::default(); Testnet2::NUM_INPUT_RECORDS],
+            Operation::Evaluate(function_id, function_type, function_inputs.clone()),
+            AleoAmount(fee),
+            false,
+            rng,
+        )?;
+
+        let vm = VirtualMachine::<Testnet2>::new(LedgerTree::<Testnet2>::new()?.root())?;
+        let function = Testnet2::noop_program().to_function(&function_id)?;
+        vm.evaluate(&request, *Testnet2::noop_program_id(), &*function, &function_type, &function_inputs, vec![], rng)
+    }
+
+    /// Returns the number of non-dummy output records in the given response.
+    fn num_outputs(response: &Response<Testnet2>) -> usize {
+        response.records().iter().filter(|record| !record.is_dummy()).count()
+    }
+
+    /// Returns the virtual machine error of the given result.
+    fn to_error(result: Result<Response<Testnet2>>) -> VMError {
+        result.unwrap_err().downcast::<VMError>().unwrap()
+    }
+
+    #[test]
+    fn test_evaluate_noop() {
+        assert_eq!(0, num_outputs(&evaluate(FunctionType::Noop, &[], 0, 0).unwrap()));
+
+        let error = to_error(evaluate(FunctionType::Noop, &[100], 0, 100));
+        assert!(matches!(error, VMError::InvalidInputCount(FunctionType::Noop, 0, 1)));
+    }
+
+    #[test]
+    fn test_evaluate_insert() {
+        assert!(evaluate(FunctionType::Insert, &[], 0, 0).is_ok());
+
+        let error = to_error(evaluate(FunctionType::Insert, &[100], 100, 0));
+        assert!(matches!(error, VMError::InvalidInputCount(FunctionType::Insert, 0, 1)));
+    }
+
+    #[test]
+    fn test_evaluate_update() {
+        assert_eq!(1, num_outputs(&evaluate(FunctionType::Update, &[100], 100, 0).unwrap()));
+
+        let error = to_error(evaluate(FunctionType::Update, &[100], 40, 0));
+        assert!(matches!(error, VMError::InvalidOutputCount(FunctionType::Update, 1, 2)));
+
+        let error = to_error(evaluate(FunctionType::Update, &[50, 50], 100, 0));
+        assert!(matches!(error, VMError::InvalidInputCount(FunctionType::Update, 1, 2)));
+    }
+
+    #[test]
+    fn test_evaluate_remove() {
+        assert_eq!(0, num_outputs(&evaluate(FunctionType::Remove, &[100], 0, 100).unwrap()));
+
+        let error = to_error(evaluate(FunctionType::Remove, &[100], 100, 0));
+        assert!(matches!(error, VMError::InvalidOutputCount(FunctionType::Remove, 0, 1)));
+
+        let error = to_error(evaluate(FunctionType::Remove, &[], 0, 0));
+        assert!(matches!(error, VMError::InvalidInputCount(FunctionType::Remove, 1, 0)));
+    }
+
+    #[test]
+    fn test_evaluate_double_insert() {
+        assert!(evaluate(FunctionType::DoubleInsert, &[], 0, 0).is_ok());
+
+        let error = to_error(evaluate(FunctionType::DoubleInsert, &[50, 50], 100, 0));
+        assert!(matches!(error, VMError::InvalidInputCount(FunctionType::DoubleInsert, 0, 2)));
+    }
+
+    #[test]
+    fn test_evaluate_double_remove() {
+        assert_eq!(0, num_outputs(&evaluate(FunctionType::DoubleRemove, &[50, 50], 0, 100).unwrap()));
+
+        let error = to_error(evaluate(FunctionType::DoubleRemove, &[100], 0, 100));
+        assert!(matches!(error, VMError::InvalidInputCount(FunctionType::DoubleRemove, 2, 1)));
+
+        let error = to_error(evaluate(FunctionType::DoubleRemove, &[50, 50], 0, 60));
+        assert!(matches!(error, VMError::InvalidOutputCount(FunctionType::DoubleRemove, 0, 1)));
+    }
+
+    #[test]
+    fn test_evaluate_join() {
+        assert_eq!(1, num_outputs(&evaluate(FunctionType::Join, &[50, 50], 100, 0).unwrap()));
+
+        let error = to_error(evaluate(FunctionType::Join, &[50, 50], 60, 0));
+        assert!(matches!(error, VMError::InvalidOutputCount(FunctionType::Join, 1, 2)));
+
+        let error = to_error(evaluate(FunctionType::Join, &[100], 100, 0));
+        assert!(matches!(error, VMError::InvalidInputCount(FunctionType::Join, 2, 1)));
+    }
+
+    #[test]
+    fn test_evaluate_split() {
+        assert_eq!(2, num_outputs(&evaluate(FunctionType::Split, &[100], 40, 0).unwrap()));
+        // A split without change is permitted.
+        assert_eq!(1, num_outputs(&evaluate(FunctionType::Split, &[100], 100, 0).unwrap()));
+
+        let error = to_error(evaluate(FunctionType::Split, &[50, 50], 40, 0));
+        assert!(matches!(error, VMError::InvalidInputCount(FunctionType::Split, 1, 2)));
+    }
+
+    #[test]
+    fn test_evaluate_full() {
+        assert_eq!(2, num_outputs(&evaluate(FunctionType::Full, &[50, 50], 40, 0).unwrap()));
+
+        let error = to_error(evaluate(FunctionType::Full, &[100], 40, 0));
+        assert!(matches!(error, VMError::InvalidInputCount(FunctionType::Full, 2, 1)));
+    }
+
+    #[test]
+    fn test_evaluate_function_id_mismatch() {
+        let rng = &mut thread_rng();
+        let account = Account::<Testnet2>::new(rng);
+
+        // Construct a request for a function of a program other than the noop program.
+        let program_id: <Testnet2 as Network>::ProgramID = Default::default();
+        let record = Record::new(account.address(), AleoAmount(100), Default::default(), program_id, rng).unwrap();
+
+        let function_inputs =
+            FunctionInputs::new(&account.address(), &account.address(), AleoAmount(100), Default::default());
+        let request = Request::new(
+            account.private_key(),
+            vec![record],
+            vec![LedgerProof::default(); Testnet2::NUM_INPUT_RECORDS],
+            Operation::Evaluate(Default::default(), FunctionType::Update, function_inputs.clone()),
+            AleoAmount::ZERO,
+            false,
+            rng,
+        )
+        .unwrap();
+
+        let vm = VirtualMachine::<Testnet2>::new(LedgerTree::<Testnet2>::new().unwrap().root()).unwrap();
+        let function = Testnet2::noop_program().to_function(Testnet2::noop_function_id()).unwrap();
+        let result =
+            vm.evaluate(&request, program_id, &*function, &FunctionType::Update, &function_inputs, vec![], rng);
+        assert!(matches!(to_error(result), VMError::FunctionIDMismatch(..)));
+    }
+
+    #[test]
+    fn test_execute_program_id_mismatch() {
+        let rng = &mut thread_rng();
+        let account = Account::<Testnet2>::new(rng);
+
+        // Construct a request on a record of a program other than the noop program.
+        let program_id: <Testnet2 as Network>::ProgramID = Default::default();
+        assert_ne!(*Testnet2::noop_program_id(), program_id);
+        let record = Record::new(account.address(), AleoAmount(100), Default::default(), program_id, rng).unwrap();
+
+        let function_inputs =
+            FunctionInputs::new(&account.address(), &account.address(), AleoAmount(100), Default::default());
+        let request = Request::new(
+            account.private_key(),
+            vec![record],
+            vec![LedgerProof::default(); Testnet2::NUM_INPUT_RECORDS],
+            Operation::Evaluate(*Testnet2::noop_function_id(), FunctionType::Update, function_inputs.clone()),
+            AleoAmount::ZERO,
+            false,
+            rng,
+        )
+        .unwrap();
+        assert_eq!(program_id, request.to_program_id().unwrap());
+
+        // Ensure the request is not evaluated as the noop program.
+        let vm = VirtualMachine::<Testnet2>::new(LedgerTree::<Testnet2>::new().unwrap().root()).unwrap();
+        let function = Testnet2::noop_program().to_function(Testnet2::noop_function_id()).unwrap();
+        let result = vm.evaluate(
+            &request,
+            *Testnet2::noop_program_id(),
+            &*function,
+            &FunctionType::Update,
+            &function_inputs,
+            vec![],
+            rng,
+        );
+        assert!(matches!(to_error(result), VMError::ProgramIDMismatch(..)));
+
+        // Ensure the request is not executed with the noop execution.
+        assert!(vm.execute(&request, rng).is_err());
+    }
+}
@@ -0,0 +1,274 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::prelude::*;
+
+use anyhow::{anyhow, Result};
+use rand::{CryptoRng, Rng};
+use std::collections::VecDeque;
+
+///
+/// A builder for transfers that selects its input records from a given set of unspent records.
+/// If more input records are required than fit in one transition, the input records are first
+/// combined with `Join` transitions, before the final transition pays the recipient and the fee.
+///
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"))]
+pub struct TransferBuilder<N: Network> {
+    /// The private key of the caller.
+    caller: PrivateKey<N>,
+    /// The address of the recipient.
+    recipient: Address<N>,
+    /// The amount to transfer to the recipient.
+    amount: AleoAmount,
+    /// The fee to pay for the transfer.
+    fee: AleoAmount,
+    /// The visibility of the transfer.
+    is_public: bool,
+    /// The unspent records of the caller, from which the input records are selected.
+    records: Vec<Record<N>>,
+}
+
+impl<N: Network> TransferBuilder<N> {
+    /// Initializes a new instance of `TransferBuilder`.
+    pub fn new(caller: &PrivateKey<N>, recipient: Address<N>, amount: AleoAmount, fee: AleoAmount) -> Self {
+        Self { caller: caller.clone(), recipient, amount, fee, is_public: false, records: Vec::new() }
+    }
+
+    /// Sets the visibility of the transfer.
+    pub fn is_public(mut self, is_public: bool) -> Self {
+        self.is_public = is_public;
+        self
+    }
+
+    /// Adds the given unspent record into the builder.
+    pub fn add_record(mut self, record: Record<N>) -> Self {
+        self.records.push(record);
+        self
+    }
+
+    /// Adds the given unspent records into the builder.
+    pub fn add_records(mut self, records: Vec<Record<N>>) -> Self {
+        self.records.extend(records);
+        self
+    }
+
+    ///
+    /// Returns the maximum number of input records of a transfer, which is the number of records
+    /// the first transition consumes, plus the number of ledger records each subsequent transition
+    /// consumes alongside the record of the previous transition.
+    ///
+    pub fn max_input_records() -> usize {
+        N::NUM_INPUT_RECORDS + (N::NUM_TRANSITIONS as usize - 1) * (N::NUM_INPUT_RECORDS - 1)
+    }
+
+    ///
+    /// Returns the input records for the transfer, selected from the unspent records.
+    /// If one record covers the amount and fee, the smallest such record is selected.
+    /// Otherwise, the largest records are selected until they cover the amount and fee.
+    ///
+    pub fn select_records(&self) -> Result<Vec<Record<N>>> {
+        // Ensure the amount and fee are non-negative.
+        if self.amount.is_negative() || self.fee.is_negative() {
+            return Err(anyhow!("The transfer amount and fee must be non-negative"));
+        }
+
+        let caller = Address::from_private_key(&self.caller);
+        let target = self.amount.add(self.fee);
+
+        // Retrieve the unique, non-empty records of the caller, that are not bound to a program.
+        let mut candidates: Vec<&Record<N>> = Vec::with_capacity(self.records.len());
+        for record in &self.records {
+            if record.owner() != caller {
+                return Err(anyhow!("Record {} is not owned by the caller", record.commitment()));
+            }
+            if !record.is_dummy()
+                && record.program_id() == *N::noop_program_id()
+                && !candidates.iter().any(|candidate| candidate.commitment() == record.commitment())
+            {
+                candidates.push(record);
+            }
+        }
+        candidates.sort_by_key(|record| record.value().0);
+
+        // Select the smallest record that covers the target, if one exists.
+        if let Some(record) = candidates.iter().find(|record| record.value() >= target) {
+            return Ok(vec![(*record).clone()]);
+        }
+
+        // Otherwise, select the largest records until they cover the target.
+        let mut selected = Vec::new();
+        let mut balance = AleoAmount::ZERO;
+        for record in candidates.into_iter().rev() {
+            if balance >= target {
+                break;
+            }
+            balance = balance.add(record.value());
+            selected.push(record.clone());
+        }
+
+        if balance < target {
+            return Err(VMError::BalanceInsufficient.into());
+        }
+        if selected.len() > Self::max_input_records() {
+            return Err(anyhow!(
+                "The transfer requires {} input records, which exceeds the maximum of {}",
+                selected.len(),
+                Self::max_input_records()
+            ));
+        }
+        Ok(selected)
+    }
+
+    /// Selects the input records and returns a finalized transfer transaction, with ledger proofs from the given ledger.
+    pub fn build<R: Rng + CryptoRng>(&self, ledger: &Ledger<N>, rng: &mut R) -> Result<Transaction<N>> {
+        let caller = Address::from_private_key(&self.caller);
+
+        // Prepare the input records with their ledger proofs.
+        let mut inputs = VecDeque::new();
+        for record in self.select_records()? {
+            let ledger_proof = ledger.to_ledger_proof(record.commitment())?;
+            inputs.push_back((record, ledger_proof));
+        }
+
+        let mut vm = VirtualMachine::<N>::new(ledger.latest_ledger_root())?;
+        loop {
+            // Take the next input records for the transition.
+            let (records, mut ledger_proofs): (Vec<_>, Vec<_>) =
+                inputs.drain(..std::cmp::min(N::NUM_INPUT_RECORDS, inputs.len())).unzip();
+            ledger_proofs.resize(N::NUM_INPUT_RECORDS, Default::default());
+
+            // If all input records are consumed, perform the final transition to the recipient.
+            if inputs.is_empty() {
+                let operation = match records.len() {
+                    1 => Operation::Evaluate(
+                        *N::noop_function_id(),
+                        FunctionType::Split,
+                        FunctionInputs::new(&caller, &self.recipient, self.amount, Default::default()),
+                    ),
+                    _ => Operation::Transfer(caller, self.recipient, self.amount),
+                };
+                let request =
+                    Request::new(&self.caller, records, ledger_proofs, operation, self.fee, self.is_public, rng)?;
+                let (next_vm, _) = vm.execute(&request, rng)?;
+                return next_vm.finalize();
+            }
+
+            // Otherwise, join the input records into one record of the caller.
+            let balance: AleoAmount = records.iter().map(Record::value).sum();
+            let operation = Operation::Evaluate(
+                *N::noop_function_id(),
+                FunctionType::Join,
+                FunctionInputs::new(&caller, &caller, balance, Default::default()),
+            );
+            let request =
+                Request::new(&self.caller, records, ledger_proofs, operation, AleoAmount::ZERO, self.is_public, rng)?;
+            let (next_vm, response) = vm.execute(&request, rng)?;
+            vm = next_vm;
+
+            // Consume the joined record in the next transition, with a proof of its inclusion in the local transitions.
+            let joined_record = response.records()[0].clone();
+            let ledger_proof = LedgerProof::new_dummy(vm.to_local_proof(joined_record.commitment())?)?;
+            inputs.push_front((joined_record, ledger_proof));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    use rand::thread_rng;
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn test_select_records() {
+        let rng = &mut thread_rng();
+        let account = Account::<Testnet2>::new(rng);
+        let recipient = Account::<Testnet2>::new(rng).address();
+
+        let records: Vec<_> = [10, 30, 20]
+            .iter()
+            .map(|value| {
+                Output::new(account.address(), AleoAmount::from_i64(*value), Default::default(), None)
+                    .unwrap()
+                    .to_record(rng)
+                    .unwrap()
+                    .0
+            })
+            .collect();
+        let select = |amount: i64| {
+            TransferBuilder::new(
+                account.private_key(),
+                recipient,
+                AleoAmount::from_i64(amount),
+                AleoAmount::from_i64(1),
+            )
+            .add_records(records.clone())
+            .select_records()
+            .map(|selected| selected.iter().map(|record| record.value().0).collect::<Vec<_>>())
+        };
+
+        // Ensure the smallest record that covers the amount and fee is selected.
+        assert_eq!(vec![20], select(15).unwrap());
+        // Ensure the largest records are selected when no single record covers the amount and fee.
+        assert_eq!(vec![30, 20], select(40).unwrap());
+        assert_eq!(vec![30, 20, 10], select(59).unwrap());
+        // Ensure the selection fails when the records do not cover the amount and fee.
+        assert!(select(60).is_err());
+
+        // Ensure records owned by another account are rejected.
+        let other_account = Account::<Testnet2>::new(rng);
+        let builder = TransferBuilder::new(other_account.private_key(), recipient, AleoAmount::ZERO, AleoAmount::ZERO);
+        assert!(builder.add_records(records).select_records().is_err());
+    }
+
+    #[test]
+    fn test_build() {
+        let rng = &mut thread_rng();
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let account = Account::<Testnet2>::new(rng);
+        let recipient = Account::<Testnet2>::new(rng);
+
+        let mut records = Vec::new();
+        for _ in 0..3 {
+            records.push(ledger.mine_next_block(account.address(), true, &AtomicBool::new(false), rng).unwrap());
+        }
+
+        // Transfer an amount that requires all 3 records, which are joined before the final transition.
+        let amount = records[0].value().add(records[1].value());
+        let fee = AleoAmount::from_i64(1);
+        let transaction = TransferBuilder::new(account.private_key(), recipient.address(), amount, fee)
+            .is_public(true)
+            .add_records(records)
+            .build(&ledger, rng)
+            .unwrap();
+        assert_eq!(2, transaction.transitions().len());
+        assert_eq!(fee, transaction.value_balance());
+
+        // Ensure the transfer is accepted by the ledger, and the recipient receives the amount.
+        ledger.add_unconfirmed_transaction(&transaction).unwrap();
+        ledger.mine_next_block(account.address(), true, &AtomicBool::new(false), rng).unwrap();
+        assert!(ledger.contains_transaction(&transaction));
+
+        let compute_key = ComputeKey::from_private_key(recipient.private_key());
+        let mut wallet = WalletScanner::new(recipient.view_key(), &compute_key).unwrap();
+        wallet.scan(&ledger).unwrap();
+        assert_eq!(amount, wallet.balance());
+    }
+}
@@ -0,0 +1,360 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::prelude::*;
+
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeMap, HashMap};
+
+/// A record owned by the wallet account, along with its scanned state.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"))]
+pub struct WalletRecord<N: Network> {
+    /// The owned record.
+    record: Record<N>,
+    /// The serial number of the record.
+    serial_number: N::SerialNumber,
+    /// The height of the block that created the record.
+    block_height: u32,
+    /// The height of the block that spent the record, if it is spent.
+    spent_block_height: Option<u32>,
+}
+
+impl<N: Network> WalletRecord<N> {
+    /// Returns the owned record.
+    pub fn record(&self) -> &Record<N> {
+        &self.record
+    }
+
+    /// Returns the serial number of the record.
+    pub fn serial_number(&self) -> N::SerialNumber {
+        self.serial_number
+    }
+
+    /// Returns the height of the block that created the record.
+    pub fn block_height(&self) -> u32 {
+        self.block_height
+    }
+
+    /// Returns the height of the block that spent the record, if it is spent.
+    pub fn spent_block_height(&self) -> Option<u32> {
+        self.spent_block_height
+    }
+
+    /// Returns `true` if the record is spent.
+    pub fn is_spent(&self) -> bool {
+        self.spent_block_height.is_some()
+    }
+}
+
+///
+/// Scans blocks for the records owned by an account, and tracks which of them are spent.
+/// The account view key decrypts the owned records, and the account compute key derives their serial numbers.
+///
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"))]
+pub struct WalletScanner<N: Network> {
+    /// The address of the account.
+    address: Address<N>,
+    /// The decryption key of the account.
+    decryption_key: DecryptionKey<N>,
+    /// The compute key of the account.
+    compute_key: ComputeKey<N>,
+    /// The height of the next block to scan.
+    next_block_height: u32,
+    /// The hashes of the most recently scanned blocks, up to the maximum fork depth, keyed by block height.
+    block_hashes: BTreeMap<u32, N::BlockHash>,
+    /// The records owned by the account, keyed by serial number.
+    records: HashMap<N::SerialNumber, WalletRecord<N>>,
+}
+
+impl<N: Network> WalletScanner<N> {
+    /// Initializes a new wallet scanner for the given account, starting from the genesis block.
+    pub fn new(view_key: &ViewKey<N>, compute_key: &ComputeKey<N>) -> Result<Self> {
+        Self::from_checkpoint(view_key, compute_key, 0)
+    }
+
+    ///
+    /// Initializes a new wallet scanner for the given account, starting from the given block height.
+    /// Records created before the given block height are not found by the wallet scanner.
+    ///
+    pub fn from_checkpoint(view_key: &ViewKey<N>, compute_key: &ComputeKey<N>, block_height: u32) -> Result<Self> {
+        // Ensure the view key and compute key belong to the same account.
+        let address = Address::from_view_key(view_key);
+        if address != Address::from_compute_key(compute_key) {
+            return Err(anyhow!("The view key and compute key belong to different accounts"));
+        }
+
+        Ok(Self {
+            address,
+            decryption_key: view_key.into(),
+            compute_key: compute_key.clone(),
+            next_block_height: block_height,
+            block_hashes: Default::default(),
+            records: Default::default(),
+        })
+    }
+
+    /// Returns the address of the account.
+    pub fn address(&self) -> Address<N> {
+        self.address
+    }
+
+    /// Returns the height of the next block to scan, from which a new wallet scanner may resume.
+    pub fn next_block_height(&self) -> u32 {
+        self.next_block_height
+    }
+
+    /// Returns the unspent balance of the account.
+    pub fn balance(&self) -> AleoAmount {
+        self.unspent_records().iter().map(|record| record.record().value()).sum()
+    }
+
+    /// Returns the owned records, in ascending order of block height.
+    pub fn records(&self) -> Vec<&WalletRecord<N>> {
+        let mut records: Vec<_> = self.records.values().collect();
+        records.sort_by_key(|record| record.block_height());
+        records
+    }
+
+    /// Returns the unspent owned records, in ascending order of block height.
+    pub fn unspent_records(&self) -> Vec<&WalletRecord<N>> {
+        self.records().into_iter().filter(|record| !record.is_spent()).collect()
+    }
+
+    ///
+    /// Scans the canon blocks of the given ledger, from the next block height to the latest block height,
+    /// and returns the number of scanned blocks. If the ledger switched to a fork since the last scan,
+    /// the wallet scanner first reverts to the last block it shares with the ledger.
+    ///
+    pub fn scan(&mut self, ledger: &Ledger<N>) -> Result<u32> {
+        // Revert to the last scanned block that remains on the canon chain.
+        let fork_height = self
+            .block_hashes
+            .iter()
+            .rev()
+            .find(|(height, block_hash)| ledger.get_block_hash(**height).ok().as_ref() == Some(*block_hash))
+            .map(|(height, _)| *height);
+        match fork_height {
+            Some(height) => self.revert_to(height)?,
+            None if !self.block_hashes.is_empty() => {
+                return Err(anyhow!(
+                    "The scanned blocks do not connect to the canon chain within the maximum fork depth"
+                ));
+            }
+            None => (),
+        }
+
+        let start_block_height = self.next_block_height;
+        while self.next_block_height <= ledger.latest_block_height() {
+            self.scan_block(&ledger.get_block(self.next_block_height)?)?;
+        }
+
+        Ok(self.next_block_height.saturating_sub(start_block_height))
+    }
+
+    /// Scans the given block for owned records and spent serial numbers. The block must be the next block to scan.
+    pub fn scan_block(&mut self, block: &Block<N>) -> Result<()> {
+        // Ensure the block is the next block to scan.
+        if block.height() != self.next_block_height {
+            return Err(anyhow!("Expected to scan block {}, found block {}", self.next_block_height, block.height()));
+        }
+
+        // Ensure the block extends the last scanned block.
+        if let Some(previous_block_hash) =
+            block.height().checked_sub(1).and_then(|height| self.block_hashes.get(&height))
+        {
+            if *previous_block_hash != block.previous_block_hash() {
+                return Err(anyhow!("Block {} does not extend the last scanned block", block.height()));
+            }
+        }
+
+        // Scan the block. This code section executes atomically.
+        {
+            let mut wallet = self.clone();
+
+            // Add the owned records, which are decrypted with the account view key.
+            for transaction in block.transactions().iter() {
+                for record in transaction.to_decrypted_records(&wallet.decryption_key) {
+                    if record.is_dummy() || record.owner() != wallet.address {
+                        continue;
+                    }
+
+                    let serial_number = record.to_serial_number(&wallet.compute_key)?;
+                    wallet.records.insert(serial_number, WalletRecord {
+                        record,
+                        serial_number,
+                        block_height: block.height(),
+                        spent_block_height: None,
+                    });
+                }
+            }
+
+            // Mark the owned records that are spent in the block.
+            for serial_number in block.serial_numbers() {
+                if let Some(record) = wallet.records.get_mut(serial_number) {
+                    record.spent_block_height = Some(block.height());
+                }
+            }
+
+            // Update the scanned block hashes, retaining up to the maximum fork depth.
+            wallet.block_hashes.insert(block.height(), block.hash());
+            while wallet.block_hashes.len() > N::ALEO_MAXIMUM_FORK_DEPTH as usize {
+                let oldest_height = *wallet.block_hashes.keys().next().expect("Failed to fetch the oldest block");
+                wallet.block_hashes.remove(&oldest_height);
+            }
+            wallet.next_block_height = block.height() + 1;
+
+            *self = wallet;
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Reverts the wallet scanner to the given block height, forgetting the records created
+    /// and spends made in later blocks. The wallet scanner may only be reverted to a scanned block
+    /// within the maximum fork depth.
+    ///
+    pub fn revert_to(&mut self, height: u32) -> Result<()> {
+        // Ensure the given block height was scanned, and is within the maximum fork depth.
+        if !self.block_hashes.contains_key(&height) {
+            return Err(anyhow!("Cannot revert the wallet scanner to unknown block {}", height));
+        }
+
+        self.records.retain(|_, record| record.block_height <= height);
+        for record in self.records.values_mut() {
+            if record.spent_block_height.map_or(false, |spent_block_height| spent_block_height > height) {
+                record.spent_block_height = None;
+            }
+        }
+        self.block_hashes.retain(|block_height, _| *block_height <= height);
+        self.next_block_height = height + 1;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    use rand::thread_rng;
+    use std::sync::atomic::AtomicBool;
+
+    fn new_wallet_scanner(account: &Account<Testnet2>) -> WalletScanner<Testnet2> {
+        WalletScanner::new(account.view_key(), &ComputeKey::from_private_key(account.private_key())).unwrap()
+    }
+
+    #[test]
+    fn test_mismatched_account() {
+        let rng = &mut thread_rng();
+        let account = Account::<Testnet2>::new(rng);
+        let other_account = Account::<Testnet2>::new(rng);
+
+        let compute_key = ComputeKey::from_private_key(other_account.private_key());
+        assert!(WalletScanner::new(account.view_key(), &compute_key).is_err());
+    }
+
+    #[test]
+    fn test_scan() {
+        let rng = &mut thread_rng();
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let account = Account::<Testnet2>::new(rng);
+        let recipient = Account::<Testnet2>::new(rng);
+
+        let mut wallet = new_wallet_scanner(&account);
+        let mut recipient_wallet = new_wallet_scanner(&recipient);
+
+        // Mine 2 blocks to the account.
+        let record = ledger.mine_next_block(account.address(), true, &AtomicBool::new(false), rng).unwrap();
+        ledger.mine_next_block(account.address(), true, &AtomicBool::new(false), rng).unwrap();
+
+        assert_eq!(3, wallet.scan(&ledger).unwrap());
+        assert_eq!(3, wallet.next_block_height());
+        assert_eq!(2, wallet.unspent_records().len());
+        assert_eq!(Block::<Testnet2>::block_reward(1).add(Block::<Testnet2>::block_reward(2)), wallet.balance());
+
+        // Spend the first record, and mine the transfer into a block to the recipient.
+        let amount = AleoAmount::from_i64(100);
+        let fee = AleoAmount::from_i64(1);
+        let request = Request::new_transfer(
+            account.private_key(),
+            vec![record.clone()],
+            vec![ledger.to_ledger_proof(record.commitment()).unwrap(), Default::default()],
+            recipient.address(),
+            amount,
+            fee,
+            true,
+            rng,
+        )
+        .unwrap();
+        let transaction = Transaction::new(ledger.to_ledger_tree().clone(), &request, rng).unwrap();
+        ledger.add_unconfirmed_transaction(&transaction).unwrap();
+        ledger.mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng).unwrap();
+
+        // Ensure the spent record is marked as spent, and the change record is found.
+        assert_eq!(1, wallet.scan(&ledger).unwrap());
+        let spent_record =
+            wallet.records().into_iter().find(|wallet_record| wallet_record.record() == &record).unwrap();
+        assert_eq!(Some(3), spent_record.spent_block_height());
+        assert_eq!(2, wallet.unspent_records().len());
+        assert_eq!(
+            Block::<Testnet2>::block_reward(1).add(Block::<Testnet2>::block_reward(2)).sub(amount).sub(fee),
+            wallet.balance()
+        );
+
+        // Ensure the recipient finds the transferred record and the coinbase record.
+        assert_eq!(4, recipient_wallet.scan(&ledger).unwrap());
+        assert_eq!(Block::<Testnet2>::block_reward(3).add(amount), recipient_wallet.balance());
+
+        // Ensure a rescan of the same ledger is a no-op.
+        assert_eq!(0, wallet.scan(&ledger).unwrap());
+    }
+
+    #[test]
+    fn test_scan_from_checkpoint_and_fork() {
+        let rng = &mut thread_rng();
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let account = Account::<Testnet2>::new(rng);
+        let other_account = Account::<Testnet2>::new(rng);
+
+        ledger.mine_next_block(account.address(), true, &AtomicBool::new(false), rng).unwrap();
+        ledger.mine_next_block(account.address(), true, &AtomicBool::new(false), rng).unwrap();
+
+        // Ensure a wallet scanner resumed from a checkpoint skips the earlier blocks.
+        let compute_key = ComputeKey::from_private_key(account.private_key());
+        let mut wallet = WalletScanner::from_checkpoint(account.view_key(), &compute_key, 2).unwrap();
+        assert_eq!(1, wallet.scan(&ledger).unwrap());
+        assert_eq!(Block::<Testnet2>::block_reward(2), wallet.balance());
+
+        // Ensure the wallet scanner forgets the records from blocks that leave the canon chain.
+        ledger.revert_to(1).unwrap();
+        ledger.mine_next_block(other_account.address(), true, &AtomicBool::new(false), rng).unwrap();
+        assert!(wallet.scan(&ledger).is_err());
+
+        let mut wallet = new_wallet_scanner(&account);
+        ledger.mine_next_block(account.address(), true, &AtomicBool::new(false), rng).unwrap();
+        assert_eq!(4, wallet.scan(&ledger).unwrap());
+        ledger.revert_to(2).unwrap();
+        ledger.mine_next_block(other_account.address(), true, &AtomicBool::new(false), rng).unwrap();
+        assert_eq!(1, wallet.scan(&ledger).unwrap());
+        assert_eq!(Block::<Testnet2>::block_reward(1), wallet.balance());
+    }
+}
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use std::fmt::Debug;
+use std::{fmt::Debug, path::PathBuf};
 
 #[derive(Debug, Error)]
 pub enum ParameterError {
@@ -32,6 +32,12 @@ pub enum ParameterError {
 
     #[error("Expected size of {}, found size of {}", _0, _1)]
     SizeMismatch(usize, usize),
+
+    #[error("{} is unavailable from the parameter cache and mirrors: {}", _0, _1)]
+    Unavailable(String, String),
+
+    #[error("{} is unavailable offline, please seed the parameter cache at {:?} or add a file:// mirror", _0, _1)]
+    UnavailableOffline(String, PathBuf),
 }
 
 #[cfg(not(feature = "wasm"))]
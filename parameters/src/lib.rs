@@ -30,6 +30,11 @@ pub mod macros;
 pub mod errors;
 pub use errors::*;
 
+#[cfg(not(feature = "wasm"))]
+pub mod resolver;
+#[cfg(not(feature = "wasm"))]
+pub use resolver::*;
+
 pub mod testnet1;
 
 pub mod testnet2;
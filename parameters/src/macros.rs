@@ -92,76 +92,43 @@ macro_rules! impl_remote {
                     _ => concat!($fname, $ftype).to_string()
                 };
 
-                // Compose the correct file path for the parameter file.
-                let mut file_path = aleo_std::aleo_dir();
-                file_path.push($local_dir);
-                file_path.push(&filename);
-
-                let buffer = if file_path.exists() {
-                    // Attempts to load the parameter file locally with an absolute path.
-                    std::fs::read(file_path)?
-                } else {
-                    // Downloads the missing parameters and stores it in the local directory for use.
-                    eprintln!(
-                        "\nATTENTION - \"{}\" does not exist, downloading this file remotely and storing it locally. Please ensure \"{}\" is stored in {:?}.\n",
-                        filename, filename, file_path
-                    );
-
-                    // Load remote file
-                    cfg_if::cfg_if! {
-                        if #[cfg(not(feature = "wasm"))] {
-                            #[cfg(not(feature = "no_std_out"))]
-                            println!("{} - Downloading parameters...", module_path!());
-
-
-                            let mut buffer = vec![];
-                            Self::remote_fetch(&mut buffer, &format!("{}/{}", $remote_url, filename))?;
-
-                            #[cfg(not(feature = "no_std_out"))]
-                            println!("\n{} - Download complete", module_path!());
-
-                            // Ensure the checksum matches.
-                            let candidate_checksum = checksum!(&buffer);
-                            if expected_checksum != candidate_checksum {
-                                return checksum_error!(expected_checksum, candidate_checksum)
-                            }
-
-                            match Self::store_bytes(&buffer, &file_path) {
-                                Ok(()) => buffer,
-                                Err(_) => {
-                                    eprintln!(
-                                        "\nATTENTION - Failed to store \"{}\" locally. Please download this file manually and ensure it is stored in {:?}.\n",
-                                        filename, file_path
-                                    );
-                                    buffer
-                                }
-                            }
-                        } else if #[cfg(feature = "wasm")] {
-                            let buffer = alloc::sync::Arc::new(parking_lot::RwLock::new(vec![]));
-                            let url = String::from($remote_url);
-
-                            // NOTE(julesdesmit): I'm leaking memory here so that I can get a
-                            // static reference to the url, which is needed to pass it into
-                            // the local thread which downloads the file.
-                            let url = Box::leak(url.into_boxed_str());
-
-                            let buffer_clone = alloc::sync::Arc::downgrade(&buffer);
-                            Self::remote_fetch(buffer_clone, url)?;
-
-                            // Recover the bytes.
-                            let buffer = alloc::sync::Arc::try_unwrap(buffer).unwrap();
-                            let buffer = buffer.write().clone();
-
-                            // Ensure the checksum matches.
-                            let candidate_checksum = checksum!(&buffer);
-                            if expected_checksum != candidate_checksum {
-                                return checksum_error!(expected_checksum, candidate_checksum)
-                            }
-
-                            buffer
-                        } else {
-                            return Err(crate::errors::ParameterError::RemoteFetchDisabled);
+                // Resolve the parameter file from the parameter cache and mirrors.
+                #[cfg(not(feature = "wasm"))]
+                let buffer = crate::resolver::ParameterResolver::global().resolve(&filename, &expected_checksum, expected_size, $remote_url)?;
+
+                #[cfg(feature = "wasm")]
+                let buffer = {
+                    // Compose the correct file path for the parameter file.
+                    let mut file_path = aleo_std::aleo_dir();
+                    file_path.push($local_dir);
+                    file_path.push(&filename);
+
+                    if file_path.exists() {
+                        // Attempts to load the parameter file locally with an absolute path.
+                        std::fs::read(file_path)?
+                    } else {
+                        let buffer = alloc::sync::Arc::new(parking_lot::RwLock::new(vec![]));
+                        let url = String::from($remote_url);
+
+                        // NOTE(julesdesmit): I'm leaking memory here so that I can get a
+                        // static reference to the url, which is needed to pass it into
+                        // the local thread which downloads the file.
+                        let url = Box::leak(url.into_boxed_str());
+
+                        let buffer_clone = alloc::sync::Arc::downgrade(&buffer);
+                        Self::remote_fetch(buffer_clone, url)?;
+
+                        // Recover the bytes.
+                        let buffer = alloc::sync::Arc::try_unwrap(buffer).unwrap();
+                        let buffer = buffer.write().clone();
+
+                        // Ensure the checksum matches.
+                        let candidate_checksum = checksum!(&buffer);
+                        if expected_checksum != candidate_checksum {
+                            return checksum_error!(expected_checksum, candidate_checksum)
                         }
+
+                        buffer
                     }
                 };
 
@@ -179,57 +146,6 @@ macro_rules! impl_remote {
                 return Ok(buffer)
             }
 
-            #[cfg(not(feature = "wasm"))]
-            fn store_bytes(
-                buffer: &[u8],
-                file_path: &std::path::Path,
-            ) -> Result<(), crate::errors::ParameterError> {
-                use snarkvm_utilities::Write;
-
-                #[cfg(not(feature = "no_std_out"))]
-                println!("{} - Storing parameters ({:?})", module_path!(), file_path);
-
-                // Ensure the folders up to the file path all exist.
-                let mut directory_path = file_path.to_path_buf();
-                directory_path.pop();
-                let _ = std::fs::create_dir_all(directory_path)?;
-
-                // Attempt to write the parameter buffer to a file.
-                match std::fs::File::create(file_path) {
-                    Ok(mut file) => file.write_all(&buffer)?,
-                    Err(error) => eprintln!("{}", error)
-                }
-                Ok(())
-            }
-
-            #[cfg(not(feature = "wasm"))]
-            fn remote_fetch(buffer: &mut Vec<u8>, url: &str) -> Result<(), crate::errors::ParameterError> {
-                let mut easy = curl::easy::Easy::new();
-                easy.url(url)?;
-                #[cfg(not(feature = "no_std_out"))]
-                {
-                    easy.progress(true)?;
-                    easy.progress_function(|total_download, current_download, _, _| {
-                        let percent = (current_download / total_download) * 100.0;
-                        let size_in_megabytes = total_download as u64 / 1_048_576;
-                        print!(
-                            "\r{} - {:.2}% complete ({:#} MB total)",
-                            module_path!(),
-                            percent,
-                            size_in_megabytes
-                        );
-                        true
-                    })?;
-                }
-
-                let mut transfer = easy.transfer();
-                transfer.write_function(|data| {
-                    buffer.extend_from_slice(data);
-                    Ok(data.len())
-                })?;
-                Ok(transfer.perform()?)
-            }
-
             #[cfg(feature = "wasm")]
             fn remote_fetch(buffer: alloc::sync::Weak<parking_lot::RwLock<Vec<u8>>>, url: &'static str) -> Result<(), crate::errors::ParameterError> {
                 // NOTE(julesdesmit): We spawn a local thread here in order to be
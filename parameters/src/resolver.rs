@@ -0,0 +1,412 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::errors::ParameterError;
+
+use once_cell::sync::OnceCell;
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// The environment variable of the parameter cache directory.
+pub const PARAMETERS_DIRECTORY_ENV: &str = "SNARKVM_PARAMETERS_DIRECTORY";
+/// The environment variable of the comma-separated parameter mirrors, which are `http(s)://` or `file://` URLs.
+pub const PARAMETERS_MIRRORS_ENV: &str = "SNARKVM_PARAMETERS_MIRRORS";
+/// The environment variable which disables network access, if it is set to `1` or `true`.
+pub const PARAMETERS_OFFLINE_ENV: &str = "SNARKVM_PARAMETERS_OFFLINE";
+
+/// The size of a block in a tar archive.
+const TAR_BLOCK_SIZE: usize = 512;
+
+/// The parameter resolver used by remote parameters, if it is set.
+static GLOBAL_RESOLVER: OnceCell<ParameterResolver> = OnceCell::new();
+
+///
+/// Resolves remote parameter files, first from the cache directory, and then from the mirrors in order.
+/// Each file is verified against its expected checksum and size, and is stored in the cache directory
+/// once it is retrieved from a mirror. In offline mode, only the cache directory and `file://` mirrors are used.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParameterResolver {
+    /// The directory of the cached parameter files.
+    cache_directory: PathBuf,
+    /// The mirrors of the parameter files, if they override the default mirror.
+    mirrors: Option<Vec<String>>,
+    /// If `true`, the parameter files are never fetched over the network.
+    is_offline: bool,
+}
+
+impl ParameterResolver {
+    /// Initializes a new parameter resolver with the given cache directory, which uses the default mirror.
+    pub fn new<P: AsRef<Path>>(cache_directory: P) -> Self {
+        Self { cache_directory: cache_directory.as_ref().to_path_buf(), mirrors: None, is_offline: false }
+    }
+
+    ///
+    /// Initializes a new parameter resolver from the environment variables, which defaults to
+    /// the `resources` folder in the Aleo directory, the default mirror, and network access.
+    ///
+    pub fn from_env() -> Self {
+        let cache_directory = match std::env::var_os(PARAMETERS_DIRECTORY_ENV) {
+            Some(directory) => PathBuf::from(directory),
+            None => aleo_std::aleo_dir().join("resources"),
+        };
+        let mirrors = std::env::var(PARAMETERS_MIRRORS_ENV).ok().map(|mirrors| {
+            mirrors.split(',').map(str::trim).filter(|mirror| !mirror.is_empty()).map(str::to_string).collect()
+        });
+        let is_offline = matches!(std::env::var(PARAMETERS_OFFLINE_ENV).as_deref(), Ok("1") | Ok("true"));
+
+        Self { cache_directory, mirrors, is_offline }
+    }
+
+    /// Sets the mirrors of the parameter files, which override the default mirror.
+    pub fn with_mirrors(mut self, mirrors: Vec<String>) -> Self {
+        self.mirrors = Some(mirrors);
+        self
+    }
+
+    /// Sets the offline mode, which disables fetching the parameter files over the network.
+    pub fn offline(mut self, is_offline: bool) -> Self {
+        self.is_offline = is_offline;
+        self
+    }
+
+    ///
+    /// Sets the parameter resolver used by remote parameters, in place of the one from the environment variables.
+    /// The parameter resolver may only be set once, before any remote parameters are loaded.
+    ///
+    pub fn set_global(self) -> Result<(), ParameterError> {
+        GLOBAL_RESOLVER
+            .set(self)
+            .map_err(|_| ParameterError::Message("The global parameter resolver is already set".to_string()))
+    }
+
+    /// Returns the parameter resolver used by remote parameters.
+    pub fn global() -> &'static Self {
+        GLOBAL_RESOLVER.get_or_init(Self::from_env)
+    }
+
+    /// Returns the directory of the cached parameter files.
+    pub fn cache_directory(&self) -> &Path {
+        &self.cache_directory
+    }
+
+    /// Returns the mirrors of the parameter files, in order, given the default mirror.
+    pub fn mirrors(&self, default_mirror: &str) -> Vec<String> {
+        match &self.mirrors {
+            Some(mirrors) => mirrors.clone(),
+            None => vec![default_mirror.to_string()],
+        }
+    }
+
+    /// Returns `true` if the parameter files are never fetched over the network.
+    pub fn is_offline(&self) -> bool {
+        self.is_offline
+    }
+
+    ///
+    /// Returns the bytes of the given parameter file, if they match the expected checksum and size.
+    /// The file is loaded from the cache directory if it is valid there, and otherwise from the mirrors in order.
+    ///
+    pub fn resolve(
+        &self,
+        filename: &str,
+        expected_checksum: &str,
+        expected_size: usize,
+        default_mirror: &str,
+    ) -> Result<Vec<u8>, ParameterError> {
+        let mut errors = Vec::new();
+
+        // Attempt to load the parameter file from the cache directory.
+        let file_path = self.cache_directory.join(filename);
+        if file_path.exists() {
+            match fs::read(&file_path)
+                .map_err(ParameterError::from)
+                .and_then(|buffer| Self::verify(buffer, expected_checksum, expected_size))
+            {
+                Ok(buffer) => return Ok(buffer),
+                Err(error) => errors.push(format!("{:?}: {}", file_path, error)),
+            }
+        }
+
+        // Attempt to load the parameter file from each mirror.
+        for mirror in self.mirrors(default_mirror) {
+            let buffer = match mirror.strip_prefix("file://") {
+                Some(directory) => fs::read(Path::new(directory).join(filename)).map_err(ParameterError::from),
+                None if self.is_offline => continue,
+                None => Self::remote_fetch(&format!("{}/{}", mirror.trim_end_matches('/'), filename)),
+            };
+
+            match buffer.and_then(|buffer| Self::verify(buffer, expected_checksum, expected_size)) {
+                Ok(buffer) => {
+                    // Store the parameter file in the cache directory, which may be read-only.
+                    if let Err(error) = Self::store_bytes(&buffer, &file_path) {
+                        eprintln!("\nATTENTION - Failed to store \"{}\" in {:?}: {}\n", filename, file_path, error);
+                    }
+                    return Ok(buffer);
+                }
+                Err(error) => errors.push(format!("{}: {}", mirror, error)),
+            }
+        }
+
+        match self.is_offline {
+            true => Err(ParameterError::UnavailableOffline(filename.to_string(), self.cache_directory.clone())),
+            false => Err(ParameterError::Unavailable(filename.to_string(), errors.join("; "))),
+        }
+    }
+
+    ///
+    /// Extracts the parameter files from the given (uncompressed) tar archive into the cache directory,
+    /// and returns their filenames. Versioned filenames are verified against the checksum prefix they contain.
+    ///
+    pub fn seed_from_tarball<P: AsRef<Path>>(&self, tarball: P) -> Result<Vec<String>, ParameterError> {
+        let mut archive = Vec::new();
+        fs::File::open(tarball)?.read_to_end(&mut archive)?;
+
+        let mut filenames = Vec::new();
+        let mut offset = 0;
+        while offset + TAR_BLOCK_SIZE <= archive.len() {
+            let header = &archive[offset..offset + TAR_BLOCK_SIZE];
+            // The archive ends with an empty block.
+            if header.iter().all(|byte| *byte == 0) {
+                break;
+            }
+
+            let path = Self::tar_string(&header[345..500]) + &Self::tar_string(&header[0..100]);
+            let size = usize::from_str_radix(Self::tar_string(&header[124..136]).trim(), 8)
+                .map_err(|_| ParameterError::Message(format!("Invalid size of tar entry {}", path)))?;
+            let start = offset + TAR_BLOCK_SIZE;
+            let end = start + size;
+            if end > archive.len() {
+                return Err(ParameterError::Message(format!("Tar entry {} is truncated", path)));
+            }
+
+            // Extract the regular files, ignoring their directories.
+            let is_file = header[156] == b'0' || header[156] == 0;
+            let filename = Path::new(&path).file_name().and_then(|filename| filename.to_str()).map(str::to_string);
+            if let (true, Some(filename)) = (is_file, filename) {
+                let buffer = &archive[start..end];
+
+                // Ensure the checksum prefix of a versioned filename matches.
+                if let Some(sum) = Self::checksum_prefix(&filename) {
+                    let candidate_checksum = checksum!(buffer);
+                    if !candidate_checksum.starts_with(sum) {
+                        return checksum_error!(sum.to_string(), candidate_checksum);
+                    }
+                }
+
+                Self::store_bytes(buffer, &self.cache_directory.join(&filename))?;
+                filenames.push(filename);
+            }
+
+            offset = start + (size + TAR_BLOCK_SIZE - 1) / TAR_BLOCK_SIZE * TAR_BLOCK_SIZE;
+        }
+
+        Ok(filenames)
+    }
+
+    /// Returns the given buffer, if it matches the expected checksum and size.
+    fn verify(buffer: Vec<u8>, expected_checksum: &str, expected_size: usize) -> Result<Vec<u8>, ParameterError> {
+        // Ensure the size matches.
+        if expected_size != buffer.len() {
+            return Err(ParameterError::SizeMismatch(expected_size, buffer.len()));
+        }
+
+        // Ensure the checksum matches.
+        let candidate_checksum = checksum!(buffer.as_slice());
+        if expected_checksum != candidate_checksum {
+            return checksum_error!(expected_checksum.to_string(), candidate_checksum);
+        }
+
+        Ok(buffer)
+    }
+
+    /// Returns the checksum prefix of a versioned filename, of the form `{name}.{type}.{checksum prefix}`.
+    fn checksum_prefix(filename: &str) -> Option<&str> {
+        let mut components = filename.rsplitn(3, '.');
+        match (components.next(), components.next(), components.next()) {
+            (Some(sum), Some(_), Some(_)) if sum.len() == 7 && sum.chars().all(|c| c.is_ascii_hexdigit()) => Some(sum),
+            _ => None,
+        }
+    }
+
+    /// Returns the given null-terminated string from a tar header.
+    fn tar_string(bytes: &[u8]) -> String {
+        let end = bytes.iter().position(|byte| *byte == 0).unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[..end]).to_string()
+    }
+
+    fn store_bytes(buffer: &[u8], file_path: &Path) -> Result<(), ParameterError> {
+        #[cfg(not(feature = "no_std_out"))]
+        println!("{} - Storing parameters ({:?})", module_path!(), file_path);
+
+        // Ensure the folders up to the file path all exist.
+        if let Some(directory_path) = file_path.parent() {
+            fs::create_dir_all(directory_path)?;
+        }
+
+        // Write to a temporary file first, so an interrupted write does not leave a partial file.
+        let temporary_path = file_path.with_extension("partial");
+        fs::write(&temporary_path, buffer)?;
+        fs::rename(&temporary_path, file_path)?;
+        Ok(())
+    }
+
+    #[cfg(all(feature = "remote", not(target_family = "wasm")))]
+    fn remote_fetch(url: &str) -> Result<Vec<u8>, ParameterError> {
+        #[cfg(not(feature = "no_std_out"))]
+        println!("{} - Downloading parameters from {}", module_path!(), url);
+
+        let mut buffer = vec![];
+        let mut easy = curl::easy::Easy::new();
+        easy.url(url)?;
+        easy.fail_on_error(true)?;
+        #[cfg(not(feature = "no_std_out"))]
+        {
+            easy.progress(true)?;
+            easy.progress_function(|total_download, current_download, _, _| {
+                let percent = (current_download / total_download) * 100.0;
+                let size_in_megabytes = total_download as u64 / 1_048_576;
+                print!("\r{} - {:.2}% complete ({:#} MB total)", module_path!(), percent, size_in_megabytes);
+                true
+            })?;
+        }
+
+        {
+            let mut transfer = easy.transfer();
+            transfer.write_function(|data| {
+                buffer.extend_from_slice(data);
+                Ok(data.len())
+            })?;
+            transfer.perform()?;
+        }
+
+        #[cfg(not(feature = "no_std_out"))]
+        println!("\n{} - Download complete", module_path!());
+
+        Ok(buffer)
+    }
+
+    #[cfg(not(all(feature = "remote", not(target_family = "wasm"))))]
+    fn remote_fetch(_url: &str) -> Result<Vec<u8>, ParameterError> {
+        Err(ParameterError::RemoteFetchDisabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{thread_rng, Rng};
+
+    /// Returns a new, empty temporary directory.
+    fn temporary_directory() -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("snarkvm-parameters-{}", thread_rng().gen::<u64>()));
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    /// Returns an uncompressed tar archive of the given files.
+    fn tarball(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut archive = Vec::new();
+        for (filename, bytes) in files {
+            let mut header = [0u8; TAR_BLOCK_SIZE];
+            header[..filename.len()].copy_from_slice(filename.as_bytes());
+            header[124..135].copy_from_slice(format!("{:011o}", bytes.len()).as_bytes());
+            header[156] = b'0';
+            archive.extend_from_slice(&header);
+            archive.extend_from_slice(bytes);
+            archive.resize((archive.len() + TAR_BLOCK_SIZE - 1) / TAR_BLOCK_SIZE * TAR_BLOCK_SIZE, 0);
+        }
+        archive.extend_from_slice(&[0u8; 2 * TAR_BLOCK_SIZE]);
+        archive
+    }
+
+    #[test]
+    fn test_resolve_from_file_mirror() {
+        let (mirror, cache) = (temporary_directory(), temporary_directory());
+        let bytes = b"parameters".to_vec();
+        let checksum = checksum!(&bytes);
+        fs::write(mirror.join("test.proving"), &bytes).unwrap();
+
+        let resolver =
+            ParameterResolver::new(&cache).with_mirrors(vec![format!("file://{}", mirror.display())]).offline(true);
+
+        // Ensure the parameter file is verified, and stored in the cache directory.
+        assert!(resolver.resolve("test.proving", &"0".repeat(64), bytes.len(), "").is_err());
+        assert_eq!(bytes, resolver.resolve("test.proving", &checksum, bytes.len(), "").unwrap());
+        assert_eq!(bytes, fs::read(cache.join("test.proving")).unwrap());
+
+        // Ensure the parameter file is loaded from the cache directory, once the mirror is gone.
+        fs::remove_dir_all(&mirror).unwrap();
+        assert_eq!(bytes, resolver.resolve("test.proving", &checksum, bytes.len(), "").unwrap());
+
+        // Ensure a corrupted cached parameter file is rejected.
+        fs::write(cache.join("test.proving"), b"corrupted!").unwrap();
+        assert!(resolver.resolve("test.proving", &checksum, bytes.len(), "").is_err());
+
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_offline() {
+        let cache = temporary_directory();
+        let resolver = ParameterResolver::new(&cache).offline(true);
+
+        // Ensure the network is not accessed in offline mode.
+        match resolver.resolve("test.proving", &"0".repeat(64), 10, "https://localhost:1") {
+            Err(ParameterError::UnavailableOffline(filename, directory)) => {
+                assert_eq!("test.proving", filename);
+                assert_eq!(cache, directory);
+            }
+            result => panic!("Expected the parameter file to be unavailable offline, found {:?}", result),
+        }
+
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn test_seed_from_tarball() {
+        let (directory, cache) = (temporary_directory(), temporary_directory());
+        let bytes = b"parameters".to_vec();
+        let checksum = checksum!(&bytes);
+        let filename = format!("test.proving.{}", &checksum[0..7]);
+
+        // Ensure the versioned parameter files are extracted into the cache directory.
+        let tarball_path = directory.join("parameters.tar");
+        fs::write(
+            &tarball_path,
+            tarball(&[(format!("resources/{}", filename).as_str(), &bytes[..]), ("test.metadata", &b"{}"[..])]),
+        )
+        .unwrap();
+
+        let resolver = ParameterResolver::new(&cache).offline(true);
+        assert_eq!(
+            vec![filename.clone(), "test.metadata".to_string()],
+            resolver.seed_from_tarball(&tarball_path).unwrap()
+        );
+        assert_eq!(bytes, resolver.resolve(&filename, &checksum, bytes.len(), "").unwrap());
+
+        // Ensure a versioned parameter file with the wrong checksum is rejected.
+        fs::write(&tarball_path, tarball(&[("test.proving.0000000", &bytes[..])])).unwrap();
+        assert!(resolver.seed_from_tarball(&tarball_path).is_err());
+
+        fs::remove_dir_all(&directory).unwrap();
+        fs::remove_dir_all(&cache).unwrap();
+    }
+}
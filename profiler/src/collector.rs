@@ -0,0 +1,478 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+//! A collector for the spans recorded by `start_timer!` and `end_timer!`.
+//!
+//! While a collection is running, every timer is recorded as a span in a per-thread tree,
+//! along with its start offset, duration, and thread ID. The resulting [`Trace`] can be
+//! exported as JSON, in the Chrome trace-event format, or aggregated by span path.
+
+use once_cell::sync::Lazy;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{Display, Write},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// The separator used to join span names into a path in [`Trace::aggregate`].
+pub const PATH_SEPARATOR: &str = " > ";
+
+static IS_COLLECTING: AtomicBool = AtomicBool::new(false);
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+static STATE: Lazy<Mutex<State>> =
+    Lazy::new(|| Mutex::new(State { generation: 0, epoch: Instant::now(), spans: Vec::new() }));
+
+thread_local! {
+    static STACK: RefCell<Stack> = RefCell::new(Stack {
+        generation: 0,
+        epoch: Instant::now(),
+        thread_id: NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed),
+        spans: Vec::new(),
+    });
+}
+
+/// The root spans of the current collection.
+struct State {
+    generation: u64,
+    epoch: Instant,
+    spans: Vec<Span>,
+}
+
+/// The spans that are currently open on this thread.
+struct Stack {
+    generation: u64,
+    epoch: Instant,
+    thread_id: u64,
+    spans: Vec<OpenSpan>,
+}
+
+struct OpenSpan {
+    name: String,
+    start: Instant,
+    children: Vec<Span>,
+}
+
+/// A handle to a span opened by `start_timer!`, which is closed by `end_timer!`.
+#[derive(Debug)]
+pub struct SpanHandle(Option<(u64, usize)>);
+
+/// A completed span.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    /// The message given to `start_timer!`.
+    pub name: String,
+    /// The ID of the thread that recorded the span.
+    pub thread_id: u64,
+    /// The offset of the start of the span from the start of the collection.
+    pub start: Duration,
+    /// The duration of the span.
+    pub duration: Duration,
+    /// The spans that were recorded while this span was open, in start order.
+    pub children: Vec<Span>,
+}
+
+/// The aggregated timings of all spans with the same path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpanSummary {
+    /// The names of the span and its ancestors, joined by [`PATH_SEPARATOR`].
+    pub path: String,
+    /// The number of spans with this path.
+    pub count: usize,
+    /// The total duration of the spans with this path.
+    pub total: Duration,
+    /// The duration of the shortest span with this path.
+    pub min: Duration,
+    /// The duration of the longest span with this path.
+    pub max: Duration,
+}
+
+impl SpanSummary {
+    /// Returns the mean duration of the spans with this path.
+    pub fn mean(&self) -> Duration {
+        match self.count {
+            0 => Duration::default(),
+            count => Duration::from_nanos((self.total.as_nanos() / count as u128) as u64),
+        }
+    }
+}
+
+/// The spans recorded during a collection.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trace {
+    /// The root spans of each thread, in start order.
+    pub spans: Vec<Span>,
+}
+
+pub struct Collector;
+
+impl Collector {
+    /// Starts a new collection, discarding any spans from a previous collection.
+    pub fn start() {
+        let mut state = STATE.lock().unwrap();
+        state.generation += 1;
+        state.epoch = Instant::now();
+        state.spans.clear();
+
+        GENERATION.store(state.generation, Ordering::SeqCst);
+        IS_COLLECTING.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if a collection is running.
+    pub fn is_collecting() -> bool {
+        IS_COLLECTING.load(Ordering::Relaxed)
+    }
+
+    /// Stops the current collection, and returns the spans that were completed during it.
+    /// Spans that are still open are not included.
+    pub fn finish() -> Trace {
+        let mut state = STATE.lock().unwrap();
+        IS_COLLECTING.store(false, Ordering::SeqCst);
+
+        let mut spans = std::mem::take(&mut state.spans);
+        spans.sort_by_key(|span| (span.start, span.thread_id));
+        Trace { spans }
+    }
+}
+
+/// Opens a span on this thread, if a collection is running.
+/// The message is only evaluated if the span is recorded.
+pub fn enter_span<M: Display, F: FnOnce() -> M>(msg: F) -> SpanHandle {
+    if !Collector::is_collecting() {
+        return SpanHandle(None);
+    }
+
+    let generation = GENERATION.load(Ordering::SeqCst);
+    let name = msg().to_string();
+
+    STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        // Reset the stack if it was left over from a previous collection.
+        if stack.generation != generation {
+            stack.generation = generation;
+            stack.epoch = STATE.lock().unwrap().epoch;
+            stack.spans.clear();
+        }
+
+        stack.spans.push(OpenSpan { name, start: Instant::now(), children: Vec::new() });
+        SpanHandle(Some((generation, stack.spans.len())))
+    })
+}
+
+/// Closes the given span, along with any spans on this thread that were opened after it
+/// and left open.
+pub fn exit_span(handle: SpanHandle) {
+    let (generation, depth) = match handle.0 {
+        Some(handle) => handle,
+        None => return,
+    };
+    let end = Instant::now();
+
+    let root = STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        // Ignore spans from a previous collection, or that were opened on another thread.
+        if stack.generation != generation || stack.spans.len() < depth {
+            return None;
+        }
+
+        while stack.spans.len() > depth {
+            let span = close(&mut stack, end);
+            stack.spans.last_mut().unwrap().children.push(span);
+        }
+
+        let span = close(&mut stack, end);
+        match stack.spans.last_mut() {
+            Some(parent) => {
+                parent.children.push(span);
+                None
+            }
+            None => Some(span),
+        }
+    });
+
+    if let Some(span) = root {
+        let mut state = STATE.lock().unwrap();
+        if Collector::is_collecting() && state.generation == generation {
+            state.spans.push(span);
+        }
+    }
+}
+
+/// Pops the innermost open span from the given stack, and returns it as a completed span.
+fn close(stack: &mut Stack, end: Instant) -> Span {
+    let open = stack.spans.pop().unwrap();
+    Span {
+        name: open.name,
+        thread_id: stack.thread_id,
+        start: open.start.saturating_duration_since(stack.epoch),
+        duration: end.saturating_duration_since(open.start),
+        children: open.children,
+    }
+}
+
+impl Trace {
+    /// Returns the summaries of all spans, grouped by their path, in order of first occurrence.
+    pub fn aggregate(&self) -> Vec<SpanSummary> {
+        fn visit(span: &Span, prefix: &str, indices: &mut HashMap<String, usize>, summaries: &mut Vec<SpanSummary>) {
+            let path = match prefix.is_empty() {
+                true => span.name.clone(),
+                false => format!("{}{}{}", prefix, PATH_SEPARATOR, span.name),
+            };
+
+            match indices.get(&path) {
+                Some(index) => {
+                    let summary = &mut summaries[*index];
+                    summary.count += 1;
+                    summary.total += span.duration;
+                    summary.min = summary.min.min(span.duration);
+                    summary.max = summary.max.max(span.duration);
+                }
+                None => {
+                    indices.insert(path.clone(), summaries.len());
+                    summaries.push(SpanSummary {
+                        path: path.clone(),
+                        count: 1,
+                        total: span.duration,
+                        min: span.duration,
+                        max: span.duration,
+                    });
+                }
+            }
+
+            for child in &span.children {
+                visit(child, &path, indices, summaries);
+            }
+        }
+
+        let mut indices = HashMap::new();
+        let mut summaries = Vec::new();
+        for span in &self.spans {
+            visit(span, "", &mut indices, &mut summaries);
+        }
+        summaries
+    }
+
+    /// Returns the span tree and its aggregated summaries as a JSON object.
+    /// All durations are given in nanoseconds.
+    pub fn to_json(&self) -> String {
+        fn write_span(output: &mut String, span: &Span) {
+            write!(
+                output,
+                "{{\"name\":{},\"thread_id\":{},\"start_ns\":{},\"duration_ns\":{},\"children\":[",
+                escape(&span.name),
+                span.thread_id,
+                span.start.as_nanos(),
+                span.duration.as_nanos()
+            )
+            .unwrap();
+            for (i, child) in span.children.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                write_span(output, child);
+            }
+            output.push_str("]}");
+        }
+
+        let mut output = String::from("{\"spans\":[");
+        for (i, span) in self.spans.iter().enumerate() {
+            if i > 0 {
+                output.push(',');
+            }
+            write_span(&mut output, span);
+        }
+
+        output.push_str("],\"summary\":[");
+        for (i, summary) in self.aggregate().iter().enumerate() {
+            if i > 0 {
+                output.push(',');
+            }
+            write!(
+                output,
+                "{{\"path\":{},\"count\":{},\"total_ns\":{},\"min_ns\":{},\"max_ns\":{},\"mean_ns\":{}}}",
+                escape(&summary.path),
+                summary.count,
+                summary.total.as_nanos(),
+                summary.min.as_nanos(),
+                summary.max.as_nanos(),
+                summary.mean().as_nanos()
+            )
+            .unwrap();
+        }
+        output.push_str("]}");
+        output
+    }
+
+    /// Returns the spans as complete events in the Chrome trace-event format,
+    /// which can be loaded in `chrome://tracing` or Perfetto.
+    pub fn to_chrome_trace(&self) -> String {
+        fn write_events(output: &mut String, span: &Span) {
+            if !output.ends_with('[') {
+                output.push(',');
+            }
+            write!(
+                output,
+                "{{\"name\":{},\"cat\":\"snarkvm\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":{},\"tid\":{}}}",
+                escape(&span.name),
+                span.start.as_nanos() as f64 / 1000.0,
+                span.duration.as_nanos() as f64 / 1000.0,
+                std::process::id(),
+                span.thread_id
+            )
+            .unwrap();
+            for child in &span.children {
+                write_events(output, child);
+            }
+        }
+
+        let mut output = String::from("{\"traceEvents\":[");
+        for span in &self.spans {
+            write_events(&mut output, span);
+        }
+        output.push_str("],\"displayTimeUnit\":\"ms\"}");
+        output
+    }
+}
+
+/// Returns the given string as a quoted JSON string.
+fn escape(input: &str) -> String {
+    let mut output = String::with_capacity(input.len() + 2);
+    output.push('"');
+    for c in input.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(output, "\\u{:04x}", c as u32).unwrap(),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(name: &str, start: u64, duration: u64, children: Vec<Span>) -> Span {
+        Span {
+            name: name.to_string(),
+            thread_id: 1,
+            start: Duration::from_micros(start),
+            duration: Duration::from_micros(duration),
+            children,
+        }
+    }
+
+    fn sample_trace() -> Trace {
+        Trace {
+            spans: vec![span("Prover", 0, 100, vec![
+                span("Round", 10, 20, vec![]),
+                span("Round", 30, 40, vec![]),
+                span("Round \"final\"", 70, 30, vec![]),
+            ])],
+        }
+    }
+
+    #[test]
+    fn test_collect() {
+        // Other tests in this crate may record spans concurrently, so only look for our own.
+        Collector::start();
+        let outer = start_timer!(|| "test_collect::outer");
+        for i in 0..3 {
+            let inner = start_timer!(|| format!("test_collect::inner {}", i % 2));
+            end_timer!(inner);
+        }
+        let unclosed = start_timer!(|| "test_collect::unclosed");
+        end_timer!(outer);
+        end_timer!(unclosed);
+        let trace = Collector::finish();
+        assert!(!Collector::is_collecting());
+
+        let outer = trace.spans.iter().find(|span| span.name == "test_collect::outer").unwrap();
+        let names = outer.children.iter().map(|span| span.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec![
+            "test_collect::inner 0",
+            "test_collect::inner 1",
+            "test_collect::inner 0",
+            "test_collect::unclosed"
+        ]);
+        for child in &outer.children {
+            assert_eq!(child.thread_id, outer.thread_id);
+            assert!(child.start >= outer.start);
+            assert!(child.start + child.duration <= outer.start + outer.duration);
+        }
+
+        let summary =
+            trace.aggregate().into_iter().find(|summary| summary.path == "test_collect::outer > test_collect::inner 0");
+        assert_eq!(summary.unwrap().count, 2);
+
+        // Spans are not recorded once the collection has finished.
+        let ignored = start_timer!(|| "test_collect::ignored");
+        end_timer!(ignored);
+        assert_eq!(Collector::finish(), Trace::default());
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let summaries = sample_trace().aggregate();
+        assert_eq!(summaries.len(), 3);
+
+        assert_eq!(summaries[0].path, "Prover");
+        assert_eq!(summaries[0].count, 1);
+
+        assert_eq!(summaries[1].path, "Prover > Round");
+        assert_eq!(summaries[1].count, 2);
+        assert_eq!(summaries[1].total, Duration::from_micros(60));
+        assert_eq!(summaries[1].min, Duration::from_micros(20));
+        assert_eq!(summaries[1].max, Duration::from_micros(40));
+        assert_eq!(summaries[1].mean(), Duration::from_micros(30));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let expected = concat!(
+            "{\"spans\":[{\"name\":\"Prover\",\"thread_id\":1,\"start_ns\":0,\"duration_ns\":100000,\"children\":[",
+            "{\"name\":\"Round\",\"thread_id\":1,\"start_ns\":10000,\"duration_ns\":20000,\"children\":[]},",
+            "{\"name\":\"Round\",\"thread_id\":1,\"start_ns\":30000,\"duration_ns\":40000,\"children\":[]},",
+            "{\"name\":\"Round \\\"final\\\"\",\"thread_id\":1,\"start_ns\":70000,\"duration_ns\":30000,\"children\":[]}]}],",
+            "\"summary\":[",
+            "{\"path\":\"Prover\",\"count\":1,\"total_ns\":100000,\"min_ns\":100000,\"max_ns\":100000,\"mean_ns\":100000},",
+            "{\"path\":\"Prover > Round\",\"count\":2,\"total_ns\":60000,\"min_ns\":20000,\"max_ns\":40000,\"mean_ns\":30000},",
+            "{\"path\":\"Prover > Round \\\"final\\\"\",\"count\":1,\"total_ns\":30000,\"min_ns\":30000,\"max_ns\":30000,\"mean_ns\":30000}]}"
+        );
+        assert_eq!(sample_trace().to_json(), expected);
+    }
+
+    #[test]
+    fn test_to_chrome_trace() {
+        let trace = sample_trace().to_chrome_trace();
+        assert!(trace.starts_with(
+            "{\"traceEvents\":[{\"name\":\"Prover\",\"cat\":\"snarkvm\",\"ph\":\"X\",\"ts\":0.000,\"dur\":100.000,"
+        ));
+        assert!(trace.ends_with("],\"displayTimeUnit\":\"ms\"}"));
+        assert_eq!(trace.matches("\"ph\":\"X\"").count(), 4);
+        assert!(trace.contains("\"ts\":30.000,\"dur\":40.000"));
+    }
+}
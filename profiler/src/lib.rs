@@ -15,6 +15,7 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 #![allow(unused_imports)]
+pub use collector::*;
 pub use inner::*;
 
 #[cfg(feature = "print-trace")]
@@ -29,6 +30,7 @@ pub mod inner {
 
     pub struct TimerInfo {
         pub msg: String,
+        pub span: crate::SpanHandle,
         pub time: Instant,
     }
 
@@ -45,7 +47,7 @@ pub mod inner {
 
             println!("{}{:8} {}", indent, start_info, msg);
             NUM_INDENT.fetch_add(1, Ordering::Relaxed);
-            $crate::TimerInfo { msg: msg.to_string(), time: Instant::now() }
+            $crate::TimerInfo { msg: msg.to_string(), span: $crate::enter_span(|| &msg), time: Instant::now() }
         }};
     }
 
@@ -86,6 +88,7 @@ pub mod inner {
             // Todo: Recursively ensure that *entire* string is of appropriate
             // width (not just message).
             println!("{}{:8} {:.<pad$}{}", indent, end_info, message, final_time, pad = 75 - indent_amount);
+            $crate::exit_span($time.span);
         }};
     }
 
@@ -150,14 +153,30 @@ pub mod inner {
 #[cfg(not(feature = "print-trace"))]
 #[macro_use]
 mod inner {
+    #[cfg(not(feature = "collect-trace"))]
     pub struct TimerInfo;
 
+    #[cfg(feature = "collect-trace")]
+    pub struct TimerInfo {
+        pub span: crate::SpanHandle,
+    }
+
+    #[cfg(not(feature = "collect-trace"))]
     #[macro_export]
     macro_rules! start_timer {
         ($msg:expr) => {
             $crate::TimerInfo
         };
     }
+
+    #[cfg(feature = "collect-trace")]
+    #[macro_export]
+    macro_rules! start_timer {
+        ($msg:expr) => {
+            $crate::TimerInfo { span: $crate::enter_span($msg) }
+        };
+    }
+
     #[macro_export]
     macro_rules! add_to_trace {
         ($title:expr, $msg:expr) => {
@@ -165,6 +184,7 @@ mod inner {
         };
     }
 
+    #[cfg(not(feature = "collect-trace"))]
     #[macro_export]
     macro_rules! end_timer {
         ($time:expr, $msg:expr) => {
@@ -175,6 +195,35 @@ mod inner {
             let _ = $time;
         };
     }
+
+    #[cfg(feature = "collect-trace")]
+    #[macro_export]
+    macro_rules! end_timer {
+        ($time:expr, $msg:expr) => {
+            let _ = $msg;
+            $crate::exit_span($time.span);
+        };
+        ($time:expr) => {
+            $crate::exit_span($time.span);
+        };
+    }
+}
+
+#[cfg(feature = "collect-trace")]
+pub mod collector;
+
+#[cfg(not(feature = "collect-trace"))]
+#[doc(hidden)]
+pub mod collector {
+    pub struct SpanHandle;
+
+    #[inline]
+    pub fn enter_span<M, F: FnOnce() -> M>(_msg: F) -> SpanHandle {
+        SpanHandle
+    }
+
+    #[inline]
+    pub fn exit_span(_handle: SpanHandle) {}
 }
 
 mod tests {
@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
+use std::path::PathBuf;
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
@@ -33,6 +34,69 @@ pub struct CLI {
 
 #[derive(StructOpt, Debug)]
 pub enum Command {
+    /// Generate or derive an Aleo account
+    Account(AccountCommand),
+
+    /// Run a local development ledger. Devnet keys are insecure and devnet-only, as they are derived from
+    /// the public `SNARKVM_DEVNET_SEED` (zero by default), so anyone can forge its proofs and spend its genesis records
+    Devnet {
+        /// The directory of the devnet
+        #[structopt(long, default_value = "devnet", parse(from_os_str))]
+        path: PathBuf,
+
+        #[structopt(subcommand)]
+        command: DevnetCommand,
+    },
+
+    /// Build a devnet genesis block with premine allocations. The testnets always use their published genesis block
+    Genesis {
+        /// An allocation of the starting supply, as `{address}={amount}` with the amount in Aleo bytes (AB)
+        #[structopt(long = "allocation", required = true, number_of_values = 1)]
+        allocations: Vec<String>,
+
+        /// The timestamp of the genesis block, which defaults to zero
+        #[structopt(long)]
+        timestamp: Option<i64>,
+
+        /// The difficulty target of the genesis block, which defaults to the minimum difficulty
+        #[structopt(long)]
+        difficulty_target: Option<u64>,
+
+        /// The output file
+        #[structopt(long, default_value = "genesis.block", parse(from_os_str))]
+        output: PathBuf,
+    },
+
+    /// Setup, verify or inspect SNARK parameters
+    Parameters(ParametersCommand),
+
+    /// Run a local prover service, which proves transactions for authorizations from light clients
+    Prover {
+        /// The network of the prover service
+        #[structopt(long, default_value = "devnet", possible_values = &["devnet", "testnet1", "testnet2"])]
+        network: String,
+
+        /// The address to listen on
+        #[structopt(long, default_value = "127.0.0.1:4180")]
+        listen: String,
+    },
+
+    /// Decrypt an Aleo record
+    Record(RecordCommand),
+
+    /// Sign a message with an account private key
+    Sign {
+        /// The account private key
+        #[structopt(long)]
+        private_key: String,
+
+        /// The message to sign
+        message: String,
+    },
+
+    /// Inspect an Aleo transaction
+    Transaction(TransactionCommand),
+
     /// Update snarkVM to the latest version
     Update {
         /// Lists all available versions of snarkVM
@@ -43,4 +107,144 @@ pub enum Command {
         #[structopt(short = "q", long)]
         quiet: bool,
     },
+
+    /// Verify a message signature against an account address
+    Verify {
+        /// The account address
+        #[structopt(long)]
+        address: String,
+
+        /// The signature of the message
+        #[structopt(long)]
+        signature: String,
+
+        /// The signed message
+        message: String,
+    },
+
+    /// Verify an Aleo transaction, given as hex or JSON
+    VerifyTransaction {
+        /// The transaction, as hex or JSON
+        transaction: String,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+pub enum AccountCommand {
+    /// Generate a new Aleo account
+    New,
+
+    /// Derive an Aleo account from its private key
+    FromPrivateKey {
+        /// The account private key
+        private_key: String,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+pub enum DevnetCommand {
+    /// Print the balance of an account, from its view key or private key
+    Balance {
+        /// The account view key, which cannot detect spent records
+        #[structopt(long, required_unless = "private_key", conflicts_with = "private_key")]
+        view_key: Option<String>,
+
+        /// The account private key, which only counts unspent records
+        #[structopt(long)]
+        private_key: Option<String>,
+    },
+
+    /// Initialize a devnet with the genesis block
+    Init,
+
+    /// Mine blocks, with their coinbase records to the given address
+    Mine {
+        /// The address of the coinbase recipient
+        #[structopt(long)]
+        to: String,
+
+        /// The number of blocks to mine
+        #[structopt(long, default_value = "1")]
+        blocks: u32,
+    },
+
+    /// Print the latest block height, block hash and ledger root
+    Status,
+
+    /// Add a transfer to the memory pool, to be included in the next mined block
+    Transfer {
+        /// The private key of the sender
+        #[structopt(long)]
+        private_key: String,
+
+        /// The address of the recipient
+        #[structopt(long)]
+        to: String,
+
+        /// The amount to transfer, in Aleo bytes (AB)
+        #[structopt(long)]
+        amount: i64,
+
+        /// The fee to pay, in Aleo bytes (AB)
+        #[structopt(long, default_value = "0")]
+        fee: i64,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+pub enum ParametersCommand {
+    /// Print a summary of a parameter file, such as the circuit sizes, domain sizes and degree bounds of a key
+    Inspect {
+        /// The parameter file, named `{parameter}.{kind}`
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+
+        /// The network of the parameter file
+        #[structopt(long, default_value = "testnet2", possible_values = &["testnet1", "testnet2"])]
+        network: String,
+    },
+
+    /// Run the setup of a parameter, and write its key files and metadata to the output directory
+    Setup {
+        /// The parameter to setup
+        #[structopt(possible_values = &["inner", "noop", "posw", "universal"])]
+        parameter: String,
+
+        /// The network of the parameter
+        #[structopt(long, default_value = "testnet2", possible_values = &["testnet1", "testnet2"])]
+        network: String,
+
+        /// The output directory
+        #[structopt(long, default_value = ".", parse(from_os_str))]
+        output: PathBuf,
+    },
+
+    /// Verify the parameter files in a directory against the checksums and sizes in their metadata
+    Verify {
+        /// The directory of the parameter files
+        #[structopt(default_value = ".", parse(from_os_str))]
+        directory: PathBuf,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+pub enum RecordCommand {
+    /// Decrypt a record ciphertext with an account view key
+    Decrypt {
+        /// The account view key
+        #[structopt(long)]
+        view_key: String,
+
+        /// The record ciphertext
+        ciphertext: String,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+pub enum TransactionCommand {
+    /// Print the transitions, serial numbers, commitments, events and value balance of a transaction
+    Inspect {
+        /// The transaction, as hex or JSON
+        transaction: String,
+    },
 }
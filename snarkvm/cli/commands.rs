@@ -14,10 +14,54 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::cli::{Command, Updater};
+use crate::cli::{
+    AccountCommand,
+    Command,
+    Devnet,
+    DevnetCommand,
+    Genesis,
+    Parameters,
+    ParametersCommand,
+    ProverService,
+    RecordCommand,
+    Toolkit,
+    TransactionCommand,
+    Updater,
+};
 
 pub fn parse(command: Command) -> anyhow::Result<String> {
     match command {
+        Command::Account(command) => match command {
+            AccountCommand::New => Toolkit::new_account(),
+            AccountCommand::FromPrivateKey { private_key } => Toolkit::account_from_private_key(&private_key),
+        },
+        Command::Devnet { path, command } => match command {
+            DevnetCommand::Balance { view_key, private_key } => {
+                Devnet::open(path)?.balance(view_key.as_deref(), private_key.as_deref())
+            }
+            DevnetCommand::Init => Devnet::init(path),
+            DevnetCommand::Mine { to, blocks } => Devnet::open(path)?.mine(&to, blocks),
+            DevnetCommand::Status => Ok(Devnet::open(path)?.status()),
+            DevnetCommand::Transfer { private_key, to, amount, fee } => {
+                Devnet::open(path)?.transfer(&private_key, &to, amount, fee)
+            }
+        },
+        Command::Genesis { allocations, timestamp, difficulty_target, output } => {
+            Genesis::build(&allocations, timestamp, difficulty_target, &output)
+        }
+        Command::Parameters(command) => match command {
+            ParametersCommand::Inspect { file, network } => Parameters::inspect(&file, &network),
+            ParametersCommand::Setup { parameter, network, output } => Parameters::setup(&parameter, &network, &output),
+            ParametersCommand::Verify { directory } => Parameters::verify(&directory),
+        },
+        Command::Prover { network, listen } => ProverService::serve(&network, &listen),
+        Command::Record(command) => match command {
+            RecordCommand::Decrypt { view_key, ciphertext } => Toolkit::decrypt_record(&view_key, &ciphertext),
+        },
+        Command::Sign { private_key, message } => Toolkit::sign(&private_key, &message),
+        Command::Transaction(command) => match command {
+            TransactionCommand::Inspect { transaction } => Toolkit::inspect_transaction(&transaction),
+        },
         Command::Update { list, quiet } => match list {
             true => match Updater::show_available_releases() {
                 Ok(output) => Ok(output),
@@ -42,6 +86,8 @@ pub fn parse(command: Command) -> anyhow::Result<String> {
                     Ok("".to_string())
                 }
             }
-        }, // _ => Err(anyhow!("\nUnknown command\n")),
+        },
+        Command::Verify { address, signature, message } => Toolkit::verify(&address, &signature, &message),
+        Command::VerifyTransaction { transaction } => Toolkit::verify_transaction(&transaction),
     }
 }
@@ -0,0 +1,218 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::dpc::{
+    testnet2::Testnet2,
+    Address,
+    AleoAmount,
+    ComputeKey,
+    DecryptionKey,
+    Ledger,
+    PrivateKey,
+    Transaction,
+    TransferBuilder,
+    ViewKey,
+    WalletScanner,
+};
+
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use core::{str::FromStr, sync::atomic::AtomicBool};
+use rand::thread_rng;
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A local development ledger on Testnet2, which is persisted in a directory.
+pub struct Devnet {
+    /// The directory of the devnet.
+    path: PathBuf,
+    /// The ledger of the devnet.
+    ledger: Ledger<Testnet2>,
+}
+
+impl Devnet {
+    /// The directory of the ledger storage, relative to the devnet directory.
+    const LEDGER_DIRECTORY: &'static str = "ledger";
+    /// The file of the unconfirmed transactions, relative to the devnet directory.
+    const MEMORY_POOL_FILE: &'static str = "memory_pool.jsonl";
+
+    /// Initialize a new devnet in the given directory, with the genesis block.
+    pub fn init<P: AsRef<Path>>(path: P) -> Result<String> {
+        let path = path.as_ref();
+        if path.join(Self::LEDGER_DIRECTORY).exists() {
+            return Err(anyhow!("A devnet already exists in {}", path.display()));
+        }
+        fs::create_dir_all(path)?;
+
+        let devnet = Self { path: path.to_path_buf(), ledger: Ledger::open(path.join(Self::LEDGER_DIRECTORY))? };
+        devnet.save_memory_pool()?;
+
+        Ok(format!("Initialized a devnet in {}\n\n{}", path.display(), devnet.status()))
+    }
+
+    /// Open the devnet in the given directory, restoring its unconfirmed transactions.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.join(Self::LEDGER_DIRECTORY).exists() {
+            return Err(anyhow!("There is no devnet in {}. Run `snarkvm devnet init` first", path.display()));
+        }
+
+        let mut ledger = Ledger::open(path.join(Self::LEDGER_DIRECTORY))?;
+
+        // Restore the unconfirmed transactions, skipping those that are no longer valid.
+        let memory_pool_file = path.join(Self::MEMORY_POOL_FILE);
+        if memory_pool_file.exists() {
+            for line in fs::read_to_string(memory_pool_file)?.lines().filter(|line| !line.trim().is_empty()) {
+                let transaction = Transaction::from_str(line)?;
+                if let Err(error) = ledger.add_unconfirmed_transaction(&transaction) {
+                    eprintln!("Dropping transaction {}: {}", transaction.transaction_id(), error);
+                }
+            }
+        }
+
+        Ok(Self { path: path.to_path_buf(), ledger })
+    }
+
+    /// Print the latest block height, block hash and ledger root of the devnet.
+    pub fn status(&self) -> String {
+        let mut output = Self::format_field("Height", self.ledger.latest_block_height());
+        output += &Self::format_field("Block Hash", self.ledger.latest_block_hash());
+        output += &Self::format_field("Ledger Root", self.ledger.latest_ledger_root());
+        output += &Self::format_field("Unconfirmed", self.ledger.memory_pool().num_transactions());
+        output
+    }
+
+    /// Mine the given number of blocks, with their coinbase records to the given recipient.
+    pub fn mine(&mut self, recipient: &str, num_blocks: u32) -> Result<String> {
+        let recipient = Address::<Testnet2>::from_str(recipient)?;
+        let rng = &mut thread_rng();
+
+        let mut output = String::new();
+        for _ in 0..num_blocks {
+            let coinbase_record = self.ledger.mine_next_block(recipient, false, &AtomicBool::new(false), rng)?;
+            output += &format!(
+                "Mined block {} ({}) with a coinbase record of {} AB\n",
+                self.ledger.latest_block_height(),
+                self.ledger.latest_block_hash(),
+                coinbase_record.value()
+            );
+        }
+        self.save_memory_pool()?;
+
+        Ok(output)
+    }
+
+    /// Transfer the given amount from the account of the given private key to the given recipient.
+    pub fn transfer(&mut self, private_key: &str, recipient: &str, amount: i64, fee: i64) -> Result<String> {
+        let private_key = PrivateKey::<Testnet2>::from_str(private_key)?;
+        let recipient = Address::<Testnet2>::from_str(recipient)?;
+
+        // Exclude the records that are already spent by unconfirmed transactions.
+        let pending_serial_numbers: HashSet<_> = self
+            .ledger
+            .memory_pool()
+            .transactions()
+            .iter()
+            .flat_map(|transaction| transaction.serial_numbers().copied().collect::<Vec<_>>())
+            .collect();
+        let records = self
+            .scan(&ViewKey::from(&private_key), &private_key.to_compute_key())?
+            .unspent_records()
+            .into_iter()
+            .filter(|record| !pending_serial_numbers.contains(&record.serial_number()))
+            .map(|record| record.record().clone())
+            .collect();
+
+        let transaction = TransferBuilder::new(&private_key, recipient, AleoAmount(amount), AleoAmount(fee))
+            .add_records(records)
+            .build(&self.ledger, &mut thread_rng())?;
+        self.ledger.add_unconfirmed_transaction(&transaction)?;
+        self.save_memory_pool()?;
+
+        Ok(format!("Added transaction {} to the memory pool", transaction.transaction_id()))
+    }
+
+    ///
+    /// Print the records owned by the account of the given view key, and their total value.
+    /// If the private key is given instead, only the unspent records are printed.
+    ///
+    pub fn balance(&self, view_key: Option<&str>, private_key: Option<&str>) -> Result<String> {
+        let mut output = String::new();
+        let total = match (view_key, private_key) {
+            (Some(view_key), None) => {
+                // The view key decrypts the owned records, but cannot tell whether they are spent.
+                let decryption_key = DecryptionKey::from(ViewKey::<Testnet2>::from_str(view_key)?);
+                let mut total = AleoAmount::ZERO;
+                for height in 0..=self.ledger.latest_block_height() {
+                    for transaction in self.ledger.get_block(height)?.transactions().iter() {
+                        for record in transaction.to_decrypted_records(&decryption_key) {
+                            output += &Self::format_record(height, record.commitment(), record.value());
+                            total = total.add(record.value());
+                        }
+                    }
+                }
+                output += &format!("{}\n", "Spent records are included, as a view key cannot detect spends".yellow());
+                total
+            }
+            (None, Some(private_key)) => {
+                let private_key = PrivateKey::<Testnet2>::from_str(private_key)?;
+                let wallet = self.scan(&ViewKey::from(&private_key), &private_key.to_compute_key())?;
+                for record in wallet.unspent_records() {
+                    output += &Self::format_record(
+                        record.block_height(),
+                        record.record().commitment(),
+                        record.record().value(),
+                    );
+                }
+                wallet.balance()
+            }
+            _ => return Err(anyhow!("Provide either a view key or a private key")),
+        };
+
+        output += &Self::format_field("Balance", format!("{} AB", total));
+        Ok(output)
+    }
+
+    /// Scan the ledger for the records of the given account.
+    fn scan(
+        &self,
+        view_key: &ViewKey<Testnet2>,
+        compute_key: &ComputeKey<Testnet2>,
+    ) -> Result<WalletScanner<Testnet2>> {
+        let mut wallet = WalletScanner::new(view_key, compute_key)?;
+        wallet.scan(&self.ledger)?;
+        Ok(wallet)
+    }
+
+    /// Write the unconfirmed transactions to the devnet directory.
+    fn save_memory_pool(&self) -> Result<()> {
+        let transactions: Vec<_> =
+            self.ledger.memory_pool().transactions().iter().map(|transaction| transaction.to_string()).collect();
+        fs::write(self.path.join(Self::MEMORY_POOL_FILE), transactions.join("\n"))?;
+        Ok(())
+    }
+
+    fn format_record<T: core::fmt::Display>(height: u32, commitment: T, value: AleoAmount) -> String {
+        Self::format_field("Record", format!("{} AB in block {} ({})", value, height, commitment))
+    }
+
+    fn format_field<T: core::fmt::Display>(name: &str, value: T) -> String {
+        format!("{} {}\n", format!("{:>14}", name).cyan().bold(), value)
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    dpc::{
+        devnet::{Devnet, DEVNET_DIRECTORY_ENV},
+        Address,
+        AleoAmount,
+        GenesisBuilder,
+        Network,
+    },
+    utilities::ToBytes,
+};
+
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use core::str::FromStr;
+use rand::thread_rng;
+use std::{fs, path::Path};
+
+/// A builder for genesis blocks with premine allocations.
+pub struct Genesis;
+
+impl Genesis {
+    /// Build a devnet genesis block with the given allocations, and write it to the given file.
+    /// Each allocation is given as `{address}={amount}`, with the amount in Aleo bytes (AB).
+    ///
+    /// Only the devnet supports a custom genesis block, which it loads from its cache directory.
+    /// The testnets always use their published genesis block.
+    pub fn build(
+        allocations: &[String],
+        timestamp: Option<i64>,
+        difficulty_target: Option<u64>,
+        output: &Path,
+    ) -> Result<String> {
+        let mut builder = GenesisBuilder::<Devnet>::new();
+        for allocation in allocations {
+            let (recipient, amount) = Self::parse_allocation::<Devnet>(allocation)?;
+            builder = builder.add_allocation(recipient, amount);
+        }
+        if let Some(timestamp) = timestamp {
+            builder = builder.timestamp(timestamp);
+        }
+        if let Some(difficulty_target) = difficulty_target {
+            builder = builder.difficulty_target(difficulty_target);
+        }
+
+        // Check the allocations before the (expensive) proving begins.
+        builder.check()?;
+        let block = builder.build(&mut thread_rng())?;
+        fs::write(output, block.to_bytes_le()?)?;
+
+        let mut result = Self::format_field("Block Hash", block.hash());
+        result += &Self::format_field("Timestamp", block.timestamp());
+        result += &Self::format_field("Difficulty", block.difficulty_target());
+        for (recipient, amount) in builder.allocations() {
+            result += &Self::format_field("Allocation", format!("{} AB to {}", amount, recipient));
+        }
+        result += &Self::format_field("Output", output.display());
+        result += &format!(
+            "\nTo use it, copy it to {} as genesis.block (see {}) before the devnet is first used\n",
+            Devnet::cache_directory().display(),
+            DEVNET_DIRECTORY_ENV
+        );
+        Ok(result)
+    }
+
+    /// Returns the recipient and amount of an allocation, given as `{address}={amount}`.
+    fn parse_allocation<N: Network>(allocation: &str) -> Result<(Address<N>, AleoAmount)> {
+        let (address, amount) = allocation
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Expected an allocation as `{{address}}={{amount}}`, found {}", allocation))?;
+        let amount = amount.trim().parse::<i64>().map_err(|error| anyhow!("Invalid amount {}: {}", amount, error))?;
+        Ok((Address::from_str(address.trim())?, AleoAmount::from_i64(amount)))
+    }
+
+    fn format_field<T: core::fmt::Display>(name: &str, value: T) -> String {
+        format!("{} {}\n", format!("{:>14}", name).cyan().bold(), value)
+    }
+}
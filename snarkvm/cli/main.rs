@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use snarkvm::cli::{parse, Updater, CLI};
+use snarkvm::cli::{parse, Command, Updater, CLI};
 
 use structopt::StructOpt;
 
@@ -25,7 +25,10 @@ fn main() -> anyhow::Result<()> {
         println!("\n{:#?}\n", cli);
     }
 
-    println!("{}", Updater::print_cli());
+    // Only check for a new version when updating, as the other commands are offline.
+    if let Command::Update { .. } = cli.command {
+        println!("{}", Updater::print_cli());
+    }
 
     println!("{}", parse(cli.command)?);
 
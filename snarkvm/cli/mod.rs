@@ -20,8 +20,23 @@ pub use cli::*;
 pub mod commands;
 pub use commands::*;
 
+pub mod devnet;
+pub use devnet::*;
+
 pub mod errors;
 pub use errors::*;
 
+pub mod genesis;
+pub use genesis::*;
+
+pub mod parameters;
+pub use parameters::*;
+
+pub mod prover;
+pub use prover::*;
+
+pub mod toolkit;
+pub use toolkit::*;
+
 pub mod updater;
 pub use updater::*;
@@ -0,0 +1,340 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    algorithms::{
+        crh::sha256::sha256,
+        polycommit::{PCCommitterKey, PCUniversalParams, PCVerifierKey, PolynomialCommitment},
+        snark::{
+            groth16,
+            marlin::{AHPForR1CS, CircuitProvingKey, CircuitVerifyingKey, MarlinHidingMode, MarlinMode},
+        },
+        CRH,
+        SNARK,
+        SRS,
+    },
+    curves::PairingEngine,
+    dpc::{testnet1::Testnet1, testnet2::Testnet2, InnerCircuit, Network, PoSWScheme, SynthesizedCircuit},
+    fields::PrimeField,
+    utilities::{FromBytes, ToBytes, ToMinimalBits},
+};
+
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use rand::{prelude::ThreadRng, thread_rng};
+use serde_json::{json, Value};
+use std::{fs, path::Path};
+
+/// A summary of the sizes of a SNARK key.
+pub trait KeySummary {
+    /// Returns the named sizes of the key.
+    fn summary(&self) -> Vec<(&'static str, usize)>;
+}
+
+impl<F: PrimeField, CF: PrimeField, PC: PolynomialCommitment<F, CF>, MM: MarlinMode> KeySummary
+    for CircuitVerifyingKey<F, CF, PC, MM>
+{
+    fn summary(&self) -> Vec<(&'static str, usize)> {
+        let info = &self.circuit_info;
+        let [constraint_bound, non_zero_a_bound, non_zero_b_bound, non_zero_c_bound] =
+            AHPForR1CS::<F, MM>::get_degree_bounds(info);
+        vec![
+            ("Variables", info.num_variables),
+            ("Constraints", info.num_constraints),
+            ("Non-Zero A", info.num_non_zero_a),
+            ("Non-Zero B", info.num_non_zero_b),
+            ("Non-Zero C", info.num_non_zero_c),
+            ("Constraint Domain", constraint_bound + 2),
+            ("Non-Zero Domain", AHPForR1CS::<F, MM>::max_non_zero_domain(info).size()),
+            ("Max Degree", info.max_degree::<MM>()),
+            ("Degree Bound H", constraint_bound),
+            ("Degree Bound A", non_zero_a_bound),
+            ("Degree Bound B", non_zero_b_bound),
+            ("Degree Bound C", non_zero_c_bound),
+            ("Commitments", self.circuit_commitments.len()),
+            ("Verifier Degree", self.verifier_key.supported_degree()),
+        ]
+    }
+}
+
+impl<F: PrimeField, CF: PrimeField, PC: PolynomialCommitment<F, CF>, MM: MarlinMode> KeySummary
+    for CircuitProvingKey<F, CF, PC, MM>
+{
+    fn summary(&self) -> Vec<(&'static str, usize)> {
+        let mut summary = self.circuit_verifying_key.summary();
+        summary.push(("Committer Degree", self.committer_key.supported_degree()));
+        summary
+    }
+}
+
+impl<E: PairingEngine> KeySummary for groth16::VerifyingKey<E> {
+    fn summary(&self) -> Vec<(&'static str, usize)> {
+        vec![("Public Inputs", self.gamma_abc_g1.len().saturating_sub(1))]
+    }
+}
+
+impl<E: PairingEngine> KeySummary for groth16::ProvingKey<E> {
+    fn summary(&self) -> Vec<(&'static str, usize)> {
+        let mut summary = self.vk.summary();
+        summary.extend_from_slice(&[
+            ("Variables", self.a_query.len()),
+            ("Private Variables", self.l_query.len()),
+            ("H Query", self.h_query.len()),
+            ("B G2 Query", self.b_g2_query.len()),
+        ]);
+        summary
+    }
+}
+
+/// Setup, verification and inspection of SNARK parameters.
+pub struct Parameters;
+
+impl Parameters {
+    /// Run the setup of the given parameter for the given network, and write the resulting files to the given directory.
+    pub fn setup(parameter: &str, network: &str, output: &Path) -> Result<String> {
+        fs::create_dir_all(output)?;
+        match (parameter, network) {
+            ("inner", "testnet1") => Self::inner_setup::<Testnet1>(output),
+            ("inner", "testnet2") => Self::inner_setup::<Testnet2>(output),
+            ("noop", "testnet1") => Self::noop_setup::<Testnet1>(output),
+            ("noop", "testnet2") => Self::noop_setup::<Testnet2>(output),
+            ("posw", "testnet1") => Self::posw_setup::<Testnet1>(output),
+            ("posw", "testnet2") => Self::posw_setup::<Testnet2>(output),
+            ("universal", "testnet1") => Err(anyhow!("Testnet1 does not support a universal SRS")),
+            ("universal", "testnet2") => Self::universal_setup::<Testnet2>(output),
+            (_, "testnet1") | (_, "testnet2") => Err(anyhow!("Invalid parameter {}", parameter)),
+            _ => Err(anyhow!("Invalid network {}", network)),
+        }
+    }
+
+    /// Verify the parameter files in the given directory against the checksums and sizes in their metadata.
+    pub fn verify(directory: &Path) -> Result<String> {
+        let mut output = String::new();
+        let (mut num_verified, mut num_failed) = (0, 0);
+
+        let mut metadata_files: Vec<_> = fs::read_dir(directory)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().map_or(false, |extension| extension == "metadata"))
+            .collect();
+        metadata_files.sort();
+
+        for metadata_file in metadata_files {
+            let stem = metadata_file.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default().to_string();
+            let metadata: Value = serde_json::from_slice(&fs::read(&metadata_file)?)?;
+            let metadata = metadata.as_object().ok_or_else(|| anyhow!("Invalid metadata in {}", stem))?;
+
+            for (key, checksum) in metadata {
+                let kind = match key.strip_suffix("_checksum") {
+                    Some(kind) => kind,
+                    None => continue,
+                };
+                let checksum = checksum.as_str().ok_or_else(|| anyhow!("Invalid checksum {} in {}", key, stem))?;
+                let size = metadata.get(&format!("{}_size", kind)).and_then(Value::as_u64);
+
+                // Parameter files are named either `{stem}.{kind}`, or `{stem}.{kind}.{checksum}` if they are hosted remotely.
+                let filename = format!("{}.{}", stem, kind);
+                let candidates =
+                    [directory.join(&filename), directory.join(Self::versioned_filename(&filename, checksum))];
+                let file = match candidates.iter().find(|file| file.exists()) {
+                    Some(file) => file,
+                    None => {
+                        output += &format!("{} {}\n", format!("{:>9}", "Missing").yellow().bold(), filename);
+                        continue;
+                    }
+                };
+
+                let bytes = fs::read(file)?;
+                match Self::checksum(&bytes) == checksum && size.map_or(true, |size| size == bytes.len() as u64) {
+                    true => {
+                        num_verified += 1;
+                        output += &format!("{} {}\n", format!("{:>9}", "Verified").green().bold(), filename);
+                    }
+                    false => {
+                        num_failed += 1;
+                        output += &format!("{} {}\n", format!("{:>9}", "Mismatch").red().bold(), filename);
+                    }
+                }
+            }
+        }
+
+        match num_failed {
+            0 => Ok(output + &format!("\nVerified {} parameter files", num_verified)),
+            _ => Err(anyhow!("{}\n{} parameter files do not match their metadata", output, num_failed)),
+        }
+    }
+
+    /// Print a summary of the given parameter file, which is named `{parameter}.{kind}`, for the given network.
+    pub fn inspect(file: &Path, network: &str) -> Result<String> {
+        let filename = file.file_name().and_then(|filename| filename.to_str()).unwrap_or_default();
+        let mut components = filename.split('.');
+        let (parameter, kind) = match (components.next(), components.next()) {
+            (Some(parameter), Some(kind)) => (parameter, kind),
+            _ => return Err(anyhow!("Expected a parameter file named `{{parameter}}.{{kind}}`, found {}", filename)),
+        };
+        let bytes = fs::read(file)?;
+
+        let summary = match (parameter, network) {
+            ("universal", "testnet1") => return Err(anyhow!("Testnet1 does not support a universal SRS")),
+            ("universal", "testnet2") => {
+                let srs =
+                    <<Testnet2 as Network>::ProgramSNARK as SNARK>::UniversalSetupParameters::read_le(&bytes[..])?;
+                vec![("Max Degree", srs.max_degree()), ("Degree Bounds", srs.supported_degree_bounds().len())]
+            }
+            (_, "testnet1") => Self::summarize::<Testnet1>(parameter, kind, &bytes)?,
+            (_, "testnet2") => Self::summarize::<Testnet2>(parameter, kind, &bytes)?,
+            _ => return Err(anyhow!("Invalid network {}", network)),
+        };
+
+        let mut output =
+            format!("{} {} key ({} bytes, checksum {})\n", parameter, kind, bytes.len(), Self::checksum(&bytes));
+        for (name, value) in summary {
+            output += &format!("{} {}\n", format!("{:>18}", name).cyan().bold(), value);
+        }
+        Ok(output)
+    }
+
+    /// Returns the summary of the given key bytes, for the given parameter and kind of key.
+    fn summarize<N: Network>(parameter: &str, kind: &str, bytes: &[u8]) -> Result<Vec<(&'static str, usize)>>
+    where
+        <N::InnerSNARK as SNARK>::ProvingKey: KeySummary,
+        <N::InnerSNARK as SNARK>::VerifyingKey: KeySummary,
+        <N::ProgramSNARK as SNARK>::ProvingKey: KeySummary,
+        <N::ProgramSNARK as SNARK>::VerifyingKey: KeySummary,
+        <N::PoSWSNARK as SNARK>::ProvingKey: KeySummary,
+        <N::PoSWSNARK as SNARK>::VerifyingKey: KeySummary,
+    {
+        Ok(match (parameter, kind) {
+            ("inner", "proving") => <N::InnerSNARK as SNARK>::ProvingKey::read_le(bytes)?.summary(),
+            ("inner", "verifying") => <N::InnerSNARK as SNARK>::VerifyingKey::read_le(bytes)?.summary(),
+            ("noop", "proving") => <N::ProgramSNARK as SNARK>::ProvingKey::read_le(bytes)?.summary(),
+            ("noop", "verifying") => <N::ProgramSNARK as SNARK>::VerifyingKey::read_le(bytes)?.summary(),
+            ("posw", "proving") => <N::PoSWSNARK as SNARK>::ProvingKey::read_le(bytes)?.summary(),
+            ("posw", "verifying") => <N::PoSWSNARK as SNARK>::VerifyingKey::read_le(bytes)?.summary(),
+            _ => return Err(anyhow!("Unknown parameter file {}.{}", parameter, kind)),
+        })
+    }
+
+    /// Runs a universal SRS setup.
+    fn universal_setup<N: Network>(output: &Path) -> Result<String> {
+        let max_degree =
+            AHPForR1CS::<<N as Network>::InnerScalarField, MarlinHidingMode>::max_degree(2000000, 4000000, 8000000)
+                .map_err(|error| anyhow!("{:?}", error))?;
+        let universal_srs = <<N as Network>::ProgramSNARK as SNARK>::universal_setup(&max_degree, &mut thread_rng())?;
+        let universal_srs = universal_srs.to_bytes_le()?;
+
+        let universal_checksum = Self::checksum(&universal_srs);
+        let universal_metadata = json!({
+            "srs_checksum": universal_checksum,
+            "srs_size": universal_srs.len()
+        });
+
+        fs::write(output.join("universal.metadata"), serde_json::to_vec_pretty(&universal_metadata)?)?;
+        fs::write(output.join(Self::versioned_filename("universal.srs", &universal_checksum)), &universal_srs)?;
+
+        Ok(serde_json::to_string_pretty(&universal_metadata)?)
+    }
+
+    /// Runs the noop circuit setup.
+    fn noop_setup<N: Network>(output: &Path) -> Result<String> {
+        let (proving_key, verifying_key) = <N::ProgramSNARK as SNARK>::setup(
+            &SynthesizedCircuit::<N>::Noop(Default::default()),
+            &mut *N::program_srs(&mut thread_rng()).borrow_mut(),
+        )?;
+
+        let noop_function_id = hex::encode(<N as Network>::function_id(&verifying_key)?.to_bytes_le()?);
+        let noop_proving_key = proving_key.to_bytes_le()?;
+        let noop_verifying_key = verifying_key.to_bytes_le()?;
+
+        let noop_metadata = json!({
+            "proving_checksum": Self::checksum(&noop_proving_key),
+            "proving_size": noop_proving_key.len(),
+            "verifying_checksum": Self::checksum(&noop_verifying_key),
+            "verifying_size": noop_verifying_key.len(),
+            "circuit_id": noop_function_id,
+        });
+
+        fs::write(output.join("noop.metadata"), serde_json::to_vec_pretty(&noop_metadata)?)?;
+        fs::write(output.join("noop.proving"), &noop_proving_key)?;
+        fs::write(output.join("noop.verifying"), &noop_verifying_key)?;
+
+        Ok(serde_json::to_string_pretty(&noop_metadata)?)
+    }
+
+    /// Runs the inner circuit setup.
+    fn inner_setup<N: Network>(output: &Path) -> Result<String> {
+        let (inner_proving_key, inner_verifying_key) =
+            N::InnerSNARK::setup(&InnerCircuit::<N>::blank(), &mut SRS::CircuitSpecific(&mut thread_rng()))?;
+
+        let inner_circuit_id =
+            hex::encode(N::inner_circuit_id_crh().hash_bits(&inner_verifying_key.to_minimal_bits())?.to_bytes_le()?);
+        let inner_proving_key = inner_proving_key.to_bytes_le()?;
+        let inner_proving_checksum = Self::checksum(&inner_proving_key);
+        let inner_verifying_key = inner_verifying_key.to_bytes_le()?;
+
+        let inner_metadata = json!({
+            "proving_checksum": inner_proving_checksum,
+            "proving_size": inner_proving_key.len(),
+            "verifying_checksum": Self::checksum(&inner_verifying_key),
+            "verifying_size": inner_verifying_key.len(),
+            "circuit_id": inner_circuit_id
+        });
+
+        fs::write(output.join("inner.metadata"), serde_json::to_vec_pretty(&inner_metadata)?)?;
+        fs::write(output.join(Self::versioned_filename("inner.proving", &inner_proving_checksum)), &inner_proving_key)?;
+        fs::write(output.join("inner.verifying"), &inner_verifying_key)?;
+
+        Ok(serde_json::to_string_pretty(&inner_metadata)?)
+    }
+
+    /// Runs the PoSW circuit setup.
+    fn posw_setup<N: Network>(output: &Path) -> Result<String> {
+        let max_degree =
+            AHPForR1CS::<<N as Network>::InnerScalarField, MarlinHidingMode>::max_degree(40000, 40000, 60000)
+                .map_err(|error| anyhow!("{:?}", error))?;
+        let universal_srs = <<N as Network>::PoSWSNARK as SNARK>::universal_setup(&max_degree, &mut thread_rng())?;
+
+        let posw = <N::PoSW as PoSWScheme<N>>::setup::<ThreadRng>(&mut SRS::<ThreadRng, _>::Universal(&universal_srs))?;
+
+        let posw_proving_key =
+            posw.proving_key().as_ref().ok_or_else(|| anyhow!("The PoSW proving key is missing"))?.to_bytes_le()?;
+        let posw_proving_checksum = Self::checksum(&posw_proving_key);
+        let posw_verifying_key = posw.verifying_key().to_bytes_le()?;
+
+        let posw_metadata = json!({
+            "proving_checksum": posw_proving_checksum,
+            "proving_size": posw_proving_key.len(),
+            "verifying_checksum": Self::checksum(&posw_verifying_key),
+            "verifying_size": posw_verifying_key.len(),
+        });
+
+        fs::write(output.join("posw.metadata"), serde_json::to_vec_pretty(&posw_metadata)?)?;
+        fs::write(output.join(Self::versioned_filename("posw.proving", &posw_proving_checksum)), &posw_proving_key)?;
+        fs::write(output.join("posw.verifying"), &posw_verifying_key)?;
+
+        Ok(serde_json::to_string_pretty(&posw_metadata)?)
+    }
+
+    fn checksum(bytes: &[u8]) -> String {
+        hex::encode(sha256(bytes))
+    }
+
+    fn versioned_filename(filename: &str, checksum: &str) -> String {
+        match checksum.get(0..7) {
+            Some(sum) => format!("{}.{}", filename, sum),
+            _ => filename.to_string(),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::dpc::{devnet::Devnet, testnet1::Testnet1, testnet2::Testnet2, Network, Prover, ProverServer};
+
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+
+/// A local prover service, which proves authorizations from light clients over HTTP.
+pub struct ProverService;
+
+impl ProverService {
+    /// Serve authorizations for the given network on the given address, until the process is stopped.
+    pub fn serve(network: &str, address: &str) -> Result<String> {
+        match network {
+            "devnet" => Self::serve_for::<Devnet>(address),
+            "testnet1" => Self::serve_for::<Testnet1>(address),
+            "testnet2" => Self::serve_for::<Testnet2>(address),
+            _ => Err(anyhow!("Invalid network {}", network)),
+        }
+    }
+
+    fn serve_for<N: Network>(address: &str) -> Result<String> {
+        let server = ProverServer::bind(address, Prover::<N>::new())?;
+        eprintln!("{} {} on http://{}/prove", "Serving".cyan().bold(), N::NETWORK_NAME, server.local_addr()?);
+
+        loop {
+            // A failed authorization is reported to its client, and does not stop the service.
+            if let Err(error) = server.accept() {
+                eprintln!("Failed to accept a connection: {}", error);
+            }
+        }
+    }
+}
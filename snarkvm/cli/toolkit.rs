@@ -0,0 +1,136 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    dpc::{testnet2::Testnet2, Account, Address, DecryptionKey, Network, PrivateKey, Record, Transaction, ViewKey},
+    utilities::{FromBytes, ToBytes},
+};
+
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use core::str::FromStr;
+use rand::thread_rng;
+
+type AccountSignature = <Testnet2 as Network>::AccountSignature;
+type RecordCiphertext = <Testnet2 as Network>::RecordCiphertext;
+
+/// Offline operations on Testnet2 accounts, records and transactions.
+pub struct Toolkit;
+
+impl Toolkit {
+    /// Generate a new account.
+    pub fn new_account() -> Result<String> {
+        Ok(Self::format_account(&Account::<Testnet2>::new(&mut thread_rng())))
+    }
+
+    /// Derive the account of the given private key.
+    pub fn account_from_private_key(private_key: &str) -> Result<String> {
+        let private_key = PrivateKey::<Testnet2>::from_str(private_key)?;
+        Ok(Self::format_account(&Account::from(private_key)))
+    }
+
+    /// Sign the given message with the given private key.
+    pub fn sign(private_key: &str, message: &str) -> Result<String> {
+        let private_key = PrivateKey::<Testnet2>::from_str(private_key)?;
+        let signature = private_key.sign(message.as_bytes(), &mut thread_rng())?;
+        Ok(signature.to_string())
+    }
+
+    /// Verify the given signature of the given message against the given address.
+    pub fn verify(address: &str, signature: &str, message: &str) -> Result<String> {
+        let address = Address::<Testnet2>::from_str(address)?;
+        let signature = AccountSignature::from_str(signature)?;
+        match address.verify_signature(message.as_bytes(), &signature)? {
+            true => Ok("The signature is valid".green().bold().to_string()),
+            false => Err(anyhow!("The signature is invalid")),
+        }
+    }
+
+    /// Decrypt the given record ciphertext with the given view key.
+    pub fn decrypt_record(view_key: &str, ciphertext: &str) -> Result<String> {
+        let view_key = ViewKey::<Testnet2>::from_str(view_key)?;
+        let ciphertext = RecordCiphertext::from_str(ciphertext)?;
+        if !ciphertext.is_owner(&view_key) {
+            return Err(anyhow!("The record ciphertext does not belong to the given view key"));
+        }
+
+        let record = Record::decrypt(&DecryptionKey::from(&view_key), &ciphertext)?;
+        Ok(record.to_string())
+    }
+
+    /// Print the contents of the given transaction.
+    pub fn inspect_transaction(transaction: &str) -> Result<String> {
+        let transaction = Self::parse_transaction(transaction)?;
+
+        let mut output = Self::format_field("Transaction ID", transaction.transaction_id());
+        output += &Self::format_field("Ledger Root", transaction.ledger_root());
+        output += &Self::format_field("Value Balance", transaction.value_balance());
+
+        for (index, transition) in transaction.transitions().iter().enumerate() {
+            output += &format!("\n{}\n", format!("Transition {}", index).bold());
+            output += &Self::format_field("Transition ID", transition.transition_id());
+            output += &Self::format_field("Value Balance", transition.value_balance());
+            for serial_number in transition.serial_numbers() {
+                output += &Self::format_field("Serial Number", serial_number);
+            }
+            for commitment in transition.commitments() {
+                output += &Self::format_field("Commitment", commitment);
+            }
+            for event in transition.events() {
+                output += &Self::format_field("Event", event);
+            }
+        }
+        Ok(output)
+    }
+
+    /// Verify the given transaction.
+    pub fn verify_transaction(transaction: &str) -> Result<String> {
+        let transaction = Self::parse_transaction(transaction)?;
+        match transaction.is_valid() {
+            true => Ok(format!("Transaction {} is valid", transaction.transaction_id()).green().bold().to_string()),
+            false => Err(anyhow!("Transaction {} is invalid", transaction.transaction_id())),
+        }
+    }
+
+    /// Parse a transaction from its JSON string or hex-encoded bytes.
+    fn parse_transaction(transaction: &str) -> Result<Transaction<Testnet2>> {
+        let transaction = transaction.trim();
+        match transaction.starts_with('{') {
+            true => Transaction::from_str(transaction),
+            false => {
+                let bytes = hex::decode(transaction.trim_start_matches("0x"))
+                    .map_err(|error| anyhow!("The transaction is neither JSON nor hex: {}", error))?;
+                let transaction = Transaction::<Testnet2>::from_bytes_le(&bytes)?;
+                // Ensure the transaction bytes are canonical.
+                match transaction.to_bytes_le()? == bytes {
+                    true => Ok(transaction),
+                    false => Err(anyhow!("The transaction bytes contain trailing data")),
+                }
+            }
+        }
+    }
+
+    fn format_account(account: &Account<Testnet2>) -> String {
+        let mut output = Self::format_field("Private Key", account.private_key());
+        output += &Self::format_field("View Key", account.view_key());
+        output += &Self::format_field("Address", account.address());
+        output
+    }
+
+    fn format_field<T: core::fmt::Display>(name: &str, value: T) -> String {
+        format!("{} {}\n", format!("{:>14}", name).cyan().bold(), value)
+    }
+}
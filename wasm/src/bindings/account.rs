@@ -0,0 +1,153 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::bindings::to_js_error;
+use snarkvm_dpc::{testnet2::Testnet2, Network};
+
+use core::str::FromStr;
+use rand::thread_rng;
+use wasm_bindgen::prelude::*;
+
+/// An account private key.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct PrivateKey {
+    pub(crate) private_key: snarkvm_dpc::PrivateKey<Testnet2>,
+}
+
+#[wasm_bindgen]
+impl PrivateKey {
+    /// Samples a new account private key.
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self { private_key: snarkvm_dpc::PrivateKey::new(&mut thread_rng()) }
+    }
+
+    /// Parses an account private key from its Bech32 string.
+    pub fn from_string(private_key: &str) -> Result<PrivateKey, JsValue> {
+        Ok(Self { private_key: snarkvm_dpc::PrivateKey::from_str(private_key).map_err(to_js_error)? })
+    }
+
+    /// Returns the Bech32 string of the account private key.
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.private_key.to_string()
+    }
+
+    /// Returns the account view key of the account private key.
+    pub fn to_view_key(&self) -> ViewKey {
+        ViewKey { view_key: snarkvm_dpc::ViewKey::from(&self.private_key) }
+    }
+
+    /// Returns the account address of the account private key.
+    pub fn to_address(&self) -> Address {
+        Address { address: self.private_key.to_address() }
+    }
+
+    /// Signs the given message with the account private key.
+    pub fn sign(&self, message: &[u8]) -> Result<Signature, JsValue> {
+        Ok(Signature { signature: self.private_key.sign(message, &mut thread_rng()).map_err(to_js_error)? })
+    }
+}
+
+/// An account view key.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct ViewKey {
+    pub(crate) view_key: snarkvm_dpc::ViewKey<Testnet2>,
+}
+
+#[wasm_bindgen]
+impl ViewKey {
+    /// Derives the account view key of the given account private key.
+    pub fn from_private_key(private_key: &PrivateKey) -> ViewKey {
+        private_key.to_view_key()
+    }
+
+    /// Parses an account view key from its Bech32 string.
+    pub fn from_string(view_key: &str) -> Result<ViewKey, JsValue> {
+        Ok(Self { view_key: snarkvm_dpc::ViewKey::from_str(view_key).map_err(to_js_error)? })
+    }
+
+    /// Returns the Bech32 string of the account view key.
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.view_key.to_string()
+    }
+
+    /// Returns the account address of the account view key.
+    pub fn to_address(&self) -> Address {
+        Address { address: snarkvm_dpc::Address::from(&self.view_key) }
+    }
+}
+
+/// An account address.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Address {
+    pub(crate) address: snarkvm_dpc::Address<Testnet2>,
+}
+
+#[wasm_bindgen]
+impl Address {
+    /// Derives the account address of the given account private key.
+    pub fn from_private_key(private_key: &PrivateKey) -> Address {
+        private_key.to_address()
+    }
+
+    /// Derives the account address of the given account view key.
+    pub fn from_view_key(view_key: &ViewKey) -> Address {
+        view_key.to_address()
+    }
+
+    /// Parses an account address from its Bech32 string.
+    pub fn from_string(address: &str) -> Result<Address, JsValue> {
+        Ok(Self { address: snarkvm_dpc::Address::from_str(address).map_err(to_js_error)? })
+    }
+
+    /// Returns the Bech32 string of the account address.
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.address.to_string()
+    }
+
+    /// Returns `true` if the given signature is valid for the given message and this account address.
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> Result<bool, JsValue> {
+        self.address.verify_signature(message, &signature.signature).map_err(to_js_error)
+    }
+}
+
+/// An account signature.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Signature {
+    pub(crate) signature: <Testnet2 as Network>::AccountSignature,
+}
+
+#[wasm_bindgen]
+impl Signature {
+    /// Parses an account signature from its Bech32 string.
+    pub fn from_string(signature: &str) -> Result<Signature, JsValue> {
+        Ok(Self { signature: FromStr::from_str(signature).map_err(to_js_error)? })
+    }
+
+    /// Returns the Bech32 string of the account signature.
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.signature.to_string()
+    }
+}
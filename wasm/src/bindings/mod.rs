@@ -0,0 +1,34 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+//! JavaScript bindings for Aleo accounts, records and transactions on Testnet2.
+
+pub mod account;
+pub use account::*;
+
+pub mod record;
+pub use record::*;
+
+pub mod transaction;
+pub use transaction::*;
+
+use core::fmt::Display;
+use wasm_bindgen::JsValue;
+
+/// Converts the given error into a JavaScript exception.
+pub(crate) fn to_js_error<E: Display>(error: E) -> JsValue {
+    JsValue::from_str(&error.to_string())
+}
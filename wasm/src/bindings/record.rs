@@ -0,0 +1,110 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::bindings::{to_js_error, Address, ViewKey};
+use snarkvm_dpc::{testnet2::Testnet2, DecryptionKey, Network};
+
+use core::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+/// An encrypted record.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct RecordCiphertext {
+    pub(crate) ciphertext: <Testnet2 as Network>::RecordCiphertext,
+}
+
+#[wasm_bindgen]
+impl RecordCiphertext {
+    /// Parses a record ciphertext from its Bech32 string.
+    pub fn from_string(ciphertext: &str) -> Result<RecordCiphertext, JsValue> {
+        Ok(Self { ciphertext: FromStr::from_str(ciphertext).map_err(to_js_error)? })
+    }
+
+    /// Returns the Bech32 string of the record ciphertext.
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.ciphertext.to_string()
+    }
+
+    /// Returns the commitment of the record ciphertext.
+    pub fn commitment(&self) -> String {
+        self.ciphertext.commitment().to_string()
+    }
+
+    /// Returns `true` if the record ciphertext belongs to the given account view key.
+    pub fn is_owner(&self, view_key: &ViewKey) -> bool {
+        self.ciphertext.is_owner(&view_key.view_key)
+    }
+
+    /// Decrypts the record ciphertext with the given account view key.
+    pub fn decrypt(&self, view_key: &ViewKey) -> Result<Record, JsValue> {
+        let decryption_key = DecryptionKey::from(&view_key.view_key);
+        let record = snarkvm_dpc::Record::decrypt(&decryption_key, &self.ciphertext).map_err(to_js_error)?;
+        Ok(Record { record })
+    }
+}
+
+/// A decrypted record.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Record {
+    pub(crate) record: snarkvm_dpc::Record<Testnet2>,
+}
+
+#[wasm_bindgen]
+impl Record {
+    /// Parses a record from its JSON string.
+    pub fn from_string(record: &str) -> Result<Record, JsValue> {
+        Ok(Self { record: snarkvm_dpc::Record::from_str(record).map_err(to_js_error)? })
+    }
+
+    /// Returns the JSON string of the record.
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.record.to_string()
+    }
+
+    /// Returns `true` if the record is a dummy.
+    pub fn is_dummy(&self) -> bool {
+        self.record.is_dummy()
+    }
+
+    /// Returns the account address of the record owner.
+    pub fn owner(&self) -> Address {
+        Address { address: self.record.owner() }
+    }
+
+    /// Returns the value of the record, in Aleo bytes (AB).
+    pub fn value(&self) -> i64 {
+        self.record.value().0
+    }
+
+    /// Returns the program ID of the record.
+    pub fn program_id(&self) -> String {
+        self.record.program_id().to_string()
+    }
+
+    /// Returns the commitment of the record.
+    pub fn commitment(&self) -> String {
+        self.record.commitment().to_string()
+    }
+
+    /// Returns the record ciphertext of the record.
+    pub fn to_ciphertext(&self) -> RecordCiphertext {
+        RecordCiphertext { ciphertext: self.record.ciphertext().clone() }
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::bindings::{to_js_error, Record, RecordCiphertext, ViewKey};
+use snarkvm_dpc::{testnet2::Testnet2, DecryptionKey};
+use snarkvm_utilities::{FromBytes, ToBytes};
+
+use core::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+/// A transaction.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Transaction {
+    pub(crate) transaction: snarkvm_dpc::Transaction<Testnet2>,
+}
+
+#[wasm_bindgen]
+impl Transaction {
+    /// Parses a transaction from its JSON string.
+    pub fn from_string(transaction: &str) -> Result<Transaction, JsValue> {
+        Ok(Self { transaction: snarkvm_dpc::Transaction::from_str(transaction).map_err(to_js_error)? })
+    }
+
+    /// Parses a transaction from its little-endian bytes.
+    pub fn from_bytes(transaction: &[u8]) -> Result<Transaction, JsValue> {
+        Ok(Self { transaction: snarkvm_dpc::Transaction::from_bytes_le(transaction).map_err(to_js_error)? })
+    }
+
+    /// Returns the JSON string of the transaction.
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.transaction.to_string()
+    }
+
+    /// Returns the little-endian bytes of the transaction.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        self.transaction.to_bytes_le().map_err(to_js_error)
+    }
+
+    /// Returns `true` if the transaction is well-formed.
+    pub fn is_valid(&self) -> bool {
+        self.transaction.is_valid()
+    }
+
+    /// Returns the ID of the transaction.
+    pub fn transaction_id(&self) -> String {
+        self.transaction.transaction_id().to_string()
+    }
+
+    /// Returns the ledger root of the transaction.
+    pub fn ledger_root(&self) -> String {
+        self.transaction.ledger_root().to_string()
+    }
+
+    /// Returns the value balance of the transaction, in Aleo bytes (AB).
+    pub fn value_balance(&self) -> i64 {
+        self.transaction.value_balance().0
+    }
+
+    /// Returns the record ciphertexts of the transaction.
+    pub fn ciphertexts(&self) -> Box<[JsValue]> {
+        self.transaction
+            .ciphertexts()
+            .map(|ciphertext| RecordCiphertext { ciphertext: ciphertext.clone() }.into())
+            .collect()
+    }
+
+    /// Returns the records of the transaction that belong to the given account view key.
+    pub fn to_decrypted_records(&self, view_key: &ViewKey) -> Box<[JsValue]> {
+        let decryption_key = DecryptionKey::from(&view_key.view_key);
+        self.transaction.to_decrypted_records(&decryption_key).map(|record| Record { record }.into()).collect()
+    }
+}
@@ -28,6 +28,9 @@ pub use snarkvm_algorithms::{
     snark::*,
 };
 
+#[cfg(feature = "dpc")]
+pub mod bindings;
+
 #[cfg(feature = "curves")]
 pub use snarkvm_curves::{bls12_377::*, edwards_bls12::*, edwards_bw6::*};
 
@@ -14,7 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use snarkvm_dpc::{testnet2::Testnet2, Account, Address, PrivateKey, ViewKey};
+use crate::bindings;
+use snarkvm_dpc::{testnet2::Testnet2, Account, Address, Network, PrivateKey, Record, ViewKey};
+use snarkvm_utilities::ToBytes;
 
 use core::str::FromStr;
 use wasm_bindgen_test::*;
@@ -55,3 +57,88 @@ fn test_account_sign() {
         assert!(result.unwrap(), "Signature is invalid");
     }
 }
+
+#[wasm_bindgen_test]
+fn test_bindings_account() {
+    const ALEO_TESTNET2_PRIVATE_KEY: &str = "APrivateKey1zkp8cC4jgHEBnbtu3xxs1Ndja2EMizcvTRDq5Nikdkukg1p";
+    const ALEO_TESTNET2_VIEW_KEY: &str = "AViewKey1iAf6a7fv6ELA4ECwAth1hDNUJJNNoWNThmREjpybqder";
+    const ALEO_TESTNET2_ADDRESS: &str = "aleo1d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah";
+
+    let private_key = bindings::PrivateKey::from_string(ALEO_TESTNET2_PRIVATE_KEY).unwrap();
+    assert_eq!(ALEO_TESTNET2_PRIVATE_KEY, private_key.to_string());
+
+    let view_key = private_key.to_view_key();
+    assert_eq!(ALEO_TESTNET2_VIEW_KEY, view_key.to_string());
+    assert_eq!(ALEO_TESTNET2_VIEW_KEY, bindings::ViewKey::from_string(ALEO_TESTNET2_VIEW_KEY).unwrap().to_string());
+
+    let address = view_key.to_address();
+    assert_eq!(ALEO_TESTNET2_ADDRESS, address.to_string());
+    assert_eq!(ALEO_TESTNET2_ADDRESS, private_key.to_address().to_string());
+    assert_eq!(ALEO_TESTNET2_ADDRESS, bindings::Address::from_string(ALEO_TESTNET2_ADDRESS).unwrap().to_string());
+
+    assert!(bindings::PrivateKey::from_string(ALEO_TESTNET2_VIEW_KEY).is_err());
+    assert!(bindings::Address::from_string(ALEO_TESTNET2_PRIVATE_KEY).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_bindings_sign() {
+    let private_key = bindings::PrivateKey::new();
+    let address = private_key.to_address();
+
+    let signature = private_key.sign(b"hello world!").unwrap();
+    assert!(address.verify(b"hello world!", &signature).unwrap());
+    assert!(!address.verify(b"goodbye world!", &signature).unwrap());
+
+    // Ensure the signature round trips through its string.
+    let signature = bindings::Signature::from_string(&signature.to_string()).unwrap();
+    assert!(address.verify(b"hello world!", &signature).unwrap());
+    assert!(!bindings::PrivateKey::new().to_address().verify(b"hello world!", &signature).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_bindings_record() {
+    let rng = &mut rand::thread_rng();
+    let account = Account::<Testnet2>::new(rng);
+    let record = Record::new_noop(account.address(), rng).unwrap();
+
+    let view_key = bindings::PrivateKey::from_string(&account.private_key().to_string()).unwrap().to_view_key();
+    let other_view_key = bindings::PrivateKey::new().to_view_key();
+
+    // Ensure only the owner can decrypt the record ciphertext.
+    let ciphertext = bindings::RecordCiphertext::from_string(&record.ciphertext().to_string()).unwrap();
+    assert!(ciphertext.is_owner(&view_key));
+    assert!(!ciphertext.is_owner(&other_view_key));
+    assert!(ciphertext.decrypt(&other_view_key).is_err());
+
+    let decrypted = ciphertext.decrypt(&view_key).unwrap();
+    assert_eq!(record.to_string(), decrypted.to_string());
+    assert_eq!(account.address().to_string(), decrypted.owner().to_string());
+    assert_eq!(record.commitment().to_string(), decrypted.commitment());
+    assert_eq!(ciphertext.commitment(), decrypted.commitment());
+    assert_eq!(0, decrypted.value());
+    assert!(decrypted.is_dummy());
+
+    // Ensure the record round trips through its string.
+    assert_eq!(record.to_string(), bindings::Record::from_string(&record.to_string()).unwrap().to_string());
+}
+
+#[wasm_bindgen_test]
+fn test_bindings_transaction() {
+    let expected = Testnet2::genesis_block().to_coinbase_transaction().unwrap();
+
+    // Ensure the transaction round trips through its bytes and string.
+    let transaction = bindings::Transaction::from_bytes(&expected.to_bytes_le().unwrap()).unwrap();
+    assert!(transaction.is_valid());
+    assert_eq!(expected.transaction_id().to_string(), transaction.transaction_id());
+    assert_eq!(expected.ledger_root().to_string(), transaction.ledger_root());
+    assert_eq!(expected.value_balance().0, transaction.value_balance());
+    assert_eq!(expected.to_bytes_le().unwrap(), transaction.to_bytes().unwrap());
+
+    let transaction = bindings::Transaction::from_string(&transaction.to_string()).unwrap();
+    assert_eq!(expected.transaction_id().to_string(), transaction.transaction_id());
+    assert!(bindings::Transaction::from_bytes(&[0u8; 32]).is_err());
+
+    // Ensure the records are not decrypted for an unrelated account.
+    assert_eq!(expected.ciphertexts().count(), transaction.ciphertexts().len());
+    assert!(transaction.to_decrypted_records(&bindings::PrivateKey::new().to_view_key()).is_empty());
+}